@@ -0,0 +1,54 @@
+//! Compares updating projectiles boxed in `ProjectileInstance`
+//! with the dedicated system of `ProjectilePlugin::with_typed`.
+//!
+//! Run with `cargo bench --bench typed`.
+
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_javelin::{Projectile, ProjectileContext, ProjectileInstance, ProjectilePlugin};
+
+const FRAMES: u32 = 100;
+
+struct Mover(Vec3);
+
+impl Projectile for Mover {
+    fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+        cx.transform_mut().translation += self.0 * dt;
+    }
+}
+
+/// Average time of a frame updating `count` projectiles.
+fn frame_time(count: usize, typed: bool) -> Duration {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        ProjectilePlugin::default().with_typed::<Mover>(),
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        16,
+    )));
+    for i in 0..count {
+        let mover = Mover(Vec3::new(i as f32, 1., 0.));
+        if typed {
+            app.world_mut().spawn(ProjectileInstance::typed(mover));
+        } else {
+            app.world_mut().spawn(ProjectileInstance::new(mover));
+        }
+    }
+    app.update();
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    start.elapsed() / FRAMES
+}
+
+fn main() {
+    for count in [1_000, 10_000, 100_000] {
+        let dynamic = frame_time(count, false);
+        let typed = frame_time(count, true);
+        println!("{count:>6} projectiles: dynamic {dynamic:>10.2?}, typed {typed:>10.2?}");
+    }
+}
@@ -1,16 +1,10 @@
 use bevy::{math::VectorSpace, prelude::*};
-use bevy_javelin::{
-    Projectile, ProjectileContext, ProjectileInstance, ProjectilePlugin,
-    loading::{AddMat3, AddMesh3},
-    spawning::{ProjectileSpawning, SpawnRate},
-    util::{PhysicsExt, ProjectileRng},
-};
-use fastrand::Rng;
+use bevy_javelin::prelude::*;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(ProjectilePlugin)
+        .add_plugins(ProjectilePlugin::default())
         .insert_resource(AmbientLight {
             brightness: 800.,
             ..Default::default()
@@ -45,18 +39,21 @@ fn setup(
                     velocity: (rng.random_circle() * 4.).extend(10.0).xzy(),
                     rng: rng.fork(),
                 }
-                .with_spawner(SpawnRate::new(12.).into_spawner_world(|_, cx| {
-                    (
-                        MyProjectile3,
-                        Transform::from_translation(cx.global_transform().translation()),
-                        AddMesh3(Mesh::from(Sphere::new(0.1).mesh())),
-                        AddMat3(StandardMaterial {
-                            base_color: Color::srgb(0., 1., 0.),
-                            alpha_mode: AlphaMode::Blend,
-                            ..Default::default()
-                        }),
-                    )
-                })),
+                .with_spawner(
+                    SpawnRate::new(12.)
+                        .into_spawner_world(|_, _| {
+                            (
+                                MyProjectile3,
+                                AddMesh3(Mesh::from(Sphere::new(0.1).mesh())),
+                                AddMat3(StandardMaterial {
+                                    base_color: Color::srgb(0., 1., 0.),
+                                    alpha_mode: AlphaMode::Blend,
+                                    ..Default::default()
+                                }),
+                            )
+                        })
+                        .relative_to_spawner(),
+                ),
                 AddMesh3(Mesh::from(Sphere::new(0.5).mesh())),
                 AddMat3(StandardMaterial {
                     base_color: Color::srgb(0., 1., 1.),
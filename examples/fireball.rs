@@ -9,23 +9,16 @@ use bevy::{
     prelude::*,
     render::render_resource::{AsBindGroup, ShaderRef},
 };
-use bevy_javelin::{
-    Projectile, ProjectileBundle, ProjectileContext, ProjectileInstance, ProjectilePlugin,
-    ProjectileSpawner,
-    loading::{AddMat3, AddMesh3, LoadMesh3},
-    spawning::{ProjectileSpawning, SpawnRate},
-    util::{ConditionOnce, PhysicsExt, ProjectileRng},
-};
+use bevy_javelin::prelude::*;
 use bevy_texture_gen::{
     FbmPerlinImage, ImageBuilder, LazyImage, LoadLazyImageExt, VoronoiImage, lazy_image,
 };
-use fastrand::Rng;
 use ramp_gen::ramp;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(ProjectilePlugin)
+        .add_plugins(ProjectilePlugin::default())
         .insert_resource(AmbientLight {
             brightness: 800.,
             ..Default::default()
@@ -117,6 +110,7 @@ fn setup(
             MeshMaterial3d(materials.add(StandardMaterial::from_color(Srgba::BLUE))),
             Transform::from_xyz(-10., 1.25, 0.),
             Target,
+            HitTarget::new(0.5),
         ))
         .id();
 
@@ -183,6 +177,7 @@ impl ProjectileSpawner for FireballSpawner {
                     rng: self.rng.fork(),
                 },
                 AddMesh3(Sphere::new(0.5).mesh().into()),
+                ProjectileCollider::Sphere { radius: 0.5 },
                 AddMat3(ExtendedMaterial {
                     base: StandardMaterial {
                         base_color: (Srgba::new(8., 4., 0., 1.)).into(),
@@ -223,7 +218,7 @@ impl Projectile for HomingFireball {
         let target = transform.translation();
         cx.transform_mut().translation.move_near(target, dt * 6.);
         self.hit
-            .set(|| (cx.transform().translation - target).length_squared() < 0.5);
+            .set(|| cx.hits().iter().any(|hit| hit.entity == self.target));
         self.smoke_spawning.update(dt);
     }
 
@@ -252,6 +247,7 @@ impl ProjectileSpawner for HomingFireball {
                     rotation: self.rng.random_quat(),
                     scale: Vec3::splat(0.3),
                 },
+                UvAnimation::scroll(Vec2::ONE),
             )
         })
     }
@@ -264,13 +260,12 @@ impl Projectile for Smoke {
         3.
     }
 
-    fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+    fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
         let fac = cx.fac();
         cx.mat3d::<StandardMaterial>(|m| {
             let ramp = |x: f32| ramp!(clamp [0.2, Srgba::gray(0.8)], [0.4, Srgba::BLACK]);
             m.base_color = ramp(fac).into();
             m.base_color.set_alpha(1.0 - fac.clamp(0., 1.));
-            m.uv_transform.translation += dt
         });
         cx.transform_mut().scale = Vec3::splat(VectorSpace::lerp(0.3, 0.1, fac));
     }
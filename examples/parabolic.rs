@@ -1,15 +1,10 @@
 use bevy::{math::VectorSpace, prelude::*};
-use bevy_javelin::{
-    Projectile, ProjectileContext, ProjectileInstance, ProjectilePlugin,
-    loading::{AddMat3, AddMesh3},
-    spawning::{ProjectileSpawning, SpawnRate},
-    util::{PhysicsExt, ProjectileRng},
-};
+use bevy_javelin::prelude::*;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(ProjectilePlugin)
+        .add_plugins(ProjectilePlugin::default())
         .insert_resource(AmbientLight {
             brightness: 800.,
             ..Default::default()
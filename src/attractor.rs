@@ -0,0 +1,72 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        resource::Resource,
+        system::{Query, ResMut},
+        world::EntityMutExcept,
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+
+use crate::{DefaultProjectileBundle, ProjectileVelocity};
+
+/// Pulls projectiles with [`ProjectileVelocity`] towards this entity, or pushes them away if `strength` is negative,
+/// for black holes and magnets.
+///
+/// Acceleration is `strength * (1 - distance / radius) ^ falloff` towards the entity's [`GlobalTransform`]
+/// within `radius`, a `falloff` of `0` applies the same acceleration everywhere in range.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct ProjectileAttractor {
+    pub strength: f32,
+    pub radius: f32,
+    pub falloff: f32,
+}
+
+impl ProjectileAttractor {
+    /// Acceleration of a projectile at `position` by an attractor at `center`.
+    pub fn acceleration(&self, center: Vec3, position: Vec3) -> Vec3 {
+        let offset = center - position;
+        let distance = offset.length();
+        if distance >= self.radius || distance <= f32::EPSILON {
+            return Vec3::ZERO;
+        }
+        offset / distance * self.strength * (1. - distance / self.radius).powf(self.falloff)
+    }
+}
+
+/// Attractors of this frame.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct Attractors(Vec<(Vec3, ProjectileAttractor)>);
+
+pub(crate) fn collect_attractors(
+    mut attractors: ResMut<Attractors>,
+    query: Query<(&ProjectileAttractor, &GlobalTransform)>,
+) {
+    attractors.0.clear();
+    attractors.0.extend(
+        query
+            .iter()
+            .map(|(attractor, transform)| (transform.translation(), *attractor)),
+    );
+}
+
+/// Integrate [`ProjectileAttractor`]s into [`ProjectileVelocity`].
+pub(crate) fn apply_attractors(
+    entity_mut: &mut EntityMutExcept<DefaultProjectileBundle>,
+    attractors: &Attractors,
+    position: Vec3,
+    dt: f32,
+) {
+    if attractors.0.is_empty() {
+        return;
+    }
+    if let Some(mut velocity) = entity_mut.get_mut::<ProjectileVelocity>() {
+        velocity.0 += attractors
+            .0
+            .iter()
+            .map(|(center, attractor)| attractor.acceleration(*center, position))
+            .sum::<Vec3>()
+            * dt;
+    }
+}
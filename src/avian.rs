@@ -0,0 +1,77 @@
+//! Integration with [`avian3d`], enabled by the `avian` feature.
+//!
+//! [`CollisionStarted`] events involving a projectile are pushed as a [`Hit`] to its [`ProjectileHits`],
+//! which is inserted if missing, and delivered to [`Projectile::on_hit`](crate::Projectile::on_hit)
+//! on its next update.
+//!
+//! # Note
+//!
+//! Avian only sends [`CollisionStarted`] if either entity has [`CollisionEventsEnabled`](avian3d::prelude::CollisionEventsEnabled).
+
+use avian3d::prelude::CollisionStarted;
+use bevy::{
+    ecs::{
+        entity::Entity,
+        error::ignore,
+        event::EventReader,
+        query::With,
+        system::{Commands, Query},
+        world::EntityWorldMut,
+    },
+    transform::components::GlobalTransform,
+};
+
+use crate::{Hit, ProjectileHits, ProjectileInstance};
+
+type HitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GlobalTransform,
+        Option<&'static mut ProjectileHits>,
+    ),
+    With<ProjectileInstance>,
+>;
+
+/// Deliver [`CollisionStarted`] events to projectiles, `point` is the translation of the projectile.
+pub(crate) fn avian_hit_system(
+    mut commands: Commands,
+    mut events: EventReader<CollisionStarted>,
+    mut projectiles: HitQuery,
+) {
+    for CollisionStarted(a, b) in events.read() {
+        deliver_hit(&mut commands, &mut projectiles, *a, *b);
+        deliver_hit(&mut commands, &mut projectiles, *b, *a);
+    }
+}
+
+fn deliver_hit(
+    commands: &mut Commands,
+    projectiles: &mut HitQuery,
+    projectile: Entity,
+    target: Entity,
+) {
+    let Ok((transform, hits)) = projectiles.get_mut(projectile) else {
+        return;
+    };
+    let hit = Hit {
+        entity: target,
+        point: transform.translation(),
+    };
+    match hits {
+        Some(mut hits) => hits.push(hit),
+        None => {
+            commands.entity(projectile).queue_handled(
+                move |mut entity: EntityWorldMut| match entity.get_mut::<ProjectileHits>() {
+                    Some(mut hits) => hits.push(hit),
+                    None => {
+                        let mut hits = ProjectileHits::default();
+                        hits.push(hit);
+                        entity.insert(hits);
+                    }
+                },
+                ignore,
+            );
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::{
+    ecs::{component::Component, system::Query},
+    math::Quat,
+    render::camera::Camera,
+    transform::components::{GlobalTransform, Transform},
+};
+
+/// Rotates a projectile to face the active camera after [`projectile_update`](crate::projectile_update),
+/// so sprite-like effects such as smoke puffs don't need camera queries.
+///
+/// The local `+Z` axis faces the camera, matching [`vfx_quad`](crate::mesh::vfx_quad).
+/// Rotations set during update are overwritten. If multiple cameras are active, the one with the highest
+/// [`Camera::order`] is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component)]
+pub enum Billboard {
+    /// Align with the camera's rotation, always facing the screen.
+    #[default]
+    Spherical,
+    /// Only rotate around `Y` to face the camera's position, for upright effects like fire and trees.
+    Cylindrical,
+}
+
+pub(crate) fn billboard_system(
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut query: Query<(&Billboard, &mut Transform, &GlobalTransform)>,
+) {
+    let Some((_, camera)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+    let camera = camera.compute_transform();
+    for (billboard, mut transform, global_transform) in &mut query {
+        // Rotation of the parent, derived from the last propagated `GlobalTransform`.
+        let parent_rotation = global_transform.rotation() * transform.rotation.inverse();
+        let rotation = match billboard {
+            Billboard::Spherical => camera.rotation,
+            Billboard::Cylindrical => {
+                let direction = camera.translation - global_transform.translation();
+                Quat::from_rotation_y(direction.x.atan2(direction.z))
+            }
+        };
+        transform.rotation = parent_rotation.inverse() * rotation;
+    }
+}
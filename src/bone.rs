@@ -0,0 +1,127 @@
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        hierarchy::{ChildOf, Children},
+        name::Name,
+        system::{EntityCommands, Query, SystemParam},
+        world::EntityWorldMut,
+    },
+    log::warn,
+    transform::components::{GlobalTransform, Transform},
+};
+
+use crate::{ProjectileSpace, WorldSpaceChildOf};
+
+/// Find `entity` or its first descendant named `bone_name`.
+fn find_bone<'a>(
+    entity: Entity,
+    bone_name: &str,
+    get: &impl Fn(Entity) -> Option<(Option<&'a Name>, Option<&'a Children>)>,
+) -> Option<Entity> {
+    let (name, children) = get(entity)?;
+    if name.is_some_and(|name| name.as_str() == bone_name) {
+        return Some(entity);
+    }
+    children
+        .into_iter()
+        .flatten()
+        .find_map(|child| find_bone(*child, bone_name, get))
+}
+
+/// A [`SystemParam`] to look up bones, i.e. GLTF joints, of animated characters by [`Name`].
+#[derive(SystemParam)]
+pub struct Bones<'w, 's> {
+    query: Query<'w, 's, (Option<&'static Name>, Option<&'static Children>)>,
+}
+
+impl Bones<'_, '_> {
+    /// Find the first descendant of `entity` named `bone_name`, including itself.
+    ///
+    /// Returns [`None`] if not found, for example if the scene has not been spawned yet.
+    pub fn track_bone(&self, entity: Entity, bone_name: &str) -> Option<Entity> {
+        find_bone(entity, bone_name, &|e| self.query.get(e).ok())
+    }
+}
+
+/// Attach a projectile to a bone of an animated character,
+/// so muzzle flashes and hand-cast spells follow the character's animation.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// fn cast(mut commands: Commands, character: Single<Entity, With<Name>>, instance: ProjectileInstance) {
+///     commands
+///         .spawn(instance)
+///         .attach_to_bone(*character, "hand_r", ProjectileSpace::Local);
+/// }
+/// ```
+pub trait AttachToBoneExt {
+    /// Attach to the first descendant of `character` named `bone_name`, including itself.
+    ///
+    /// * [`ProjectileSpace::Local`]: Inserts [`ChildOf`], the current [`Transform`] is used as an offset from the bone.
+    /// * [`ProjectileSpace::World`]: Inserts [`WorldSpaceChildOf`] and moves the projectile to the bone,
+    ///   the projectile does not follow the bone afterwards.
+    ///
+    /// Logs a warning if the bone is not found.
+    fn attach_to_bone(
+        &mut self,
+        character: Entity,
+        bone_name: impl Into<Cow<'static, str>>,
+        space: ProjectileSpace,
+    ) -> &mut Self;
+}
+
+impl AttachToBoneExt for EntityWorldMut<'_> {
+    fn attach_to_bone(
+        &mut self,
+        character: Entity,
+        bone_name: impl Into<Cow<'static, str>>,
+        space: ProjectileSpace,
+    ) -> &mut Self {
+        let bone_name = bone_name.into();
+        let found = self.world_scope(|world| {
+            let bone = find_bone(character, &bone_name, &|e| {
+                let entity = world.get_entity(e).ok()?;
+                Some((entity.get::<Name>(), entity.get::<Children>()))
+            })?;
+            Some((bone, world.get::<GlobalTransform>(bone).copied()))
+        });
+        let Some((bone, global_transform)) = found else {
+            warn!("Bone {bone_name} not found on {character}.");
+            return self;
+        };
+        match space {
+            ProjectileSpace::Local => {
+                self.insert(ChildOf(bone));
+            }
+            ProjectileSpace::World => {
+                let global_transform = global_transform.unwrap_or_default();
+                let transform = self.get::<Transform>().copied().unwrap_or_default();
+                self.insert((
+                    global_transform
+                        .mul_transform(transform)
+                        .compute_transform(),
+                    WorldSpaceChildOf(bone),
+                ));
+            }
+        }
+        self
+    }
+}
+
+impl AttachToBoneExt for EntityCommands<'_> {
+    fn attach_to_bone(
+        &mut self,
+        character: Entity,
+        bone_name: impl Into<Cow<'static, str>>,
+        space: ProjectileSpace,
+    ) -> &mut Self {
+        let bone_name = bone_name.into();
+        self.queue(move |mut x: EntityWorldMut<'_>| {
+            x.attach_to_bone(character, bone_name, space);
+        });
+        self
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets},
+    ecs::{
+        component::Component,
+        event::EventReader,
+        query::With,
+        system::{Query, Res},
+    },
+    math::{Vec3, bounding::BoundingSphere},
+    platform::collections::HashSet,
+    render::{
+        mesh::{Mesh, Mesh2d, Mesh3d, MeshAabb},
+        primitives::Aabb,
+    },
+    transform::components::GlobalTransform,
+};
+
+/// Recompute the [`Aabb`] of a [`Mesh3d`] or [`Mesh2d`] when its mesh is modified,
+/// for projectiles that edit their mesh via [`ProjectileContext::mesh3d`](crate::ProjectileContext::mesh3d).
+///
+/// Bevy only computes the [`Aabb`] once, so modified meshes can be incorrectly frustum culled.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct UpdateAabb;
+
+/// World space bounding sphere of a projectile, updated from its [`Aabb`] and [`GlobalTransform`]
+/// every frame after transform propagation, for broad phase queries.
+///
+/// Without an [`Aabb`] the sphere is centered on the translation with radius `0`.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ProjectileBounds(pub BoundingSphere);
+
+impl Default for ProjectileBounds {
+    fn default() -> Self {
+        ProjectileBounds(BoundingSphere::new(Vec3::ZERO, 0.))
+    }
+}
+
+pub(crate) fn update_aabb(
+    mut events: EventReader<AssetEvent<Mesh>>,
+    meshes: Res<Assets<Mesh>>,
+    mut query: Query<(&mut Aabb, Option<&Mesh3d>, Option<&Mesh2d>), With<UpdateAabb>>,
+) {
+    let modified: HashSet<AssetId<Mesh>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+    for (mut aabb, mesh3d, mesh2d) in &mut query {
+        let Some(id) = mesh3d.map(|x| x.id()).or(mesh2d.map(|x| x.id())) else {
+            continue;
+        };
+        if !modified.contains(&id) {
+            continue;
+        }
+        if let Some(bounds) = meshes.get(id).and_then(MeshAabb::compute_aabb) {
+            *aabb = bounds;
+        }
+    }
+}
+
+pub(crate) fn update_projectile_bounds(
+    mut query: Query<(&mut ProjectileBounds, &GlobalTransform, Option<&Aabb>)>,
+) {
+    for (mut bounds, transform, aabb) in &mut query {
+        let (center, radius) = match aabb {
+            Some(aabb) => (
+                transform.transform_point(aabb.center.into()),
+                aabb.half_extents.length() * transform.scale().abs().max_element(),
+            ),
+            None => (transform.translation(), 0.),
+        };
+        bounds.0 = BoundingSphere::new(center, radius);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{Projectile, ProjectileSpawner};
+use bevy::ecs::entity::Entity;
+
+use crate::{
+    Projectile, ProjectileSpawner,
+    traits::{apply_command_on_spawner, spawner_done, update_spawner},
+};
 
 pub struct WithSpawner<A, T: ProjectileSpawner> {
     pub base: A,
@@ -18,6 +23,14 @@ impl<A: Projectile, T: ProjectileSpawner> Projectile for WithSpawner<A, T> {
         self.base.is_expired(cx)
     }
 
+    fn on_spawn(&mut self, cx: &mut crate::ProjectileContext) {
+        self.base.on_spawn(cx);
+    }
+
+    fn on_hit(&mut self, cx: &mut crate::ProjectileContext, hit: &crate::Hit) {
+        self.base.on_hit(cx, hit);
+    }
+
     fn update(&mut self, cx: &mut crate::ProjectileContext, dt: f32) {
         self.base.update(cx, dt);
     }
@@ -26,8 +39,8 @@ impl<A: Projectile, T: ProjectileSpawner> Projectile for WithSpawner<A, T> {
         self.base.on_expire(cx);
     }
 
-    fn apply_command(&mut self, command: &dyn std::any::Any) {
-        self.base.apply_command(command);
+    fn apply_command(&mut self, command: &dyn std::any::Any) -> crate::CommandResult {
+        self.base.apply_command(command)
     }
 
     fn as_spawner(&mut self) -> Option<&mut impl ProjectileSpawner> {
@@ -47,12 +60,27 @@ impl<A: ProjectileSpawner, T: ProjectileSpawner> ProjectileSpawner for WithSpawn
         self.base.space()
     }
 
+    fn spawn_origin(
+        &self,
+        cx: &crate::ProjectileContext,
+    ) -> Option<bevy::transform::components::Transform> {
+        self.base.spawn_origin(cx)
+    }
+
+    fn child_visibility(&self) -> Option<bevy::render::view::Visibility> {
+        self.base.child_visibility()
+    }
+
+    fn lifetime_offset(&self) -> f32 {
+        self.base.lifetime_offset()
+    }
+
     fn update(&mut self, cx: &mut crate::ProjectileContext, dt: f32) {
         self.base.update(cx, dt);
     }
 
-    fn apply_command(&mut self, command: &dyn std::any::Any) {
-        self.base.apply_command(command);
+    fn apply_command(&mut self, command: &dyn std::any::Any) -> crate::CommandResult {
+        self.base.apply_command(command)
     }
 
     fn duration(&self) -> f32 {
@@ -78,3 +106,73 @@ impl<A: ProjectileSpawner, T: ProjectileSpawner> ProjectileSpawner for WithSpawn
         Some(&mut self.spawner)
     }
 }
+
+/// Keeps a spawner dormant until `target` is within `radius` of it,
+/// for ambient emitters scattered across a large level.
+///
+/// While dormant, the inner spawner and its extensions are not updated or spawned from,
+/// but `lifetime` still advances.
+/// `target` must not contain a [`ProjectileInstance`](crate::ProjectileInstance),
+/// the spawner stays dormant if `target` does not exist.
+///
+/// Created via [`ProjectileSpawner::activate_within_range`].
+pub struct ActivateWithinRange<T: ProjectileSpawner> {
+    pub target: Entity,
+    pub radius: f32,
+    pub spawner: T,
+    active: bool,
+    complete: bool,
+}
+
+impl<T: ProjectileSpawner> ActivateWithinRange<T> {
+    pub fn new(target: Entity, radius: f32, spawner: T) -> Self {
+        ActivateWithinRange {
+            target,
+            radius,
+            spawner,
+            active: false,
+            complete: false,
+        }
+    }
+
+    /// Returns true if `target` was within range during the last update.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+impl<T: ProjectileSpawner> ProjectileSpawner for ActivateWithinRange<T> {
+    fn update(&mut self, cx: &mut crate::ProjectileContext, dt: f32) {
+        let position = cx.global_transform().translation();
+        self.active = cx
+            .translation_of(self.target)
+            .is_some_and(|target| target.distance_squared(position) <= self.radius * self.radius);
+        if self.active {
+            update_spawner(&mut self.spawner, cx, dt);
+        }
+        self.complete = spawner_done(&mut self.spawner, cx);
+    }
+
+    fn apply_command(&mut self, command: &dyn std::any::Any) -> crate::CommandResult {
+        apply_command_on_spawner(&mut self.spawner, command)
+    }
+
+    fn duration(&self) -> f32 {
+        self.spawner.duration()
+    }
+
+    fn fac_curve(&self, fac: f32) -> f32 {
+        self.spawner.fac_curve(fac)
+    }
+
+    fn is_complete(&self, _: &crate::ProjectileContext) -> bool {
+        self.complete
+    }
+
+    fn children(
+        &self,
+        cx: &bevy::ecs::world::EntityMutExcept<impl bevy::ecs::bundle::Bundle>,
+    ) -> impl Iterator<Item = bevy::ecs::entity::Entity> {
+        self.spawner.children(cx)
+    }
+}
@@ -0,0 +1,131 @@
+use std::{any::Any, collections::VecDeque};
+
+use bevy::math::Vec3;
+
+use crate::{
+    CommandResult, Hit, Projectile, ProjectileBundle, ProjectileContext, ProjectileInstance,
+    ProjectileSpawner, ProjectileVelocity, WorldSpaceChildOf,
+};
+
+struct ChainStage(Box<dyn FnOnce(Vec3, ChainStages, &mut ProjectileContext) + Send + Sync>);
+type ChainStages = VecDeque<ChainStage>;
+
+/// A sequence of projectiles, when one expires the next is spawned at its final transform.
+///
+/// Each stage receives the velocity of the previous stage, its [`ProjectileVelocity`] if present,
+/// otherwise estimated from its [`GlobalTransform`](bevy::transform::components::GlobalTransform).
+/// The next stage is spawned in world space under the same parent and keeps the projectile tree alive.
+///
+/// ```
+/// # use bevy_javelin::*;
+/// # use bevy::math::Vec3;
+/// # struct Rocket; impl Projectile for Rocket {}
+/// # struct Booster { velocity: Vec3 } impl Projectile for Booster {}
+/// # struct Explosion; impl Projectile for Explosion {}
+/// let rocket = Chain::new(Rocket)
+///     .then(|velocity| Booster { velocity })
+///     .then(|_| Explosion);
+/// ```
+pub struct Chain<P> {
+    current: P,
+    stages: ChainStages,
+    previous: Option<Vec3>,
+    velocity: Vec3,
+}
+
+impl<P: Projectile> Chain<P> {
+    pub fn new(first: P) -> Self {
+        Chain {
+            current: first,
+            stages: VecDeque::new(),
+            previous: None,
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    /// Add a stage, created from the velocity of the previous stage.
+    ///
+    /// Avoid specifying [`Transform`](bevy::transform::components::Transform) in the bundle, as it is overwritten.
+    pub fn then<B: ProjectileBundle>(
+        mut self,
+        stage: impl FnOnce(Vec3) -> B + Send + Sync + 'static,
+    ) -> Self {
+        self.stages.push_back(ChainStage(Box::new(
+            move |velocity, stages, cx: &mut ProjectileContext| {
+                let (projectile, bundle) =
+                    stage(velocity).into_projectile_bundle(&mut cx.resources);
+                let transform = cx.global_transform().compute_transform();
+                let parent = cx.parent();
+                let mut entity = cx.commands.spawn((
+                    ProjectileInstance::new_with_reference(
+                        Chain {
+                            current: projectile,
+                            stages,
+                            previous: None,
+                            velocity,
+                        },
+                        cx.rc,
+                    ),
+                    bundle,
+                ));
+                entity.insert(transform);
+                if let Some(parent) = parent {
+                    entity.insert(WorldSpaceChildOf(parent));
+                }
+            },
+        )));
+        self
+    }
+}
+
+impl<P: Projectile> Projectile for Chain<P> {
+    fn duration(&self) -> f32 {
+        self.current.duration()
+    }
+
+    fn fac_curve(&self, fac: f32) -> f32 {
+        self.current.fac_curve(fac)
+    }
+
+    fn is_expired(&self, cx: &ProjectileContext) -> bool {
+        self.current.is_expired(cx)
+    }
+
+    fn on_spawn(&mut self, cx: &mut ProjectileContext) {
+        self.current.on_spawn(cx);
+    }
+
+    fn on_hit(&mut self, cx: &mut ProjectileContext, hit: &Hit) {
+        self.current.on_hit(cx, hit);
+    }
+
+    fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+        self.current.update(cx, dt);
+        let position = cx.global_transform().translation();
+        if let Some(previous) = self.previous
+            && dt > 0.
+        {
+            self.velocity = (position - previous) / dt;
+        }
+        self.previous = Some(position);
+        if let Some(velocity) = cx.get_component::<ProjectileVelocity>() {
+            self.velocity = velocity.0;
+        }
+    }
+
+    fn on_expire(&mut self, cx: &mut ProjectileContext) {
+        self.current.on_expire(cx);
+        let mut stages = std::mem::take(&mut self.stages);
+        if let Some(next) = stages.pop_front() {
+            (next.0)(self.velocity, stages, cx);
+        }
+    }
+
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+        self.current.apply_command(command)
+    }
+
+    fn as_spawner(&mut self) -> Option<&mut impl ProjectileSpawner> {
+        self.current.as_spawner()
+    }
+}
@@ -8,8 +8,10 @@ use bevy::ecs::{
 };
 
 use crate::{
-    ProjectileContext, ProjectileInstance, ProjectileSpawner,
+    CommandResult, ProjectileContext, ProjectileInstance, ProjectileSpawner,
+    storage::ProjectileBox,
     traits::{ErasedProjectile, ProjectileRc},
+    typed::CommandTarget,
 };
 
 #[derive(Debug, Clone)]
@@ -19,7 +21,7 @@ impl ProjectileInstance {
     /// Create from a list of projectile spawners, spawns each as local space children and shares projectile events.
     pub fn from_spawner_iter(iter: impl IntoIterator<Item: ProjectileSpawner>) -> Self {
         Self {
-            projectile: Box::new(SpawnerCluster::from_iter(iter)),
+            projectile: ProjectileBox::new(SpawnerCluster::from_iter(iter)),
             lifetime: 0.,
             rc: ProjectileRc::new(),
             done: false,
@@ -58,12 +60,17 @@ impl<T: ProjectileSpawner> ErasedProjectile for SpawnerCluster<T> {
         true
     }
 
-    fn apply_command(&mut self, _: &dyn Any) -> bool {
-        true
+    fn apply_command(&mut self, _: &dyn Any) -> CommandResult {
+        CommandResult::Propagate
     }
 }
 
-/// An [`Event`] that applies to a single projectile.
+/// An [`Event`] that applies to a projectile via [`Projectile::apply_command`](crate::Projectile::apply_command),
+/// and to its children if it returns [`CommandResult::Propagate`].
+///
+/// A [`SpawnerCluster`] always propagates to its spawners.
+///
+/// See [`TypedProjectileCommand`](crate::TypedProjectileCommand) for a type checked alternative.
 #[derive(Debug, Event)]
 pub struct ProjectileCommand(Entity, Box<dyn Any + Send + Sync>);
 
@@ -75,27 +82,104 @@ impl ProjectileCommand {
 
 pub fn projectile_command_system(
     mut reader: EventReader<ProjectileCommand>,
-    mut projectiles: Query<&mut ProjectileInstance>,
-    children: Query<&Children>,
+    mut projectiles: Query<(&mut ProjectileInstance, CommandTarget)>,
 ) {
     for ProjectileCommand(entity, command) in reader.read() {
-        apply_projectile_command(&mut projectiles, &children, *entity, command.as_ref());
+        apply_projectile_command(&mut projectiles, *entity, command.as_ref());
     }
 }
 
 fn apply_projectile_command(
-    projectiles: &mut Query<&mut ProjectileInstance>,
-    children: &Query<&Children>,
+    projectiles: &mut Query<(&mut ProjectileInstance, CommandTarget)>,
     entity: Entity,
     command: &dyn Any,
 ) {
-    if let Ok(mut projectile) = projectiles.get_mut(entity) {
-        if projectile.apply_command(command) {
-            if let Ok(collection) = children.get(entity) {
-                for child in collection {
-                    apply_projectile_command(projectiles, children, *child, command);
-                }
+    if let Ok((mut projectile, mut target)) = projectiles.get_mut(entity)
+        && projectile.apply_command_on(&mut target, command) == CommandResult::Propagate
+        && let Some(children) = target.get::<Children>().map(|x| x.to_vec())
+    {
+        for child in children {
+            apply_projectile_command(projectiles, child, command);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::any::Any;
+
+    use bevy::{
+        app::App,
+        ecs::{entity::Entity, hierarchy::ChildOf},
+        prelude::MinimalPlugins,
+    };
+
+    use super::ProjectileCommand;
+    use crate::{CommandResult, Projectile, ProjectileInstance, ProjectilePlugin};
+
+    struct Counter {
+        count: u32,
+        propagate: bool,
+    }
+
+    impl Projectile for Counter {
+        fn duration(&self) -> f32 {
+            100.
+        }
+
+        fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+            if let Some(amount) = command.downcast_ref::<u32>() {
+                self.count += amount;
+            }
+            match self.propagate {
+                true => CommandResult::Propagate,
+                false => CommandResult::Consume,
             }
         }
     }
+
+    fn count(app: &App, entity: Entity) -> u32 {
+        app.world()
+            .get::<ProjectileInstance>(entity)
+            .and_then(|x| x.downcast_ref::<Counter>())
+            .unwrap()
+            .count
+    }
+
+    #[test]
+    fn command_propagation() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ProjectilePlugin::default()));
+        let counter = |propagate| {
+            ProjectileInstance::new(Counter {
+                count: 0,
+                propagate,
+            })
+        };
+        let root = app.world_mut().spawn(counter(true)).id();
+        let child = app.world_mut().spawn((counter(false), ChildOf(root))).id();
+        let grandchild = app.world_mut().spawn((counter(true), ChildOf(child))).id();
+        app.world_mut()
+            .send_event(ProjectileCommand::new(root, 5u32));
+        app.update();
+        assert_eq!(
+            (
+                count(&app, root),
+                count(&app, child),
+                count(&app, grandchild)
+            ),
+            (5, 5, 0)
+        );
+        app.world_mut()
+            .send_event(ProjectileCommand::new(grandchild, 1u32));
+        app.update();
+        assert_eq!(
+            (
+                count(&app, root),
+                count(&app, child),
+                count(&app, grandchild)
+            ),
+            (5, 5, 1)
+        );
+    }
 }
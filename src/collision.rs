@@ -0,0 +1,366 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, Events},
+        query::With,
+        schedule::SystemSet,
+        system::Query,
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+
+use crate::{ProjectileContext, ProjectileInstance};
+
+/// A target that can be hit by a [`ProjectileCollider`], as a sphere around its [`GlobalTransform`].
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct HitTarget {
+    /// Radius in world units, `0` for a point.
+    pub radius: f32,
+}
+
+impl HitTarget {
+    pub const fn new(radius: f32) -> Self {
+        HitTarget { radius }
+    }
+}
+
+/// Shape of a projectile checked against [`HitTarget`]s each frame by the default hit detection backend.
+///
+/// Sizes are in world units and not affected by scale, results are available via [`ProjectileContext::hits`].
+#[derive(Debug, Clone, Copy, Component)]
+#[require(ProjectileHits)]
+pub enum ProjectileCollider {
+    Sphere {
+        radius: f32,
+    },
+    /// A capsule along the local `Y` axis.
+    Capsule {
+        radius: f32,
+        half_length: f32,
+    },
+    /// A line segment along the local forward (`-Z`) axis.
+    Ray {
+        length: f32,
+    },
+}
+
+impl ProjectileCollider {
+    /// Returns the shape as a world space segment and a radius.
+    fn segment(&self, transform: &GlobalTransform) -> (Vec3, Vec3, f32) {
+        let origin = transform.translation();
+        match *self {
+            ProjectileCollider::Sphere { radius } => (origin, origin, radius),
+            ProjectileCollider::Capsule {
+                radius,
+                half_length,
+            } => {
+                let axis = transform.up() * half_length;
+                (origin - axis, origin + axis, radius)
+            }
+            ProjectileCollider::Ray { length } => {
+                (origin, origin + transform.forward() * length, 0.)
+            }
+        }
+    }
+}
+
+/// Sweep the [`ProjectileCollider`] from its previous position to the current one,
+/// so fast projectiles do not tunnel through targets.
+///
+/// Only translation is swept, rotation is taken from the current frame.
+#[derive(Debug, Clone, Copy, Default, Component)]
+#[require(ProjectileHits)]
+pub struct ContinuousCollision {
+    pub(crate) previous: Option<Vec3>,
+}
+
+impl ContinuousCollision {
+    pub const fn new() -> Self {
+        ContinuousCollision { previous: None }
+    }
+}
+
+/// A hit registered by a hit detection backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    /// The entity that was hit.
+    pub entity: Entity,
+    /// Point on the projectile closest to the target, in world space.
+    pub point: Vec3,
+}
+
+/// Hits of a projectile in the current frame, cleared before [`HitDetection`] runs.
+///
+/// Custom backends should push to this component in [`HitDetection`].
+/// With the `avian` feature, avian's `CollisionStarted` events are pushed here as well.
+#[derive(Debug, Clone, Default, Component)]
+pub struct ProjectileHits(Vec<Hit>);
+
+impl ProjectileHits {
+    pub fn push(&mut self, hit: Hit) {
+        self.0.push(hit);
+    }
+
+    pub fn as_slice(&self) -> &[Hit] {
+        &self.0
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// A hit with a user payload like damage, reported via [`ProjectileContext::report_hit`],
+/// usually in [`Projectile::on_hit`](crate::Projectile::on_hit).
+///
+/// Triggered on the target, use a global observer via `App::add_observer` to listen to all hits.
+/// Also sent as a buffered event if registered via `App::add_event`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// #[derive(Clone)]
+/// struct Damage(f32);
+///
+/// struct Bullet;
+///
+/// impl Projectile for Bullet {
+///     fn on_hit(&mut self, cx: &mut ProjectileContext, hit: &Hit) {
+///         cx.report_hit(hit.entity, Damage(10.));
+///         cx.despawn();
+///     }
+/// }
+///
+/// fn take_damage(trigger: Trigger<ProjectileHit<Damage>>) {
+///     println!("{} dealt {} damage.", trigger.attacker, trigger.payload.0);
+/// }
+/// ```
+#[derive(Debug, Clone, Event)]
+pub struct ProjectileHit<T: Send + Sync + 'static> {
+    /// The projectile that registered the hit.
+    pub attacker: Entity,
+    /// The entity that was hit.
+    pub target: Entity,
+    pub payload: T,
+}
+
+/// [`SystemSet`] of hit detection backends, runs before projectiles are updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct HitDetection;
+
+impl ProjectileContext<'_, '_> {
+    /// Returns hits registered in this frame, requires [`ProjectileHits`] or a [`ProjectileCollider`].
+    pub fn hits(&self) -> &[Hit] {
+        self.entity_mut
+            .get::<ProjectileHits>()
+            .map(ProjectileHits::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Report a hit on `target` with a payload, emits a [`ProjectileHit`].
+    pub fn report_hit<T: Clone + Send + Sync + 'static>(&mut self, target: Entity, payload: T) {
+        let hit = ProjectileHit {
+            attacker: self.entity(),
+            target,
+            payload,
+        };
+        if let Ok(mut events) = self.resources.get_mut::<Events<ProjectileHit<T>>>() {
+            events.send(hit.clone());
+        }
+        self.commands.trigger_targets(hit, target);
+    }
+}
+
+/// Returns the factors of the closest points between segments `a0..a1` and `b0..b1`.
+fn closest_points_between_segments(a0: Vec3, a1: Vec3, b0: Vec3, b1: Vec3) -> (f32, f32) {
+    let da = a1 - a0;
+    let db = b1 - b0;
+    let r = a0 - b0;
+    let len_a = da.length_squared();
+    let len_b = db.length_squared();
+    let f = db.dot(r);
+    if len_a <= f32::EPSILON && len_b <= f32::EPSILON {
+        return (0., 0.);
+    }
+    if len_a <= f32::EPSILON {
+        return (0., (f / len_b).clamp(0., 1.));
+    }
+    let c = da.dot(r);
+    if len_b <= f32::EPSILON {
+        return ((-c / len_a).clamp(0., 1.), 0.);
+    }
+    let b = da.dot(db);
+    let denom = len_a * len_b - b * b;
+    // Parallel segments have no unique closest points, start from `a0`.
+    let mut s = match denom > f32::EPSILON * len_a * len_b {
+        true => ((b * f - c * len_b) / denom).clamp(0., 1.),
+        false => 0.,
+    };
+    let mut t = (b * s + f) / len_b;
+    if t < 0. {
+        t = 0.;
+        s = (-c / len_a).clamp(0., 1.);
+    } else if t > 1. {
+        t = 1.;
+        s = ((b - c) / len_a).clamp(0., 1.);
+    }
+    (s, t)
+}
+
+pub(crate) fn clear_hits(mut query: Query<&mut ProjectileHits>) {
+    for mut hits in &mut query {
+        if !hits.0.is_empty() {
+            hits.clear();
+        }
+    }
+}
+
+/// Check the collider moved by `movement` this frame against all targets, pushes hits.
+///
+/// Relative to the projectile, each target moves from `center + movement` to `center`,
+/// so the swept collider hits if it is close enough to that segment.
+fn detect_hits(
+    entity: Entity,
+    (a, b, radius): (Vec3, Vec3, f32),
+    movement: Vec3,
+    targets: &Query<(Entity, &HitTarget, &GlobalTransform)>,
+    hits: &mut ProjectileHits,
+) {
+    for (target, shape, target_transform) in targets {
+        if target == entity {
+            continue;
+        }
+        let center = target_transform.translation();
+        let (s, t) = closest_points_between_segments(a, b, center + movement, center);
+        let point = a.lerp(b, s);
+        let range = radius + shape.radius;
+        if point.distance_squared(center + movement * (1. - t)) <= range * range {
+            hits.push(Hit {
+                entity: target,
+                point: point - movement * (1. - t),
+            });
+        }
+    }
+}
+
+pub(crate) fn hit_detection_system(
+    mut projectiles: Query<
+        (
+            Entity,
+            &ProjectileCollider,
+            &GlobalTransform,
+            &mut ProjectileHits,
+            Option<&mut ContinuousCollision>,
+        ),
+        With<ProjectileInstance>,
+    >,
+    targets: Query<(Entity, &HitTarget, &GlobalTransform)>,
+) {
+    for (entity, collider, transform, mut hits, continuous) in &mut projectiles {
+        let movement = match continuous {
+            Some(mut continuous) => {
+                let current = transform.translation();
+                current - continuous.previous.replace(current).unwrap_or(current)
+            }
+            None => Vec3::ZERO,
+        };
+        detect_hits(
+            entity,
+            collider.segment(transform),
+            movement,
+            &targets,
+            &mut hits,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        ecs::{entity::Entity, system::RunSystemOnce, world::World},
+        math::Vec3,
+        transform::components::GlobalTransform,
+    };
+
+    use super::{
+        ContinuousCollision, HitTarget, ProjectileCollider, ProjectileHits,
+        closest_points_between_segments, hit_detection_system,
+    };
+    use crate::{Projectile, ProjectileInstance};
+
+    struct Bullet;
+
+    impl Projectile for Bullet {}
+
+    #[test]
+    fn closest_points() {
+        let (s, t) = closest_points_between_segments(
+            Vec3::new(-1., 0., 0.),
+            Vec3::new(1., 0., 0.),
+            Vec3::new(0.5, -1., 1.),
+            Vec3::new(0.5, 1., 1.),
+        );
+        assert_eq!((s, t), (0.75, 0.5));
+        // Degenerate and parallel segments.
+        let (s, t) = closest_points_between_segments(Vec3::X, Vec3::X, Vec3::ZERO, Vec3::Y * 2.);
+        assert_eq!((s, t), (0., 0.));
+        let (s, t) =
+            closest_points_between_segments(Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Y + Vec3::X);
+        assert_eq!((s, t), (0., 0.));
+    }
+
+    /// Move a projectile along `X` by 10 units in one frame past targets at `x = 5`.
+    fn sweep(continuous: bool, target_y: f32) -> Vec<Entity> {
+        let mut world = World::new();
+        let target = world
+            .spawn((
+                HitTarget::new(0.05),
+                GlobalTransform::from_xyz(5., target_y, 0.),
+            ))
+            .id();
+        let projectile = world
+            .spawn((
+                ProjectileInstance::new(Bullet),
+                ProjectileCollider::Sphere { radius: 0.1 },
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+        if continuous {
+            world
+                .entity_mut(projectile)
+                .insert(ContinuousCollision::new());
+        }
+        world.run_system_once(hit_detection_system).unwrap();
+        assert!(
+            world
+                .get::<ProjectileHits>(projectile)
+                .unwrap()
+                .as_slice()
+                .is_empty()
+        );
+        world
+            .entity_mut(projectile)
+            .insert(GlobalTransform::from_xyz(10., 0., 0.));
+        world.run_system_once(hit_detection_system).unwrap();
+        let hits = world.get::<ProjectileHits>(projectile).unwrap().as_slice();
+        for hit in hits {
+            assert_eq!(hit.entity, target);
+            assert!(
+                hit.point.abs_diff_eq(Vec3::new(5., 0., 0.), 1e-4),
+                "{}",
+                hit.point
+            );
+        }
+        hits.iter().map(|x| x.entity).collect()
+    }
+
+    #[test]
+    fn thin_target_between_frames() {
+        assert_eq!(sweep(true, 0.).len(), 1);
+        assert_eq!(sweep(true, 0.1).len(), 1);
+        assert!(sweep(true, 0.2).is_empty());
+        assert!(sweep(false, 0.).is_empty());
+    }
+}
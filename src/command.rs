@@ -0,0 +1,82 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    app::App,
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader},
+        schedule::{InternedScheduleLabel, IntoScheduleConfigs},
+        system::Query,
+    },
+    log::warn,
+};
+
+use crate::{Projectile, ProjectileInstance, ProjectileSystems, TypedProjectile};
+
+/// Handles commands of type `C` sent via [`TypedProjectileCommand`].
+pub trait OnCommand<C>: Projectile {
+    fn on_command(&mut self, command: &C);
+}
+
+/// An [`Event`] that sends a command to a projectile of type `P`,
+/// registered via [`ProjectilePlugin::with_command`](crate::ProjectilePlugin::with_command).
+///
+/// Unlike [`ProjectileCommand`](crate::ProjectileCommand), the command is checked to be handled by `P`
+/// and a warning is logged if the target is not a `P`.
+#[derive(Event)]
+pub struct TypedProjectileCommand<P, C> {
+    pub entity: Entity,
+    pub command: C,
+    p: PhantomData<fn() -> P>,
+}
+
+impl<P: OnCommand<C>, C: Send + Sync + 'static> TypedProjectileCommand<P, C> {
+    pub fn new(entity: Entity, command: C) -> Self {
+        TypedProjectileCommand {
+            entity,
+            command,
+            p: PhantomData,
+        }
+    }
+}
+
+/// Add the event and system of a [`TypedProjectileCommand`].
+pub(crate) fn register_command<P: OnCommand<C>, C: Send + Sync + 'static>(
+    app: &mut App,
+    schedule: InternedScheduleLabel,
+) {
+    app.add_event::<TypedProjectileCommand<P, C>>();
+    app.add_systems(
+        schedule,
+        typed_command_system::<P, C>.in_set(ProjectileSystems::Commands),
+    );
+}
+
+fn typed_command_system<P: OnCommand<C>, C: Send + Sync + 'static>(
+    mut reader: EventReader<TypedProjectileCommand<P, C>>,
+    mut instances: Query<&mut ProjectileInstance>,
+    mut typed: Query<&mut TypedProjectile<P>>,
+) {
+    for TypedProjectileCommand {
+        entity, command, ..
+    } in reader.read()
+    {
+        if let Ok(mut typed) = typed.get_mut(*entity)
+            && let Some(projectile) = typed.get_mut()
+        {
+            projectile.on_command(command);
+            continue;
+        }
+        match instances.get_mut(*entity) {
+            Ok(mut instance) => match instance.downcast_mut::<P>() {
+                Some(projectile) => projectile.on_command(command),
+                None => warn!(
+                    "Projectile command expected {}, found {} on {entity}.",
+                    std::any::type_name::<P>(),
+                    instance.type_name(),
+                ),
+            },
+            Err(_) => warn!("Projectile command target {entity} is not a projectile."),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use bevy::{
     asset::{Asset, AssetId, Assets},
     ecs::{
@@ -6,6 +8,7 @@ use bevy::{
         component::{Component, Mutable},
         entity::{ContainsEntity, Entity, EntityEquivalent},
         hierarchy::ChildOf,
+        name::Name,
         query::Without,
         relationship::{Relationship, RelationshipTarget},
         system::{Command, Commands, EntityCommands, Query},
@@ -22,8 +25,11 @@ use bevy::{
 };
 
 use crate::{
-    DefaultProjectileBundle, DetachToWorldSpaceExt, ProjectileBundle, ProjectileInstance,
-    WorldSpaceChildOf, traits::ProjectileRc,
+    DefaultProjectileBundle, DetachToWorldSpaceExt, MeasuredVelocity, ProjectileBundle,
+    ProjectileInstance, ProjectileVelocity, WorldSpaceChildOf,
+    dissolve::{DissolveOnExpire, Dissolving},
+    expiry::{LingerAfterExpire, Lingering},
+    traits::ProjectileRc,
 };
 
 /// Context for projectile rendering, includes access to components, resources and
@@ -33,8 +39,17 @@ pub struct ProjectileContext<'w, 's> {
     pub(crate) global_transform: &'s GlobalTransform,
     pub(crate) entity_mut: EntityMutExcept<'s, DefaultProjectileBundle>,
     pub(crate) resources: FilteredResourcesMut<'w, 's>,
-    pub(crate) tracking:
-        Query<'w, 's, (&'static Transform, &'static GlobalTransform), Without<ProjectileInstance>>,
+    pub(crate) tracking: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static GlobalTransform,
+            Option<&'static MeasuredVelocity>,
+            Option<&'static ProjectileVelocity>,
+        ),
+        Without<ProjectileInstance>,
+    >,
     // Safety: cannot offer access to this entity.
     pub(crate) unsafe_other: Query<
         'w,
@@ -60,11 +75,33 @@ impl ProjectileContext<'_, '_> {
         self.entity_mut.id()
     }
 
+    /// Obtain the [`Name`] of the current entity.
+    pub fn name(&self) -> Option<&str> {
+        self.entity_mut.get::<Name>().map(Name::as_str)
+    }
+
+    /// Set the [`Name`] of the current entity, inserted with commands if not present.
+    pub fn set_name(&mut self, name: impl Into<Cow<'static, str>>) {
+        if let Some(mut current) = self.entity_mut.get_mut::<Name>() {
+            current.set(name);
+        } else {
+            let entity = self.entity();
+            self.commands.entity(entity).insert(Name::new(name));
+        }
+    }
+
     /// Obtain the amount of time this projectile or spawner has stayed alive.
     pub fn lifetime(&self) -> f32 {
         self.lifetime
     }
 
+    /// Returns the amount of live projectiles in this projectile tree, excluding this one.
+    ///
+    /// See [`ProjectileInstance::live_descendants`].
+    pub fn live_children(&self) -> usize {
+        self.rc.live_others()
+    }
+
     /// Returns the amount of second elapsed.
     ///
     /// Since time is always exported in shaders by bevy's `global`,
@@ -230,6 +267,17 @@ impl ProjectileContext<'_, '_> {
         self.tracking.get(entity).map(|x| x.0.translation).ok()
     }
 
+    /// Obtain the velocity of an external entity, must not contain a [`ProjectileInstance`].
+    ///
+    /// Reads [`MeasuredVelocity`] if present, otherwise [`ProjectileVelocity`],
+    /// returns `None` if the entity has neither.
+    pub fn velocity_of(&self, entity: Entity) -> Option<Vec3> {
+        let (_, _, measured, velocity) = self.tracking.get(entity).ok()?;
+        measured
+            .map(MeasuredVelocity::get)
+            .or(velocity.map(|x| x.0))
+    }
+
     /// If has a parent projectile instance, return its [`Transform`].
     /// otherwise return [`Transform::IDENTITY`].
     pub fn parent_transform<T: Relationship>(&self) -> Transform {
@@ -293,6 +341,26 @@ impl ProjectileContext<'_, '_> {
         self.commands.entity(entity).despawn();
     }
 
+    /// Despawn the current entity once effects like [`DissolveOnExpire`] or [`LingerAfterExpire`] finish,
+    /// this is the default behavior of [`Projectile::on_expire`](crate::Projectile::on_expire).
+    pub fn despawn_deferred(&mut self) {
+        let entity = self.entity();
+        if self.entity_mut.contains::<DissolveOnExpire>() {
+            self.commands
+                .entity(entity)
+                .insert(Dissolving::new(self.rc));
+        } else if let Some(LingerAfterExpire(duration)) =
+            self.entity_mut.get::<LingerAfterExpire>().copied()
+        {
+            self.set_invisible();
+            self.commands
+                .entity(entity)
+                .insert(Lingering::new(duration, self.rc));
+        } else {
+            self.despawn();
+        }
+    }
+
     /// Insert a bundle to the entity.
     pub fn insert_bundle<B: Bundle>(&mut self, bundle: B) {
         let entity = self.entity();
@@ -308,23 +376,25 @@ impl ProjectileContext<'_, '_> {
     /// Spawn a child projectile in world space.
     pub fn spawn_world_space(&mut self, bundle: impl ProjectileBundle) {
         let entity = self.entity();
-        let (projectile, bundle) = bundle.into_projectile_bundle(&mut self.resources);
-        self.commands
-            .entity(entity)
-            .with_related::<WorldSpaceChildOf>((
-                ProjectileInstance::new_with_reference(projectile, self.rc),
-                bundle,
-            ));
+        self.spawn_child(bundle).insert(WorldSpaceChildOf(entity));
     }
 
     /// Spawn a child projectile in local space.
     pub fn spawn_local_space(&mut self, bundle: impl ProjectileBundle) {
         let entity = self.entity();
+        self.spawn_child(bundle).insert(ChildOf(entity));
+    }
+
+    /// Spawn a child projectile without a parent.
+    fn spawn_child(&mut self, bundle: impl ProjectileBundle) -> EntityCommands<'_> {
         let (projectile, bundle) = bundle.into_projectile_bundle(&mut self.resources);
-        self.commands.entity(entity).with_child((
-            ProjectileInstance::new_with_reference(projectile, self.rc),
-            bundle,
-        ));
+        let (instance, typed) =
+            ProjectileInstance::new_maybe_typed(projectile, self.rc, &self.resources);
+        let mut child = self.commands.spawn((instance, bundle));
+        if let Some(typed) = typed {
+            child.insert(typed);
+        }
+        child
     }
 
     /// Spawn a unrelated projectile in the world.
@@ -351,6 +421,17 @@ impl ProjectileContext<'_, '_> {
         self.commands.entity(entity).detach_to_world_space();
     }
 
+    /// Replace local space parent with world space parent, without changing [`GlobalTransform`],
+    /// and add the parent's velocity to [`ProjectileVelocity`](crate::ProjectileVelocity).
+    ///
+    /// See [`DetachToWorldSpaceExt::detach_to_world_space_with_velocity`].
+    pub fn detach_to_world_space_with_velocity(&mut self) {
+        let entity = self.entity();
+        self.commands
+            .entity(entity)
+            .detach_to_world_space_with_velocity();
+    }
+
     /// Queue a [`Command`].
     pub fn queue(&mut self, command: impl Command) {
         self.commands.queue(command);
@@ -0,0 +1,140 @@
+use bevy::{
+    asset::{Asset, AssetLoader, Assets, Handle, LoadContext, io::Reader},
+    math::curve::{Curve, Interval, UnevenSampleAutoCurve, cores::UnevenCoreError},
+    reflect::TypePath,
+};
+use serde::Deserialize;
+
+use crate::ProjectileContext;
+
+/// A `f32` [`Curve`] loaded from `.curve.ron` files by [`ProjectileDefPlugin`](crate::ProjectileDefPlugin),
+/// so tuning data lives in assets and hot reloads.
+///
+/// The file is a list of at least two `(time, value)` keyframes, interpolated linearly:
+///
+/// ```ron
+/// [(0.0, 0.0), (0.2, 1.0), (1.0, 0.0)]
+/// ```
+///
+/// Use [`CurveParam`] to reference this as a parameter.
+#[derive(Debug, Clone, Asset, TypePath, Deserialize)]
+#[serde(try_from = "Vec<(f32, f32)>")]
+pub struct CurveAsset(UnevenSampleAutoCurve<f32>);
+
+impl TryFrom<Vec<(f32, f32)>> for CurveAsset {
+    type Error = UnevenCoreError;
+
+    fn try_from(keyframes: Vec<(f32, f32)>) -> Result<Self, Self::Error> {
+        UnevenSampleAutoCurve::new(keyframes).map(CurveAsset)
+    }
+}
+
+impl Curve<f32> for CurveAsset {
+    fn domain(&self) -> Interval {
+        self.0.domain()
+    }
+
+    fn sample_unchecked(&self, t: f32) -> f32 {
+        self.0.sample_unchecked(t)
+    }
+
+    fn sample_clamped(&self, t: f32) -> f32 {
+        self.0.sample_clamped(t)
+    }
+}
+
+/// Error of [`CurveAssetLoader`].
+#[derive(Debug, thiserror::Error)]
+pub enum CurveAssetLoaderError {
+    #[error("could not read curve: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse curve: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// [`AssetLoader`] of [`CurveAsset`] for `.curve.ron` files.
+#[derive(Debug, Default)]
+pub struct CurveAssetLoader;
+
+impl AssetLoader for CurveAssetLoader {
+    type Asset = CurveAsset;
+    type Settings = ();
+    type Error = CurveAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        _: &mut LoadContext<'_>,
+    ) -> Result<CurveAsset, CurveAssetLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["curve.ron"]
+    }
+}
+
+/// A parameter like spawn rate, scale or color factor that is either a constant or a [`CurveAsset`].
+///
+/// Sample it every frame, so modified curves take effect immediately.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// struct Spark {
+///     scale: CurveParam,
+/// }
+///
+/// impl Projectile for Spark {
+///     fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
+///         if let Some(scale) = self.scale.sample(cx, cx.fac()) {
+///             cx.transform_mut().scale = Vec3::splat(scale);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveParam {
+    Constant(f32),
+    Asset(Handle<CurveAsset>),
+}
+
+impl CurveParam {
+    /// Sample at `t`, clamped to the domain of the curve.
+    ///
+    /// Returns [`None`] if the curve is not loaded.
+    pub fn sample(&self, cx: &ProjectileContext, t: f32) -> Option<f32> {
+        match self {
+            CurveParam::Constant(value) => Some(*value),
+            CurveParam::Asset(handle) => {
+                let assets = cx.resources.get::<Assets<CurveAsset>>().ok()?;
+                Some(assets.get(handle)?.sample_clamped(t))
+            }
+        }
+    }
+
+    /// Sample at `t` from [`Assets`], clamped to the domain of the curve.
+    ///
+    /// Returns [`None`] if the curve is not loaded.
+    pub fn sample_in(&self, assets: &Assets<CurveAsset>, t: f32) -> Option<f32> {
+        match self {
+            CurveParam::Constant(value) => Some(*value),
+            CurveParam::Asset(handle) => Some(assets.get(handle)?.sample_clamped(t)),
+        }
+    }
+}
+
+impl From<f32> for CurveParam {
+    fn from(value: f32) -> Self {
+        CurveParam::Constant(value)
+    }
+}
+
+impl From<Handle<CurveAsset>> for CurveParam {
+    fn from(handle: Handle<CurveAsset>) -> Self {
+        CurveParam::Asset(handle)
+    }
+}
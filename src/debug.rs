@@ -0,0 +1,119 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    color::{Alpha, Color, palettes::css},
+    ecs::{hierarchy::ChildOf, schedule::IntoScheduleConfigs, system::Query},
+    gizmos::{AppGizmoBuilder, config::GizmoConfigGroup, gizmos::Gizmos},
+    math::{Isometry3d, Vec3},
+    reflect::Reflect,
+    transform::{TransformSystem, components::GlobalTransform},
+};
+
+use crate::{ProjectileBounds, ProjectileInstance, ProjectileVelocity, WorldSpaceChildOf};
+
+/// [`GizmoConfigGroup`] of [`ProjectileDebugPlugin`], can be toggled via bevy's `GizmoConfigStore`.
+#[derive(Debug, Clone, Reflect, GizmoConfigGroup)]
+pub struct ProjectileGizmos {
+    /// Draw arrows of [`ProjectileVelocity`], scaled to the distance traveled in `velocity_scale` seconds.
+    pub velocity: bool,
+    pub velocity_scale: f32,
+    /// Draw a ring filled by `lifetime / duration`.
+    pub progress: bool,
+    /// Radius of the progress ring, [`ProjectileBounds`] is used instead if present.
+    pub progress_radius: f32,
+    /// Draw lines from projectiles to their parents.
+    pub hierarchy: bool,
+    pub velocity_color: Color,
+    pub progress_color: Color,
+    /// Color of lines to a [`ChildOf`] parent.
+    pub local_color: Color,
+    /// Color of lines to a [`WorldSpaceChildOf`] parent.
+    pub world_space_color: Color,
+}
+
+impl Default for ProjectileGizmos {
+    fn default() -> Self {
+        ProjectileGizmos {
+            velocity: true,
+            velocity_scale: 0.1,
+            progress: true,
+            progress_radius: 0.25,
+            hierarchy: true,
+            velocity_color: css::YELLOW.into(),
+            progress_color: css::LIME.into(),
+            local_color: css::DEEP_SKY_BLUE.into(),
+            world_space_color: css::ORANGE.into(),
+        }
+    }
+}
+
+/// Draws gizmos for every [`ProjectileInstance`], useful when tuning spawners.
+///
+/// Requires bevy's `GizmoPlugin`, see [`ProjectileGizmos`] for configuration.
+#[derive(Debug, Default)]
+pub struct ProjectileDebugPlugin;
+
+impl Plugin for ProjectileDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<ProjectileGizmos>().add_systems(
+            PostUpdate,
+            draw_projectile_gizmos.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+fn draw_projectile_gizmos(
+    mut gizmos: Gizmos<ProjectileGizmos>,
+    query: Query<(
+        &ProjectileInstance,
+        &GlobalTransform,
+        Option<&ProjectileVelocity>,
+        Option<&ProjectileBounds>,
+        Option<&ChildOf>,
+        Option<&WorldSpaceChildOf>,
+    )>,
+    parents: Query<&GlobalTransform>,
+) {
+    let config = gizmos.config_ext.clone();
+    for (instance, transform, velocity, bounds, child_of, world_space_child_of) in &query {
+        if instance.done {
+            continue;
+        }
+        let translation = transform.translation();
+        if config.velocity
+            && let Some(velocity) = velocity
+            && velocity.0 != Vec3::ZERO
+        {
+            gizmos.arrow(
+                translation,
+                translation + velocity.0 * config.velocity_scale,
+                config.velocity_color,
+            );
+        }
+        if config.progress {
+            let radius = match bounds {
+                Some(bounds) if bounds.0.radius() > 0. => bounds.0.radius(),
+                _ => config.progress_radius,
+            };
+            let fac = instance.projectile.get_fac(instance.lifetime).clamp(0., 1.);
+            let isometry = Isometry3d::new(translation, transform.rotation());
+            gizmos.circle(isometry, radius, config.progress_color.with_alpha(0.25));
+            if fac > 0. {
+                gizmos.arc_3d(fac * TAU, radius, isometry, config.progress_color);
+            }
+        }
+        if config.hierarchy {
+            if let Some(parent) = child_of
+                && let Ok(parent) = parents.get(parent.parent())
+            {
+                gizmos.line(translation, parent.translation(), config.local_color);
+            }
+            if let Some(parent) = world_space_child_of
+                && let Ok(parent) = parents.get(parent.parent())
+            {
+                gizmos.line(translation, parent.translation(), config.world_space_color);
+            }
+        }
+    }
+}
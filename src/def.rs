@@ -0,0 +1,516 @@
+use std::sync::Arc;
+
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{
+        Asset, AssetApp, AssetEvent, AssetId, AssetLoader, AssetServer, Assets, LoadContext,
+        io::Reader,
+    },
+    ecs::{
+        change_detection::Mut,
+        event::EventReader,
+        system::{Query, Res},
+    },
+    math::{
+        Quat, Vec3,
+        curve::{Curve, EaseFunction},
+    },
+    pbr::{MeshMaterial3d, StandardMaterial},
+    reflect::TypePath,
+    render::mesh::{Mesh, Mesh3d},
+    transform::components::Transform,
+};
+use fastrand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    CurveAsset, CurveAssetLoader, CurveParam, Projectile, ProjectileBundle, ProjectileContext,
+    ProjectileInstance, ProjectileSpace, ProjectileSpawner, ProjectileVelocity, TypedProjectile,
+    spawning::{Limit, ProjectileSpawning, SpawnRate},
+    util::ProjectileRng,
+};
+
+/// A data driven projectile definition, loaded from `.projectile.ron` files by [`ProjectileDefPlugin`].
+///
+/// Spawn it with [`DefProjectile`].
+///
+/// ```ron
+/// (
+///     duration: 2.0,
+///     emitter: Some((
+///         rate: 20.0,
+///         shape: Cone(angle: 0.3),
+///         speed: 5.0,
+///         child: (
+///             duration: 0.5,
+///             fac_curve: QuadraticOut,
+///             mesh: Some("models/spark.glb#Mesh0/Primitive0"),
+///             material: Some("models/spark.glb#Material0"),
+///             scale: Some("curves/spark_scale.curve.ron"),
+///         ),
+///     )),
+/// )
+/// ```
+#[derive(Debug, Clone, Asset, TypePath, Deserialize)]
+#[serde(default)]
+pub struct ProjectileDef {
+    /// Duration in seconds, see [`Projectile::duration`].
+    pub duration: f32,
+    /// See [`Projectile::fac_curve`].
+    pub fac_curve: FacCurve,
+    /// Asset path of a [`Mesh`], inserted as [`Mesh3d`].
+    pub mesh: Option<String>,
+    /// Asset path of a [`StandardMaterial`], inserted as [`MeshMaterial3d`].
+    pub material: Option<String>,
+    /// Asset path of a [`CurveAsset`] sampled by `fac`, sets the uniform scale.
+    pub scale: Option<String>,
+    /// Spawns child projectiles while this projectile is not expired.
+    pub emitter: Option<EmitterDef>,
+}
+
+impl Default for ProjectileDef {
+    fn default() -> Self {
+        ProjectileDef {
+            duration: 1.,
+            fac_curve: FacCurve::Linear,
+            mesh: None,
+            material: None,
+            scale: None,
+            emitter: None,
+        }
+    }
+}
+
+/// Spawner section of a [`ProjectileDef`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct EmitterDef {
+    /// Spawns per second.
+    pub rate: f32,
+    /// Asset path of a [`CurveAsset`] sampled by `fac` of the emitting projectile, multiplies `rate`.
+    pub rate_curve: Option<String>,
+    /// Spawn this amount of children immediately.
+    pub burst: usize,
+    /// Maximum amount of children spawned.
+    pub limit: Option<usize>,
+    pub shape: EmitterShape,
+    /// Initial speed of children along their spawn direction.
+    pub speed: f32,
+    /// If true, children are spawned in world space.
+    pub world_space: bool,
+    pub child: Arc<ProjectileDef>,
+}
+
+impl Default for EmitterDef {
+    fn default() -> Self {
+        EmitterDef {
+            rate: 10.,
+            rate_curve: None,
+            burst: 0,
+            limit: None,
+            shape: EmitterShape::Point,
+            speed: 1.,
+            world_space: true,
+            child: Default::default(),
+        }
+    }
+}
+
+/// Position and direction of spawned children relative to the emitter.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum EmitterShape {
+    /// Spawn at the origin in a random direction.
+    #[default]
+    Point,
+    /// Spawn inside a sphere, pointing outwards.
+    Sphere { radius: f32 },
+    /// Spawn on a circle on the `XZ` plane, pointing outwards.
+    Circle { radius: f32 },
+    /// Spawn at the origin in a random direction within `angle` radians of forward (`-Z`).
+    Cone { angle: f32 },
+}
+
+impl EmitterShape {
+    /// Returns a position and a direction.
+    pub fn sample(&self, rng: &mut Rng) -> (Vec3, Vec3) {
+        match *self {
+            EmitterShape::Point => (Vec3::ZERO, rng.random_sphere()),
+            EmitterShape::Sphere { radius } => {
+                let direction = rng.random_sphere();
+                (direction * radius * rng.f32().cbrt(), direction)
+            }
+            EmitterShape::Circle { radius } => {
+                let circle = rng.random_circle();
+                let direction = Vec3::new(circle.x, 0., circle.y);
+                (direction * radius, direction)
+            }
+            EmitterShape::Cone { angle } => (Vec3::ZERO, rng.random_cone(Vec3::NEG_Z, angle)),
+        }
+    }
+}
+
+/// Serializable subset of [`EaseFunction`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum FacCurve {
+    #[default]
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    SmoothStep,
+    SmootherStep,
+}
+
+impl FacCurve {
+    pub fn ease_function(self) -> EaseFunction {
+        match self {
+            FacCurve::Linear => EaseFunction::Linear,
+            FacCurve::QuadraticIn => EaseFunction::QuadraticIn,
+            FacCurve::QuadraticOut => EaseFunction::QuadraticOut,
+            FacCurve::QuadraticInOut => EaseFunction::QuadraticInOut,
+            FacCurve::CubicIn => EaseFunction::CubicIn,
+            FacCurve::CubicOut => EaseFunction::CubicOut,
+            FacCurve::CubicInOut => EaseFunction::CubicInOut,
+            FacCurve::SineIn => EaseFunction::SineIn,
+            FacCurve::SineOut => EaseFunction::SineOut,
+            FacCurve::SineInOut => EaseFunction::SineInOut,
+            FacCurve::SmoothStep => EaseFunction::SmoothStep,
+            FacCurve::SmootherStep => EaseFunction::SmootherStep,
+        }
+    }
+
+    pub fn sample(self, fac: f32) -> f32 {
+        match self {
+            FacCurve::Linear => fac,
+            _ => self.ease_function().sample_clamped(fac),
+        }
+    }
+}
+
+/// Error of [`ProjectileDefLoader`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectileDefLoaderError {
+    #[error("could not read projectile definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse projectile definition: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// [`AssetLoader`] of [`ProjectileDef`] for `.projectile.ron` files.
+#[derive(Debug, Default)]
+pub struct ProjectileDefLoader;
+
+impl AssetLoader for ProjectileDefLoader {
+    type Asset = ProjectileDef;
+    type Settings = ();
+    type Error = ProjectileDefLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        _: &mut LoadContext<'_>,
+    ) -> Result<ProjectileDef, ProjectileDefLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["projectile.ron"]
+    }
+}
+
+/// Adds the [`ProjectileDef`] and [`CurveAsset`] assets and their loaders, requires bevy's `AssetPlugin`.
+///
+/// Live [`DefProjectile`]s created via [`DefProjectile::from_asset`] are reloaded when the asset is modified,
+/// enable bevy's `file_watcher` feature to hot reload from disk.
+#[derive(Debug, Default)]
+pub struct ProjectileDefPlugin;
+
+impl Plugin for ProjectileDefPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ProjectileDef>()
+            .init_asset_loader::<ProjectileDefLoader>()
+            .init_asset::<CurveAsset>()
+            .init_asset_loader::<CurveAssetLoader>()
+            .add_systems(Update, reload_def_projectiles);
+    }
+}
+
+/// A [`Projectile`] that interprets a [`ProjectileDef`].
+///
+/// The definition is copied on creation, the asset must be loaded before spawning.
+/// If created via [`DefProjectile::from_asset`], this projectile and its children
+/// pick up changes when the asset is modified, see [`DefProjectile::reload`].
+pub struct DefProjectile {
+    def: Arc<ProjectileDef>,
+    emitter: Option<DefEmitter>,
+    initialized: bool,
+    scale: Option<CurveParam>,
+    source: Option<DefSource>,
+}
+
+/// Asset and nesting depth of a [`DefProjectile`], used for hot reloading.
+#[derive(Debug, Clone, Copy)]
+struct DefSource {
+    id: AssetId<ProjectileDef>,
+    /// Amount of [`EmitterDef::child`] from the root definition.
+    depth: usize,
+}
+
+impl DefProjectile {
+    pub fn new(def: impl Into<Arc<ProjectileDef>>) -> Self {
+        DefProjectile::with_source(def.into(), None)
+    }
+
+    fn with_source(def: Arc<ProjectileDef>, source: Option<DefSource>) -> Self {
+        DefProjectile {
+            emitter: (def.emitter.clone())
+                .map(|emitter| DefEmitter::new(emitter, def.duration, source)),
+            def,
+            initialized: false,
+            scale: None,
+            source,
+        }
+    }
+
+    /// Create from a loaded [`ProjectileDef`] asset, returns [`None`] if not loaded.
+    pub fn from_asset(
+        id: impl Into<AssetId<ProjectileDef>>,
+        assets: &Assets<ProjectileDef>,
+    ) -> Option<Self> {
+        let id = id.into();
+        let def = assets.get(id)?.clone();
+        Some(DefProjectile::with_source(
+            Arc::new(def),
+            Some(DefSource { id, depth: 0 }),
+        ))
+    }
+
+    /// Returns the interpreted [`ProjectileDef`].
+    pub fn def(&self) -> &ProjectileDef {
+        &self.def
+    }
+
+    /// Apply a changed definition, keeping `lifetime` and the progress of the emitter.
+    ///
+    /// Changed mesh, material or curve paths are loaded again, the emitter's rate, limit, shape and children
+    /// are replaced, already spawned children are not affected.
+    pub fn reload(&mut self, def: impl Into<Arc<ProjectileDef>>) {
+        let def = def.into();
+        if def.mesh != self.def.mesh
+            || def.material != self.def.material
+            || def.scale != self.def.scale
+        {
+            self.initialized = false;
+        }
+        match (&mut self.emitter, def.emitter.clone()) {
+            (Some(emitter), Some(new)) => emitter.reload(new, def.duration),
+            (emitter, new) => {
+                *emitter = new.map(|new| DefEmitter::new(new, def.duration, self.source));
+            }
+        }
+        self.def = def;
+    }
+
+    /// Reload from the root definition of its source asset.
+    fn reload_from(&mut self, root: &ProjectileDef) {
+        let Some(source) = self.source else {
+            return;
+        };
+        let mut def = root;
+        for _ in 0..source.depth {
+            let Some(emitter) = &def.emitter else {
+                return;
+            };
+            def = &emitter.child;
+        }
+        self.reload(def.clone());
+    }
+}
+
+/// Reload [`DefProjectile`]s created via [`DefProjectile::from_asset`] when their asset is modified.
+fn reload_def_projectiles(
+    mut events: EventReader<AssetEvent<ProjectileDef>>,
+    assets: Res<Assets<ProjectileDef>>,
+    mut instances: Query<&mut ProjectileInstance>,
+    mut typed: Query<&mut TypedProjectile<DefProjectile>>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = *event else {
+            continue;
+        };
+        let Some(root) = assets.get(id) else {
+            continue;
+        };
+        let projectiles = instances
+            .iter_mut()
+            .filter_map(|instance| ProjectileInstance::map_mut::<DefProjectile>(instance))
+            .chain(
+                typed
+                    .iter_mut()
+                    .filter_map(|typed| Mut::filter_map_unchanged(typed, |x| x.get_mut())),
+            );
+        for mut projectile in projectiles {
+            if projectile.source.is_some_and(|source| source.id == id) {
+                projectile.reload_from(root);
+            }
+        }
+    }
+}
+
+impl DefProjectile {
+    /// Load and insert assets of the definition.
+    fn initialize(&mut self, cx: &mut ProjectileContext) {
+        self.initialized = true;
+        let (mesh, material, scale) = {
+            let Ok(server) = cx.resources.get::<AssetServer>() else {
+                return;
+            };
+            (
+                self.def.mesh.clone().map(|x| server.load::<Mesh>(x)),
+                (self.def.material.clone()).map(|x| server.load::<StandardMaterial>(x)),
+                self.def.scale.clone().map(|x| server.load::<CurveAsset>(x)),
+            )
+        };
+        if let Some(mesh) = mesh {
+            cx.insert_bundle(Mesh3d(mesh));
+        }
+        if let Some(material) = material {
+            cx.insert_bundle(MeshMaterial3d(material));
+        }
+        self.scale = scale.map(CurveParam::Asset);
+    }
+}
+
+impl Projectile for DefProjectile {
+    fn duration(&self) -> f32 {
+        self.def.duration
+    }
+
+    fn fac_curve(&self, fac: f32) -> f32 {
+        self.def.fac_curve.sample(fac)
+    }
+
+    fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
+        if !self.initialized {
+            self.initialize(cx);
+        }
+        if let Some(scale) = self.scale.as_ref().and_then(|x| x.sample(cx, cx.fac())) {
+            cx.transform_mut().scale = Vec3::splat(scale);
+        }
+    }
+
+    fn as_spawner(&mut self) -> Option<&mut impl ProjectileSpawner> {
+        self.emitter.as_mut()
+    }
+}
+
+/// Spawner of [`DefProjectile`].
+struct DefEmitter {
+    def: EmitterDef,
+    spawning: Limit<SpawnRate>,
+    /// Loaded from [`EmitterDef::rate_curve`] on the first update.
+    rate_curve: Option<CurveParam>,
+    rng: Rng,
+    /// Duration of the emitting projectile.
+    duration: f32,
+    /// Source of the emitting projectile.
+    source: Option<DefSource>,
+}
+
+impl DefEmitter {
+    fn new(def: EmitterDef, duration: f32, source: Option<DefSource>) -> Self {
+        DefEmitter {
+            duration,
+            spawning: SpawnRate::new(def.rate)
+                .with_spawn_immediately(def.burst)
+                .limit(def.limit.unwrap_or(usize::MAX)),
+            rate_curve: None,
+            rng: Rng::new(),
+            def,
+            source,
+        }
+    }
+
+    fn reload(&mut self, def: EmitterDef, duration: f32) {
+        let spawned = self.def.limit.unwrap_or(usize::MAX) - self.spawning.count;
+        self.spawning.base.set(def.rate);
+        self.spawning.count = def.limit.unwrap_or(usize::MAX).saturating_sub(spawned);
+        if def.rate_curve != self.def.rate_curve {
+            self.rate_curve = None;
+        }
+        self.duration = duration;
+        self.def = def;
+    }
+}
+
+impl ProjectileSpawner for DefEmitter {
+    fn spawn_projectile(
+        &mut self,
+        cx: &ProjectileContext,
+    ) -> Option<impl ProjectileBundle + use<>> {
+        if !self.spawning.try_spawn() {
+            return None;
+        }
+        let (position, direction) = self.def.shape.sample(&mut self.rng);
+        let velocity = match self.space() {
+            ProjectileSpace::Local => direction * self.def.speed,
+            ProjectileSpace::World => cx.global_transform().rotation() * direction * self.def.speed,
+        };
+        Some((
+            DefProjectile::with_source(
+                self.def.child.clone(),
+                self.source.map(|source| DefSource {
+                    id: source.id,
+                    depth: source.depth + 1,
+                }),
+            ),
+            Transform::from_translation(position)
+                .with_rotation(Quat::from_rotation_arc(Vec3::NEG_Z, direction)),
+            ProjectileVelocity(velocity),
+        ))
+    }
+
+    fn space(&self) -> ProjectileSpace {
+        match self.def.world_space {
+            true => ProjectileSpace::World,
+            false => ProjectileSpace::Local,
+        }
+    }
+
+    fn spawn_origin(&self, cx: &ProjectileContext) -> Option<Transform> {
+        match self.space() {
+            ProjectileSpace::Local => None,
+            ProjectileSpace::World => Some(cx.global_transform().compute_transform()),
+        }
+    }
+
+    fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+        if self.rate_curve.is_none()
+            && let Some(path) = &self.def.rate_curve
+            && let Ok(server) = cx.resources.get::<AssetServer>()
+        {
+            self.rate_curve = Some(CurveParam::Asset(server.load(path)));
+        }
+        if let Some(factor) = self
+            .rate_curve
+            .as_ref()
+            .and_then(|x| x.sample(cx, cx.fac()))
+        {
+            self.spawning.base.set(self.def.rate * factor);
+        }
+        self.spawning.update(dt);
+    }
+
+    fn is_complete(&self, cx: &ProjectileContext) -> bool {
+        self.spawning.finished() || cx.lifetime() > self.duration
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    ecs::{
+        query::Added,
+        removal_detection::RemovedComponents,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    platform::collections::HashMap,
+    time::Time,
+};
+
+use crate::ProjectileInstance;
+
+/// Statistics of live projectiles, updated every frame by [`ProjectileDiagnosticsPlugin`].
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ProjectileDiagnostics {
+    /// Amount of [`ProjectileInstance`]s that are not finished.
+    pub live: usize,
+    /// [`ProjectileInstance`]s added in the last frame, per second.
+    pub spawned_per_second: f64,
+    /// [`ProjectileInstance`]s removed in the last frame, per second.
+    pub despawned_per_second: f64,
+    /// Amount of live projectiles per projectile type name.
+    pub per_type: HashMap<&'static str, usize>,
+}
+
+/// Adds [`ProjectileDiagnostics`] and reports them to bevy's [`Diagnostics`].
+#[derive(Debug, Default)]
+pub struct ProjectileDiagnosticsPlugin;
+
+impl ProjectileDiagnosticsPlugin {
+    pub const LIVE: DiagnosticPath = DiagnosticPath::const_new("projectile/live");
+    pub const SPAWNED_PER_SECOND: DiagnosticPath =
+        DiagnosticPath::const_new("projectile/spawned_per_second");
+    pub const DESPAWNED_PER_SECOND: DiagnosticPath =
+        DiagnosticPath::const_new("projectile/despawned_per_second");
+}
+
+impl Plugin for ProjectileDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectileDiagnostics>()
+            .register_diagnostic(Diagnostic::new(Self::LIVE))
+            .register_diagnostic(Diagnostic::new(Self::SPAWNED_PER_SECOND))
+            .register_diagnostic(Diagnostic::new(Self::DESPAWNED_PER_SECOND))
+            .add_systems(Update, projectile_diagnostics_system);
+    }
+}
+
+fn projectile_diagnostics_system(
+    time: Res<Time>,
+    mut stats: ResMut<ProjectileDiagnostics>,
+    mut diagnostics: Diagnostics,
+    query: Query<&ProjectileInstance>,
+    added: Query<(), Added<ProjectileInstance>>,
+    mut removed: RemovedComponents<ProjectileInstance>,
+) {
+    let stats = stats.as_mut();
+    stats.live = 0;
+    stats.per_type.values_mut().for_each(|x| *x = 0);
+    for instance in &query {
+        if instance.done {
+            continue;
+        }
+        stats.live += 1;
+        *stats.per_type.entry(instance.type_name()).or_default() += 1;
+    }
+    stats.per_type.retain(|_, count| *count > 0);
+    let dt = time.delta_secs_f64();
+    let per_second = |count: usize| match dt > 0. {
+        true => count as f64 / dt,
+        false => 0.,
+    };
+    stats.spawned_per_second = per_second(added.iter().count());
+    stats.despawned_per_second = per_second(removed.read().count());
+    diagnostics.add_measurement(&ProjectileDiagnosticsPlugin::LIVE, || stats.live as f64);
+    diagnostics.add_measurement(&ProjectileDiagnosticsPlugin::SPAWNED_PER_SECOND, || {
+        stats.spawned_per_second
+    });
+    diagnostics.add_measurement(&ProjectileDiagnosticsPlugin::DESPAWNED_PER_SECOND, || {
+        stats.despawned_per_second
+    });
+}
@@ -0,0 +1,47 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+
+/// Scales `dt` of projectiles within `radius` of this entity by `factor`,
+/// for bullet time fields and stasis traps.
+///
+/// Overlapping zones multiply, a `factor` of `0` freezes projectiles inside.
+/// Zones are tested against the projectile's world space position.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct TimeDilationZone {
+    pub radius: f32,
+    pub factor: f32,
+}
+
+/// Time dilation zones of this frame.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct TimeDilationZones(Vec<(Vec3, TimeDilationZone)>);
+
+impl TimeDilationZones {
+    /// Returns the `dt` scale at `position`.
+    pub(crate) fn scale_at(&self, position: Vec3) -> f32 {
+        self.0
+            .iter()
+            .filter(|(center, zone)| center.distance_squared(position) <= zone.radius * zone.radius)
+            .map(|(_, zone)| zone.factor)
+            .product()
+    }
+}
+
+pub(crate) fn collect_time_dilation_zones(
+    mut zones: ResMut<TimeDilationZones>,
+    query: Query<(&TimeDilationZone, &GlobalTransform)>,
+) {
+    zones.0.clear();
+    zones.0.extend(
+        query
+            .iter()
+            .map(|(zone, transform)| (transform.translation(), *zone)),
+    );
+}
@@ -0,0 +1,150 @@
+//! Burn projectiles away with a mask instead of popping them out of existence.
+
+use bevy::{
+    app::{App, Plugin},
+    asset::{Asset, Assets, Handle, load_internal_asset, weak_handle},
+    color::LinearRgba,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, Query, Res, ResMut},
+    },
+    image::Image,
+    pbr::{ExtendedMaterial, MaterialExtension, MaterialPlugin, MeshMaterial3d, StandardMaterial},
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, Shader, ShaderRef},
+    time::Time,
+};
+
+use crate::{ProjectileInstance, expiry::despawn_deferred_now, traits::ProjectileRc};
+
+const DISSOLVE_SHADER_HANDLE: Handle<Shader> = weak_handle!("0b6f6a55-1f0e-4d3a-8f35-6a1f6c3e2b71");
+
+/// A [`StandardMaterial`] that discards fragments where `mask` is below the dissolve threshold.
+pub type DissolveMaterial = ExtendedMaterial<StandardMaterial, DissolveExtension>;
+
+/// [`MaterialExtension`] of [`DissolveMaterial`], reads the red channel of `mask` by uv.
+#[derive(Debug, Clone, Asset, TypePath, AsBindGroup)]
+pub struct DissolveExtension {
+    #[texture(100)]
+    #[sampler(101)]
+    pub mask: Handle<Image>,
+    /// `0` is fully visible, `1` is fully dissolved.
+    #[uniform(102)]
+    pub progress: f32,
+    /// Range of mask values above the threshold that glow.
+    #[uniform(102)]
+    pub edge_width: f32,
+    /// Emissive color added to the glowing edge.
+    #[uniform(102)]
+    pub edge_color: LinearRgba,
+}
+
+impl DissolveExtension {
+    pub fn new(mask: Handle<Image>) -> Self {
+        DissolveExtension {
+            mask,
+            progress: 0.,
+            edge_width: 0.05,
+            edge_color: LinearRgba::rgb(4., 1.5, 0.2),
+        }
+    }
+}
+
+impl MaterialExtension for DissolveExtension {
+    fn fragment_shader() -> ShaderRef {
+        DISSOLVE_SHADER_HANDLE.into()
+    }
+
+    fn deferred_fragment_shader() -> ShaderRef {
+        DISSOLVE_SHADER_HANDLE.into()
+    }
+}
+
+/// Dissolve the projectile over `duration` seconds after it expires, instead of despawning it.
+///
+/// This replaces the default despawn in [`Projectile::on_expire`](crate::Projectile::on_expire),
+/// custom implementations should call [`ProjectileContext::despawn_deferred`](crate::ProjectileContext::despawn_deferred).
+///
+/// A [`MeshMaterial3d<StandardMaterial>`] is replaced by a [`DissolveMaterial`] using `mask`,
+/// an existing [`DissolveMaterial`] is driven directly and should not be shared between projectiles.
+/// Children in local space are despawned with the projectile once dissolved.
+///
+/// Requires [`DissolvePlugin`].
+#[derive(Debug, Clone, Component)]
+pub struct DissolveOnExpire {
+    pub mask: Handle<Image>,
+    pub duration: f32,
+}
+
+/// Inserted on expiry, keeps the projectile tree alive until dissolved.
+#[derive(Debug, Component)]
+pub(crate) struct Dissolving {
+    elapsed: f32,
+    rc: ProjectileRc,
+}
+
+impl Dissolving {
+    pub(crate) fn new(rc: &ProjectileRc) -> Self {
+        Dissolving {
+            elapsed: 0.,
+            rc: rc.clone(),
+        }
+    }
+}
+
+pub(crate) fn dissolve_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    standard: Res<Assets<StandardMaterial>>,
+    mut materials: ResMut<Assets<DissolveMaterial>>,
+    mut query: Query<(
+        Entity,
+        &DissolveOnExpire,
+        &mut Dissolving,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Option<&MeshMaterial3d<DissolveMaterial>>,
+        Option<&ProjectileInstance>,
+    )>,
+) {
+    for (entity, dissolve, mut dissolving, base, material, instance) in &mut query {
+        dissolving.elapsed += time.delta_secs();
+        if dissolving.elapsed >= dissolve.duration {
+            despawn_deferred_now(&mut commands, entity, instance, &dissolving.rc);
+            continue;
+        }
+        let progress = dissolving.elapsed / dissolve.duration;
+        if let Some(material) = material.and_then(|x| materials.get_mut(x.id())) {
+            material.extension.progress = progress;
+        } else if let Some(base) = base.and_then(|x| standard.get(x.id())) {
+            let mut extension = DissolveExtension::new(dissolve.mask.clone());
+            extension.progress = progress;
+            let handle = materials.add(DissolveMaterial {
+                base: base.clone(),
+                extension,
+            });
+            commands
+                .entity(entity)
+                .remove::<MeshMaterial3d<StandardMaterial>>()
+                .insert(MeshMaterial3d(handle));
+        }
+    }
+}
+
+/// Plugin for [`DissolveOnExpire`] and [`DissolveMaterial`].
+///
+/// Dissolving is advanced in the schedule of [`ProjectilePlugin`](crate::ProjectilePlugin),
+/// alongside [`LingerAfterExpire`](crate::LingerAfterExpire).
+pub struct DissolvePlugin;
+
+impl Plugin for DissolvePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DISSOLVE_SHADER_HANDLE,
+            "dissolve.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_plugins(MaterialPlugin::<DissolveMaterial>::default());
+    }
+}
@@ -0,0 +1,57 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::alpha_discard,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+struct DissolveSettings {
+    progress: f32,
+    edge_width: f32,
+    edge_color: vec4<f32>,
+}
+
+@group(2) @binding(100) var dissolve_mask: texture_2d<f32>;
+@group(2) @binding(101) var dissolve_sampler: sampler;
+@group(2) @binding(102) var<uniform> dissolve: DissolveSettings;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+#ifdef VERTEX_UVS_A
+    let mask = textureSample(dissolve_mask, dissolve_sampler, in.uv).r;
+#else
+    let mask = 1.0;
+#endif
+    // Below `cutoff` is discarded, `edge_width` above `cutoff` glows.
+    let cutoff = dissolve.progress * (1.0 + dissolve.edge_width) - dissolve.edge_width;
+    if mask < cutoff {
+        discard;
+    }
+    let edge = 1.0 - smoothstep(cutoff, cutoff + dissolve.edge_width, mask);
+    pbr_input.material.emissive += dissolve.edge_color * edge;
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+#ifdef PREPASS_PIPELINE
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+    return out;
+}
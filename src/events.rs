@@ -0,0 +1,42 @@
+use bevy::ecs::{entity::Entity, event::Event};
+
+/// Triggered on a root projectile when it and all its descendants have finished,
+/// i.e. when nothing holds its projectile tree alive anymore, right before it is despawned.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// fn show_ended(trigger: Trigger<ProjectileFinished>) {
+///     println!("{} ended.", trigger.target());
+/// }
+///
+/// # fn spawn(mut commands: Commands, instance: ProjectileInstance) {
+/// commands.spawn(instance).observe(show_ended);
+/// # }
+/// ```
+///
+/// Use a global observer via `App::add_observer` to listen to all projectiles.
+///
+/// # Note
+///
+/// If the root has no live descendants and despawns itself in [`Projectile::on_expire`](crate::Projectile::on_expire),
+/// as it does by default, this is triggered after the despawn and only reaches global observers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ProjectileFinished {
+    /// The root projectile entity.
+    pub root: Entity,
+}
+
+/// Triggered on a projectile when a [`ProjectileInstance`](crate::ProjectileInstance) is inserted,
+/// including children spawned by spawners and entities reused by [`ProjectilePool`](crate::ProjectilePool).
+///
+/// Use a global observer via `App::add_observer` to listen to all projectiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ProjectileSpawned;
+
+/// Triggered on a projectile when [`Projectile::is_expired`](crate::Projectile::is_expired)
+/// first returns true or it is otherwise expired, right before [`Projectile::on_expire`](crate::Projectile::on_expire).
+///
+/// Use a global observer via `App::add_observer` to listen to all projectiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ProjectileExpired;
@@ -0,0 +1,386 @@
+use std::{any::type_name, marker::PhantomData};
+
+use bevy::{
+    app::App,
+    ecs::{
+        component::Component,
+        entity::{Entities, Entity},
+        event::{Event, EventReader},
+        hierarchy::ChildOf,
+        observer::Trigger,
+        query::With,
+        resource::Resource,
+        schedule::{InternedScheduleLabel, IntoScheduleConfigs},
+        system::{Commands, Query, Res},
+    },
+    log::warn,
+    math::Vec3,
+    time::Time,
+};
+
+use crate::{
+    ProjectileContext, ProjectileFinished, ProjectileInstance, ProjectileSystems,
+    traits::ProjectileRc,
+};
+
+/// Expire the projectile once it is further than this distance from where it first updated,
+/// in addition to [`Projectile::is_expired`](crate::Projectile::is_expired).
+///
+/// Useful for range limited weapons.
+#[derive(Debug, Clone, Copy, Component)]
+#[require(DistanceOrigin)]
+pub struct MaxDistance(pub f32);
+
+/// Recorded on the first update of a [`MaxDistance`] projectile.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub(crate) struct DistanceOrigin(Option<Vec3>);
+
+impl ProjectileContext<'_, '_> {
+    /// Returns true if the projectile has a [`MaxDistance`] and traveled further than it.
+    pub(crate) fn exceeds_max_distance(&mut self) -> bool {
+        let Some(MaxDistance(max)) = self.entity_mut.get::<MaxDistance>().copied() else {
+            return false;
+        };
+        // `GlobalTransform` is not propagated on the first frame of root projectiles.
+        let position = match self.entity_mut.contains::<ChildOf>() {
+            true => self.global_transform.translation(),
+            false => self.transform.translation,
+        };
+        let Some(mut origin) = self.entity_mut.get_mut::<DistanceOrigin>() else {
+            return false;
+        };
+        let origin = *origin.0.get_or_insert(position);
+        position.distance_squared(origin) > max * max
+    }
+}
+
+/// Force expire projectiles whose `lifetime` exceeds this many seconds and log their type,
+/// as a safety net against projectiles that never expire.
+///
+/// Set via [`ProjectilePlugin::with_max_lifetime`](crate::ProjectilePlugin::with_max_lifetime).
+/// Spawners stop spawning and are despawned with the projectile.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct MaxProjectileLifetime(pub f32);
+
+impl ProjectileContext<'_, '_> {
+    /// Returns true and logs if `lifetime` exceeds [`MaxProjectileLifetime`].
+    pub(crate) fn exceeds_max_lifetime<T>(&self) -> bool {
+        let Ok(max) = self.resources.get::<MaxProjectileLifetime>() else {
+            return false;
+        };
+        if self.lifetime <= max.0 {
+            return false;
+        }
+        warn!(
+            "Projectile {} of type {} exceeded MaxProjectileLifetime of {}s, force expired.",
+            self.entity(),
+            type_name::<T>(),
+            max.0
+        );
+        true
+    }
+}
+
+/// Expire the projectile when an event of type `E` is sent or triggered,
+/// registered via [`ProjectilePlugin::with_expire_on`](crate::ProjectilePlugin::with_expire_on).
+///
+/// Useful for effects that should end when a boss phase changes or a door closes.
+/// Triggers that target an entity only expire that projectile.
+#[derive(Component)]
+#[require(ExpireRequested)]
+pub struct ExpireOn<E: Event>(PhantomData<fn() -> E>);
+
+impl<E: Event> ExpireOn<E> {
+    pub fn new() -> Self {
+        ExpireOn(PhantomData)
+    }
+}
+
+impl<E: Event> Default for ExpireOn<E> {
+    fn default() -> Self {
+        ExpireOn::new()
+    }
+}
+
+/// Set once an [`ExpireOn`] event is received or the owner of [`ExpireWithOwner`] despawns.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub(crate) struct ExpireRequested(bool);
+
+impl ProjectileContext<'_, '_> {
+    /// Returns true if expired by [`ExpireOn`] or [`ExpireWithOwner`].
+    pub(crate) fn expire_requested(&self) -> bool {
+        self.entity_mut
+            .get::<ExpireRequested>()
+            .is_some_and(|x| x.0)
+    }
+}
+
+/// Add the event, observer and system of [`ExpireOn<E>`].
+pub(crate) fn register_expire_on<E: Event>(app: &mut App, schedule: InternedScheduleLabel) {
+    app.add_event::<E>();
+    app.add_observer(expire_on_trigger::<E>);
+    app.add_systems(
+        schedule,
+        expire_on_event::<E>.in_set(ProjectileSystems::Commands),
+    );
+}
+
+fn expire_on_event<E: Event>(
+    mut reader: EventReader<E>,
+    mut query: Query<&mut ExpireRequested, With<ExpireOn<E>>>,
+) {
+    if reader.read().count() > 0 {
+        query.iter_mut().for_each(|mut x| x.0 = true);
+    }
+}
+
+fn expire_on_trigger<E: Event>(
+    trigger: Trigger<E>,
+    mut query: Query<&mut ExpireRequested, With<ExpireOn<E>>>,
+) {
+    let target = trigger.target();
+    if target == Entity::PLACEHOLDER {
+        query.iter_mut().for_each(|mut x| x.0 = true);
+    } else if let Ok(mut requested) = query.get_mut(target) {
+        requested.0 = true;
+    }
+}
+
+/// The entity that fired the projectile, like a turret or an enemy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct ProjectileOwner(pub Entity);
+
+/// Expire the projectile when its [`ProjectileOwner`] despawns,
+/// so bullets of dead enemies do not keep damaging the player.
+///
+/// Combine with [`LingerAfterExpire`] or [`DissolveOnExpire`](crate::dissolve::DissolveOnExpire) to fade out instead.
+#[derive(Debug, Clone, Copy, Default, Component)]
+#[require(ExpireRequested)]
+pub struct ExpireWithOwner;
+
+pub(crate) fn expire_with_owner(
+    entities: &Entities,
+    mut query: Query<(&ProjectileOwner, &mut ExpireRequested), With<ExpireWithOwner>>,
+) {
+    for (owner, mut requested) in &mut query {
+        if !requested.0 && !entities.contains(owner.0) {
+            requested.0 = true;
+        }
+    }
+}
+
+/// Hide the projectile and keep it alive for this many seconds after it expires, before despawning,
+/// so trails and child particles can finish.
+///
+/// Children in [`ProjectileSpace::Local`](crate::ProjectileSpace::Local) are despawned with the projectile
+/// once the duration ends, spawn them in world space to let them outlive it.
+///
+/// This replaces the default despawn in [`Projectile::on_expire`](crate::Projectile::on_expire),
+/// custom implementations should call [`ProjectileContext::despawn_deferred`].
+#[derive(Debug, Clone, Copy, Component)]
+pub struct LingerAfterExpire(pub f32);
+
+/// Inserted on expiry, keeps the projectile tree alive until the linger duration ends.
+#[derive(Debug, Component)]
+pub(crate) struct Lingering {
+    remaining: f32,
+    rc: ProjectileRc,
+}
+
+impl Lingering {
+    pub(crate) fn new(duration: f32, rc: &ProjectileRc) -> Self {
+        Lingering {
+            remaining: duration,
+            rc: rc.clone(),
+        }
+    }
+}
+
+pub(crate) fn linger_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Lingering, Option<&ProjectileInstance>)>,
+) {
+    for (entity, mut lingering, instance) in &mut query {
+        lingering.remaining -= time.delta_secs();
+        if lingering.remaining <= 0. {
+            despawn_deferred_now(&mut commands, entity, instance, &lingering.rc);
+        }
+    }
+}
+
+/// Despawn a projectile kept alive by [`ProjectileContext::despawn_deferred`],
+/// `rc` is the tree held alive by it.
+///
+/// The projectile is expired, so if it is a root, [`ProjectileFinished`] is triggered here
+/// unless other projectiles in its tree are still alive.
+pub(crate) fn despawn_deferred_now(
+    commands: &mut Commands,
+    entity: Entity,
+    instance: Option<&ProjectileInstance>,
+    rc: &ProjectileRc,
+) {
+    if instance.is_some_and(|x| x.root) && rc.live_others() == 0 {
+        commands.trigger_targets(ProjectileFinished { root: entity }, entity);
+    }
+    commands.entity(entity).despawn();
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        ecs::{
+            entity::Entity, event::Event, observer::Trigger, resource::Resource, system::ResMut,
+        },
+        prelude::MinimalPlugins,
+        render::view::Visibility,
+        time::TimeUpdateStrategy,
+        transform::TransformPlugin,
+    };
+
+    use super::{ExpireOn, LingerAfterExpire};
+    use crate::{
+        Projectile, ProjectileContext, ProjectileFinished, ProjectileInstance, ProjectilePlugin,
+        spawning::{ProjectileSpawning, SpawnRate},
+    };
+
+    #[derive(Event)]
+    struct DoorClosed;
+
+    struct Forever;
+
+    impl Projectile for Forever {
+        fn is_expired(&self, _: &ProjectileContext) -> bool {
+            false
+        }
+    }
+
+    struct Short;
+
+    impl Projectile for Short {
+        fn duration(&self) -> f32 {
+            0.15
+        }
+    }
+
+    fn app(plugin: ProjectilePlugin) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, plugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app
+    }
+
+    #[test]
+    fn max_lifetime() {
+        let mut app = app(ProjectilePlugin::default().with_max_lifetime(1.));
+        let projectile = app.world_mut().spawn(ProjectileInstance::new(Forever)).id();
+        let spawner = app
+            .world_mut()
+            .spawn(ProjectileInstance::spawner(
+                SpawnRate::new(1.).into_spawner_world(|_, _| Forever),
+            ))
+            .id();
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.world().get_entity(projectile).is_ok());
+        assert!(app.world().get_entity(spawner).is_ok());
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get_entity(projectile).is_err());
+        assert!(app.world().get_entity(spawner).is_err());
+    }
+
+    #[test]
+    fn no_max_lifetime() {
+        let mut app = app(ProjectilePlugin::default());
+        let projectile = app.world_mut().spawn(ProjectileInstance::new(Forever)).id();
+        for _ in 0..30 {
+            app.update();
+        }
+        assert!(app.world().get_entity(projectile).is_ok());
+    }
+
+    #[test]
+    fn expire_on() {
+        let mut app = app(ProjectilePlugin::default().with_expire_on::<DoorClosed>());
+        let triggered = app
+            .world_mut()
+            .spawn((
+                ProjectileInstance::new(Forever),
+                ExpireOn::<DoorClosed>::new(),
+            ))
+            .id();
+        let sent = app
+            .world_mut()
+            .spawn((
+                ProjectileInstance::new(Forever),
+                ExpireOn::<DoorClosed>::new(),
+            ))
+            .id();
+        let unaffected = app.world_mut().spawn(ProjectileInstance::new(Forever)).id();
+        app.update();
+        app.world_mut().trigger_targets(DoorClosed, triggered);
+        app.update();
+        app.update();
+        assert!(app.world().get_entity(triggered).is_err());
+        assert!(app.world().get_entity(sent).is_ok());
+        app.world_mut().send_event(DoorClosed);
+        app.update();
+        app.update();
+        assert!(app.world().get_entity(sent).is_err());
+        assert!(app.world().get_entity(unaffected).is_ok());
+    }
+
+    #[test]
+    fn linger_after_expire() {
+        let mut app = app(ProjectilePlugin::default());
+        let projectile = app
+            .world_mut()
+            .spawn((ProjectileInstance::new(Short), LingerAfterExpire(0.35)))
+            .id();
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().get::<Visibility>(projectile),
+            Some(&Visibility::Hidden)
+        );
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().get_entity(projectile).is_err());
+    }
+
+    #[derive(Default, Resource)]
+    struct Finished(Vec<Entity>);
+
+    #[test]
+    fn linger_finishes_root() {
+        let mut app = app(ProjectilePlugin::default());
+        app.init_resource::<Finished>();
+        app.add_observer(
+            |trigger: Trigger<ProjectileFinished>, mut finished: ResMut<Finished>| {
+                finished.0.push(trigger.root);
+            },
+        );
+        let projectile = app
+            .world_mut()
+            .spawn((ProjectileInstance::new(Short), LingerAfterExpire(0.35)))
+            .id();
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().resource::<Finished>().0.is_empty());
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Finished>().0, [projectile]);
+    }
+}
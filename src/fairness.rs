@@ -0,0 +1,187 @@
+use std::{fmt::Display, time::Duration};
+
+use bevy::{
+    app::{App, TaskPoolPlugin},
+    ecs::{bundle::Bundle, entity::Entity, observer::Trigger, resource::Resource, system::ResMut},
+    math::Vec3,
+    time::{Time, TimePlugin, TimeUpdateStrategy, Virtual},
+    transform::{TransformPlugin, components::GlobalTransform},
+};
+
+use crate::{ProjectileInstance, ProjectilePlugin, ProjectileSpawned};
+
+/// Validates that a spawner behaves identically at different frame rates,
+/// by simulating it in headless apps at several fixed timesteps,
+/// available with the `debug` feature.
+///
+/// Spawners using randomness must be seeded, see [`ProjectileSpawning::seeded`](crate::spawning::ProjectileSpawning::seeded).
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::{*, spawning::*};
+/// struct Spark;
+///
+/// impl Projectile for Spark {
+///     fn duration(&self) -> f32 {
+///         0.5
+///     }
+/// }
+///
+/// let report = SpawnFairnessTest::new(2.).run(|| {
+///     ProjectileInstance::spawner(
+///         SpawnRate::new(20.).into_spawner_world(|_, _| (Spark, ProjectileVelocity(Vec3::X))),
+///     )
+/// });
+/// assert!(report.is_fair(1, 0.5), "{report}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpawnFairnessTest {
+    /// Seconds to simulate.
+    pub duration: f32,
+    /// Timesteps to compare, in seconds.
+    pub timesteps: Vec<f32>,
+}
+
+impl Default for SpawnFairnessTest {
+    fn default() -> Self {
+        SpawnFairnessTest::new(2.)
+    }
+}
+
+impl SpawnFairnessTest {
+    /// Simulate for `duration` seconds at 30, 60 and 144 FPS.
+    pub fn new(duration: f32) -> Self {
+        SpawnFairnessTest {
+            duration,
+            timesteps: vec![1. / 30., 1. / 60., 1. / 144.],
+        }
+    }
+
+    pub fn with_timesteps(mut self, timesteps: impl IntoIterator<Item = f32>) -> Self {
+        self.timesteps = timesteps.into_iter().collect();
+        self
+    }
+
+    /// Spawn the bundle created by `spawn` in an app per timestep and record the results.
+    ///
+    /// The app only contains [`ProjectilePlugin`] and its dependencies.
+    pub fn run<B: Bundle>(&self, spawn: impl Fn() -> B) -> SpawnFairnessReport {
+        let samples = self
+            .timesteps
+            .iter()
+            .map(|&timestep| self.simulate(timestep, spawn()))
+            .collect();
+        SpawnFairnessReport { samples }
+    }
+
+    fn simulate(&self, timestep: f32, bundle: impl Bundle) -> SpawnFairnessSample {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TimePlugin,
+            TransformPlugin,
+            ProjectilePlugin::default(),
+        ));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            timestep,
+        )));
+        app.init_resource::<SpawnCount>();
+        app.add_observer(
+            |trigger: Trigger<ProjectileSpawned>, mut count: ResMut<SpawnCount>| {
+                count.0.push(trigger.target());
+            },
+        );
+        let root = app.world_mut().spawn(bundle).id();
+        while app.world().resource::<Time<Virtual>>().elapsed_secs() < self.duration - timestep / 2.
+        {
+            app.update();
+        }
+        let world = app.world_mut();
+        let spawned = world
+            .resource::<SpawnCount>()
+            .0
+            .iter()
+            .filter(|x| **x != root)
+            .count();
+        let positions = world
+            .query::<(Entity, &ProjectileInstance, &GlobalTransform)>()
+            .iter(world)
+            .filter(|(entity, instance, _)| *entity != root && !instance.done)
+            .map(|(_, _, transform)| transform.translation())
+            .collect();
+        SpawnFairnessSample {
+            timestep,
+            spawned,
+            positions,
+        }
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+struct SpawnCount(Vec<Entity>);
+
+/// Result of [`SpawnFairnessTest`] at a timestep.
+#[derive(Debug, Clone)]
+pub struct SpawnFairnessSample {
+    pub timestep: f32,
+    /// Number of projectiles spawned, excluding the root.
+    pub spawned: usize,
+    /// Positions of live projectiles at the end, excluding the root.
+    pub positions: Vec<Vec3>,
+}
+
+/// Result of [`SpawnFairnessTest`], implements [`Display`] for a readable summary.
+#[derive(Debug, Clone)]
+pub struct SpawnFairnessReport {
+    pub samples: Vec<SpawnFairnessSample>,
+}
+
+impl SpawnFairnessReport {
+    /// Difference between the most and least projectiles spawned at any timestep.
+    pub fn count_divergence(&self) -> usize {
+        let counts = self.samples.iter().map(|x| x.spawned);
+        counts.clone().max().unwrap_or(0) - counts.min().unwrap_or(0)
+    }
+
+    /// Largest distance from a live projectile to the closest live projectile of another timestep.
+    ///
+    /// Returns [`f32::INFINITY`] if a timestep has no live projectiles while another does.
+    pub fn position_divergence(&self) -> f32 {
+        let mut result = 0f32;
+        for a in &self.samples {
+            for b in &self.samples {
+                for p in &a.positions {
+                    let closest = b
+                        .positions
+                        .iter()
+                        .map(|q| p.distance(*q))
+                        .fold(f32::INFINITY, f32::min);
+                    result = result.max(closest);
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns true if divergences are within tolerances.
+    pub fn is_fair(&self, max_count_divergence: usize, max_position_divergence: f32) -> bool {
+        self.count_divergence() <= max_count_divergence
+            && self.position_divergence() <= max_position_divergence
+    }
+}
+
+impl Display for SpawnFairnessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for sample in &self.samples {
+            writeln!(
+                f,
+                "{:.1} FPS: {} spawned, {} live",
+                1. / sample.timestep,
+                sample.spawned,
+                sample.positions.len()
+            )?;
+        }
+        writeln!(f, "count divergence: {}", self.count_divergence())?;
+        write!(f, "position divergence: {}", self.position_divergence())
+    }
+}
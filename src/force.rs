@@ -0,0 +1,148 @@
+use bevy::{
+    ecs::{component::Component, resource::Resource, world::EntityMutExcept},
+    math::{
+        Vec3, Vec3A,
+        bounding::{Aabb3d, BoundingSphere},
+    },
+};
+
+use crate::{DefaultProjectileBundle, ProjectileVelocity};
+
+/// Environmental forces applied to projectiles with [`AffectedByForceFields`],
+/// integrated into [`ProjectileVelocity`] like [`ProjectileAcceleration`](crate::ProjectileAcceleration).
+///
+/// Zones are tested against the projectile's world space position, forces are in world space.
+///
+/// ```
+/// # use bevy::{prelude::*, math::bounding::Aabb3d};
+/// # use bevy_javelin::*;
+/// fn setup(mut fields: ResMut<ForceFields>) {
+///     fields.gravity = Vec3::NEG_Y * 9.8;
+///     fields.zones.push(
+///         ForceZone::new(Aabb3d::new(Vec3::ZERO, Vec3::splat(5.)))
+///             .with_wind(Vec3::X * 4.)
+///             .with_drag(0.5),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ForceFields {
+    /// Acceleration applied everywhere.
+    pub gravity: Vec3,
+    pub zones: Vec<ForceZone>,
+}
+
+impl ForceFields {
+    /// Total acceleration at `position` for a projectile moving at `velocity`.
+    pub fn acceleration(&self, position: Vec3, velocity: Vec3, time: f32) -> Vec3 {
+        self.zones
+            .iter()
+            .filter(|zone| zone.contains(position))
+            .map(|zone| zone.acceleration(position, velocity, time))
+            .fold(self.gravity, |a, b| a + b)
+    }
+}
+
+/// Shape of a [`ForceZone`].
+#[derive(Debug, Clone, Copy)]
+pub enum ForceZoneShape {
+    Box(Aabb3d),
+    Sphere(BoundingSphere),
+}
+
+impl From<Aabb3d> for ForceZoneShape {
+    fn from(value: Aabb3d) -> Self {
+        ForceZoneShape::Box(value)
+    }
+}
+
+impl From<BoundingSphere> for ForceZoneShape {
+    fn from(value: BoundingSphere) -> Self {
+        ForceZoneShape::Sphere(value)
+    }
+}
+
+/// A region of [`ForceFields`] with wind, turbulence and drag.
+#[derive(Debug, Clone, Copy)]
+pub struct ForceZone {
+    pub shape: ForceZoneShape,
+    /// Constant acceleration inside the zone.
+    pub wind: Vec3,
+    /// Magnitude of a smooth pseudo random acceleration that varies over space and time.
+    pub turbulence: f32,
+    /// Spatial frequency of turbulence, higher values produce smaller swirls.
+    pub turbulence_frequency: f32,
+    /// Deceleration proportional to velocity.
+    pub drag: f32,
+}
+
+impl ForceZone {
+    pub fn new(shape: impl Into<ForceZoneShape>) -> Self {
+        ForceZone {
+            shape: shape.into(),
+            wind: Vec3::ZERO,
+            turbulence: 0.,
+            turbulence_frequency: 1.,
+            drag: 0.,
+        }
+    }
+
+    pub fn with_wind(mut self, wind: Vec3) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    pub fn with_turbulence(mut self, turbulence: f32, frequency: f32) -> Self {
+        self.turbulence = turbulence;
+        self.turbulence_frequency = frequency;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Returns true if `position` is inside the zone.
+    pub fn contains(&self, position: Vec3) -> bool {
+        let position = Vec3A::from(position);
+        let closest = match &self.shape {
+            ForceZoneShape::Box(aabb) => aabb.closest_point(position),
+            ForceZoneShape::Sphere(sphere) => sphere.closest_point(position),
+        };
+        closest == position
+    }
+
+    /// Acceleration inside the zone, does not check [`ForceZone::contains`].
+    pub fn acceleration(&self, position: Vec3, velocity: Vec3, time: f32) -> Vec3 {
+        let p = position * self.turbulence_frequency;
+        let turbulence = Vec3::new(
+            (p.y + time * 1.3).sin() + (p.z * 1.7 - time).sin(),
+            (p.z + time * 0.7).sin() + (p.x * 1.3 + time).sin(),
+            (p.x + time * 1.1).sin() + (p.y * 1.9 - time * 0.9).sin(),
+        ) * (self.turbulence / 2.);
+        self.wind + turbulence - velocity * self.drag
+    }
+}
+
+/// Opt into [`ForceFields`].
+#[derive(Debug, Clone, Copy, Default, Component)]
+#[require(ProjectileVelocity)]
+pub struct AffectedByForceFields;
+
+/// Integrate [`ForceFields`] into [`ProjectileVelocity`].
+pub(crate) fn apply_force_fields(
+    entity_mut: &mut EntityMutExcept<DefaultProjectileBundle>,
+    fields: &ForceFields,
+    position: Vec3,
+    time: f32,
+    dt: f32,
+) {
+    if !entity_mut.contains::<AffectedByForceFields>() {
+        return;
+    }
+    if let Some(mut velocity) = entity_mut.get_mut::<ProjectileVelocity>() {
+        let acceleration = fields.acceleration(position, velocity.0, time);
+        velocity.0 += acceleration * dt;
+    }
+}
@@ -0,0 +1,177 @@
+use bevy::{
+    ecs::{component::Component, resource::Resource, system::ResMut},
+    platform::collections::HashMap,
+};
+
+use crate::ProjectileContext;
+
+/// Assigns a projectile to a group, which can be paused, expired or time scaled
+/// via [`ProjectileGroups`] regardless of hierarchy.
+///
+/// Children do not inherit the group of their parent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+pub struct ProjectileGroup(pub u32);
+
+#[derive(Debug, Clone, Copy)]
+struct GroupState {
+    time_scale: f32,
+    paused: bool,
+    expire: bool,
+}
+
+impl Default for GroupState {
+    fn default() -> Self {
+        GroupState {
+            time_scale: 1.,
+            paused: false,
+            expire: false,
+        }
+    }
+}
+
+/// Group wide operations on projectiles with a [`ProjectileGroup`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// const ENEMY_BULLETS: ProjectileGroup = ProjectileGroup(1);
+///
+/// fn clear_bullets(mut groups: ResMut<ProjectileGroups>) {
+///     groups.expire(ENEMY_BULLETS);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ProjectileGroups {
+    groups: HashMap<ProjectileGroup, GroupState>,
+}
+
+impl ProjectileGroups {
+    /// Stop updating projectiles in the group, they keep their current state.
+    pub fn pause(&mut self, group: ProjectileGroup) {
+        self.groups.entry(group).or_default().paused = true;
+    }
+
+    /// Resume a paused group.
+    pub fn resume(&mut self, group: ProjectileGroup) {
+        self.groups.entry(group).or_default().paused = false;
+    }
+
+    pub fn is_paused(&self, group: ProjectileGroup) -> bool {
+        self.groups.get(&group).is_some_and(|x| x.paused)
+    }
+
+    /// Scale `dt` of projectiles in the group, default is `1`.
+    pub fn set_time_scale(&mut self, group: ProjectileGroup, time_scale: f32) {
+        self.groups.entry(group).or_default().time_scale = time_scale;
+    }
+
+    pub fn time_scale(&self, group: ProjectileGroup) -> f32 {
+        self.groups.get(&group).map_or(1., |x| x.time_scale)
+    }
+
+    /// Expire all projectiles in the group on the next update, even if paused.
+    ///
+    /// This calls [`Projectile::on_expire`](crate::Projectile::on_expire) as usual
+    /// and stops spawners from spawning.
+    pub fn expire(&mut self, group: ProjectileGroup) {
+        self.groups.entry(group).or_default().expire = true;
+    }
+
+    /// Returns `dt` scale of a group, [`None`] if it should not be updated.
+    pub(crate) fn delta_scale(&self, group: ProjectileGroup) -> Option<f32> {
+        match self.groups.get(&group) {
+            Some(state) if state.expire => Some(state.time_scale),
+            Some(state) if state.paused => None,
+            Some(state) => Some(state.time_scale),
+            None => Some(1.),
+        }
+    }
+
+    fn is_expiring(&self, group: ProjectileGroup) -> bool {
+        self.groups.get(&group).is_some_and(|x| x.expire)
+    }
+}
+
+impl ProjectileContext<'_, '_> {
+    /// Returns true if the projectile's [`ProjectileGroup`] is being expired.
+    pub(crate) fn group_expired(&self) -> bool {
+        let Some(group) = self.entity_mut.get::<ProjectileGroup>().copied() else {
+            return false;
+        };
+        self.resources
+            .get::<ProjectileGroups>()
+            .is_ok_and(|x| x.is_expiring(group))
+    }
+}
+
+pub(crate) fn clear_group_expiry(mut groups: ResMut<ProjectileGroups>) {
+    if groups.groups.values().any(|x| x.expire) {
+        for state in groups.groups.values_mut() {
+            state.expire = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        ecs::entity::Entity,
+        math::Vec3,
+        prelude::MinimalPlugins,
+        time::TimeUpdateStrategy,
+        transform::{TransformPlugin, components::Transform},
+    };
+
+    use super::{ProjectileGroup, ProjectileGroups};
+    use crate::{Projectile, ProjectileInstance, ProjectilePlugin, ProjectileVelocity};
+
+    struct Leaf;
+
+    impl Projectile for Leaf {
+        fn duration(&self) -> f32 {
+            100.
+        }
+    }
+
+    const MOVING: ProjectileGroup = ProjectileGroup(1);
+    const PAUSED: ProjectileGroup = ProjectileGroup(2);
+
+    fn x(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Transform>(entity).unwrap().translation.x
+    }
+
+    #[test]
+    fn pause_and_expire() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, ProjectilePlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        let leaf = (ProjectileInstance::new(Leaf), ProjectileVelocity(Vec3::X));
+        let moving = app.world_mut().spawn((leaf, MOVING)).id();
+        let leaf = (ProjectileInstance::new(Leaf), ProjectileVelocity(Vec3::X));
+        let paused = app.world_mut().spawn((leaf, PAUSED)).id();
+        let ungrouped = app.world_mut().spawn(ProjectileInstance::new(Leaf)).id();
+        app.world_mut()
+            .resource_mut::<ProjectileGroups>()
+            .pause(PAUSED);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(x(&app, moving) > 0.);
+        assert_eq!(x(&app, paused), 0.);
+
+        // Expiring a paused group still expires it.
+        let mut groups = app.world_mut().resource_mut::<ProjectileGroups>();
+        groups.expire(MOVING);
+        groups.expire(PAUSED);
+        app.update();
+        app.update();
+        assert!(app.world().get_entity(moving).is_err());
+        assert!(app.world().get_entity(paused).is_err());
+        assert!(app.world().get_entity(ungrouped).is_ok());
+    }
+}
@@ -2,12 +2,19 @@ use std::{iter::Copied, slice::Iter};
 
 use bevy::{
     ecs::{
-        component::Component, entity::Entity, hierarchy::ChildOf, system::EntityCommands,
+        component::Component,
+        entity::Entity,
+        hierarchy::{ChildOf, Children},
+        system::{EntityCommands, Query},
         world::EntityWorldMut,
     },
-    transform::commands::BuildChildrenTransformExt,
+    math::Vec3,
+    render::view::{InheritedVisibility, Visibility},
+    transform::{commands::BuildChildrenTransformExt, components::GlobalTransform},
 };
 
+use crate::{MeasuredVelocity, ProjectileVelocity};
+
 /// Alternative children that does not inherit transform.
 #[derive(Debug, Component)]
 #[relationship_target(relationship = WorldSpaceChildOf)]
@@ -35,7 +42,15 @@ impl WorldSpaceChildOf {
 }
 
 pub trait DetachToWorldSpaceExt {
+    /// Replace local space parent with world space parent, without changing [`GlobalTransform`](bevy::transform::components::GlobalTransform).
     fn detach_to_world_space(&mut self) -> &mut Self;
+
+    /// Like [`detach_to_world_space`](DetachToWorldSpaceExt::detach_to_world_space),
+    /// but also adds the parent's velocity to [`ProjectileVelocity`], so the child keeps moving with its momentum.
+    ///
+    /// The parent's velocity is its [`MeasuredVelocity`] if present, otherwise its [`ProjectileVelocity`].
+    /// The child's [`ProjectileVelocity`] is converted from the parent's space to world space.
+    fn detach_to_world_space_with_velocity(&mut self) -> &mut Self;
 }
 
 impl DetachToWorldSpaceExt for EntityWorldMut<'_> {
@@ -48,6 +63,48 @@ impl DetachToWorldSpaceExt for EntityWorldMut<'_> {
         self.insert(WorldSpaceChildOf(parent));
         self
     }
+
+    fn detach_to_world_space_with_velocity(&mut self) -> &mut Self {
+        let Some(parent) = self.get::<ChildOf>() else {
+            return self;
+        };
+        let (to_world, inherited) = match self.world().get_entity(parent.parent()) {
+            Ok(parent) => {
+                let to_world = parent.get::<GlobalTransform>().map(GlobalTransform::affine);
+                let inherited = match parent.get::<MeasuredVelocity>() {
+                    Some(measured) => measured.get(),
+                    None => {
+                        // In the space of the parent's parent.
+                        let velocity = parent
+                            .get::<ProjectileVelocity>()
+                            .map_or(Vec3::ZERO, |x| x.0);
+                        match parent
+                            .get::<ChildOf>()
+                            .and_then(|x| self.world().get::<GlobalTransform>(x.parent()))
+                        {
+                            Some(transform) => transform.affine().transform_vector3(velocity),
+                            None => velocity,
+                        }
+                    }
+                };
+                (to_world, inherited)
+            }
+            Err(_) => (None, Vec3::ZERO),
+        };
+        self.detach_to_world_space();
+        match self.get_mut::<ProjectileVelocity>() {
+            Some(mut velocity) => {
+                if let Some(to_world) = to_world {
+                    velocity.0 = to_world.transform_vector3(velocity.0);
+                }
+                velocity.0 += inherited;
+            }
+            None => {
+                self.insert(ProjectileVelocity(inherited));
+            }
+        }
+        self
+    }
 }
 
 impl DetachToWorldSpaceExt for EntityCommands<'_> {
@@ -57,4 +114,106 @@ impl DetachToWorldSpaceExt for EntityCommands<'_> {
         });
         self
     }
+
+    fn detach_to_world_space_with_velocity(&mut self) -> &mut Self {
+        self.queue(|mut x: EntityWorldMut<'_>| {
+            x.detach_to_world_space_with_velocity();
+        });
+        self
+    }
+}
+
+type VisibilityQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Visibility,
+        &'static mut InheritedVisibility,
+        Option<&'static Children>,
+        Option<&'static WorldSpaceChildren>,
+    ),
+>;
+
+/// Hide [`Visibility::Inherited`] world space children of hidden entities,
+/// since bevy only propagates visibility through [`ChildOf`].
+pub(crate) fn propagate_world_space_visibility(mut query: VisibilityQuery) {
+    let hidden: Vec<Entity> = query
+        .iter()
+        .filter(|(_, inherited, ..)| !inherited.get())
+        .filter_map(|(.., world_space_children)| world_space_children)
+        .flatten()
+        .collect();
+    for entity in hidden {
+        hide_inherited(&mut query, entity);
+    }
+}
+
+fn hide_inherited(query: &mut VisibilityQuery, entity: Entity) {
+    let Ok((visibility, mut inherited, children, world_space_children)) = query.get_mut(entity)
+    else {
+        return;
+    };
+    if *visibility != Visibility::Inherited || !inherited.get() {
+        return;
+    }
+    *inherited = InheritedVisibility::HIDDEN;
+    let descendants: Vec<Entity> = children
+        .into_iter()
+        .flat_map(|x| x.iter().copied())
+        .chain(world_space_children.into_iter().flatten())
+        .collect();
+    for descendant in descendants {
+        hide_inherited(query, descendant);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        ecs::{hierarchy::ChildOf, world::World},
+        math::{Quat, Vec3},
+        transform::components::{GlobalTransform, Transform},
+    };
+
+    use super::DetachToWorldSpaceExt;
+    use crate::ProjectileVelocity;
+
+    #[test]
+    fn detach_with_velocity_in_world_space() {
+        let mut world = World::new();
+        let parent_transform = Transform::from_rotation(Quat::from_rotation_y(90f32.to_radians()))
+            .with_scale(Vec3::splat(2.));
+        let grandparent = world
+            .spawn((
+                Transform::default(),
+                GlobalTransform::from(parent_transform),
+            ))
+            .id();
+        let parent = world
+            .spawn((
+                Transform::default(),
+                GlobalTransform::from(parent_transform),
+                ProjectileVelocity(Vec3::X),
+                ChildOf(grandparent),
+            ))
+            .id();
+        let child = world
+            .spawn((
+                Transform::default(),
+                GlobalTransform::from(parent_transform),
+                ProjectileVelocity(Vec3::X),
+                ChildOf(parent),
+            ))
+            .id();
+        world
+            .entity_mut(child)
+            .detach_to_world_space_with_velocity();
+        // Both velocities are rotated to `-Z` and scaled by 2.
+        let velocity = world.get::<ProjectileVelocity>(child).unwrap().0;
+        assert!(
+            velocity.abs_diff_eq(Vec3::new(0., 0., -4.), 1e-5),
+            "{velocity}"
+        );
+        assert!(world.get::<ChildOf>(child).is_none());
+    }
 }
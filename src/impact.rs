@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use bevy::{
+    ecs::{
+        component::{Component, HookContext},
+        entity::Entity,
+        resource::Resource,
+        world::{DeferredWorld, EntityWorldMut},
+    },
+    platform::collections::HashMap,
+    transform::components::Transform,
+};
+
+use crate::{Hit, ProjectileBundle, ProjectileContext, ProjectileInstance};
+
+/// Marks the surface type of an entity, like stone, flesh or water,
+/// so projectiles can spawn matching effects from [`ImpactEffects`].
+///
+/// Surfaces are tracked by [`ImpactEffects`], so entities must be spawned after it is initialized by
+/// [`ProjectilePlugin`](crate::ProjectilePlugin).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+#[component(on_insert = register_surface, on_replace = unregister_surface)]
+pub struct ImpactSurface(pub u32);
+
+fn register_surface(mut world: DeferredWorld, cx: HookContext) {
+    let Some(surface) = world.get::<ImpactSurface>(cx.entity).copied() else {
+        return;
+    };
+    if let Some(mut effects) = world.get_resource_mut::<ImpactEffects>() {
+        effects.surfaces.insert(cx.entity, surface);
+    }
+}
+
+fn unregister_surface(mut world: DeferredWorld, cx: HookContext) {
+    if let Some(mut effects) = world.get_resource_mut::<ImpactEffects>() {
+        effects.surfaces.remove(&cx.entity);
+    }
+}
+
+type ImpactFn = Arc<dyn Fn(&mut ProjectileContext, Transform) + Send + Sync>;
+
+/// Maps [`ImpactSurface`]s to impact effects.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// const STONE: ImpactSurface = ImpactSurface(1);
+/// const WATER: ImpactSurface = ImpactSurface(2);
+///
+/// struct Dust;
+/// impl Projectile for Dust {}
+///
+/// struct Splash;
+/// impl Projectile for Splash {}
+///
+/// struct Bullet;
+///
+/// impl Projectile for Bullet {
+///     fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
+///         for hit in cx.hits().to_vec() {
+///             cx.spawn_impact_on(&hit);
+///         }
+///     }
+/// }
+///
+/// fn setup(mut effects: ResMut<ImpactEffects>) {
+///     effects.insert(STONE, || Dust);
+///     effects.insert(WATER, || Splash);
+/// }
+/// ```
+#[derive(Default, Resource)]
+pub struct ImpactEffects {
+    effects: HashMap<ImpactSurface, ImpactFn>,
+    fallback: Option<ImpactFn>,
+    surfaces: HashMap<Entity, ImpactSurface>,
+}
+
+fn impact_fn<B: ProjectileBundle>(effect: impl Fn() -> B + Send + Sync + 'static) -> ImpactFn {
+    Arc::new(move |cx, transform| {
+        let (projectile, bundle) = effect().into_projectile_bundle(&mut cx.resources);
+        cx.commands
+            .spawn((ProjectileInstance::new(projectile), bundle))
+            .queue(move |mut entity: EntityWorldMut| {
+                let offset = entity.get::<Transform>().copied().unwrap_or_default();
+                entity.insert(transform * offset);
+            });
+    })
+}
+
+impl ImpactEffects {
+    /// Set the effect of a surface, the [`Transform`] of the effect is an offset from the impact.
+    pub fn insert<B: ProjectileBundle>(
+        &mut self,
+        surface: ImpactSurface,
+        effect: impl Fn() -> B + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.effects.insert(surface, impact_fn(effect));
+        self
+    }
+
+    /// Set the effect of surfaces without an effect and entities without an [`ImpactSurface`].
+    pub fn set_fallback<B: ProjectileBundle>(
+        &mut self,
+        effect: impl Fn() -> B + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.fallback = Some(impact_fn(effect));
+        self
+    }
+
+    pub fn remove(&mut self, surface: ImpactSurface) {
+        self.effects.remove(&surface);
+    }
+
+    /// Obtain the [`ImpactSurface`] of an entity.
+    pub fn surface_of(&self, entity: Entity) -> Option<ImpactSurface> {
+        self.surfaces.get(&entity).copied()
+    }
+
+    fn get(&self, surface: Option<ImpactSurface>) -> Option<ImpactFn> {
+        surface
+            .and_then(|x| self.effects.get(&x))
+            .or(self.fallback.as_ref())
+            .cloned()
+    }
+}
+
+impl ProjectileContext<'_, '_> {
+    fn spawn_impact(&mut self, surface: Option<ImpactSurface>, transform: Transform) {
+        let Some(effect) = self
+            .resources
+            .get::<ImpactEffects>()
+            .ok()
+            .and_then(|x| x.get(surface))
+        else {
+            return;
+        };
+        effect(self, transform);
+    }
+
+    /// Spawn the impact effect of `surface` from [`ImpactEffects`] at the current position of this projectile.
+    ///
+    /// Does nothing if the surface has no effect and no fallback is set.
+    pub fn spawn_impact_for(&mut self, surface: ImpactSurface) {
+        let transform = self.global_transform().compute_transform();
+        self.spawn_impact(Some(surface), transform);
+    }
+
+    /// Spawn the impact effect of the hit entity's [`ImpactSurface`] at the point of the hit,
+    /// facing this projectile's rotation.
+    ///
+    /// Does nothing if the surface has no effect and no fallback is set.
+    pub fn spawn_impact_on(&mut self, hit: &Hit) {
+        let surface = self
+            .resources
+            .get::<ImpactEffects>()
+            .ok()
+            .and_then(|x| x.surface_of(hit.entity));
+        let transform = Transform::from_translation(hit.point)
+            .with_rotation(self.global_transform().rotation());
+        self.spawn_impact(surface, transform);
+    }
+}
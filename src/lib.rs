@@ -1,101 +1,419 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::type_complexity)]
 use bevy::{
-    app::{App, Plugin, Update},
+    app::{App, FixedUpdate, Plugin, PostUpdate, Update},
+    asset::Assets,
     ecs::{
+        change_detection::Mut,
         entity::Entity,
-        query::Without,
-        schedule::IntoScheduleConfigs,
+        event::Event,
+        query::{QueryEntityError, Without},
+        schedule::{
+            InternedScheduleLabel, InternedSystemSet, IntoScheduleConfigs, ScheduleLabel,
+            SystemSet, common_conditions::resource_exists,
+        },
         system::{
             Commands, FilteredResourcesMutParamBuilder, ParamBuilder, Query, SystemParamBuilder,
         },
-        world::{EntityMutExcept, FilteredResourcesMut},
+        world::{EntityMutExcept, FilteredResourcesMut, World},
+    },
+    render::{mesh::Mesh, view::VisibilitySystems},
+    time::Time,
+    transform::{
+        TransformSystem,
+        components::{GlobalTransform, Transform},
     },
-    time::{Time, Virtual},
-    transform::components::{GlobalTransform, Transform},
 };
 
+mod attractor;
+#[cfg(feature = "avian")]
+mod avian;
+mod billboard;
+mod bone;
+mod bounds;
 mod builder;
 mod bundle;
+mod chain;
 mod cluster;
+mod collision;
+mod command;
 mod control;
+mod curve;
+#[cfg(feature = "debug")]
+mod debug;
+mod def;
+mod diagnostics;
+mod dilation;
+pub mod dissolve;
+mod events;
+mod expiry;
+#[cfg(feature = "debug")]
+mod fairness;
+mod force;
+mod group;
 mod hierarchy;
-pub use builder::WithSpawner;
+mod impact;
+pub mod material;
+pub mod mesh;
+mod metrics;
+mod motion;
+mod path;
+mod pause;
+mod pool;
+pub mod prelude;
+mod query;
+pub use attractor::ProjectileAttractor;
+pub use billboard::Billboard;
+pub use bone::{AttachToBoneExt, Bones};
+pub use bounds::{ProjectileBounds, UpdateAabb};
+pub use builder::{ActivateWithinRange, WithSpawner};
 pub mod spawning;
+mod storage;
+pub mod trail;
 mod traits;
+mod typed;
+#[cfg(feature = "ui")]
+mod ui;
 pub mod util;
+mod wobble;
 pub use bundle::{BundleOrAsset, ProjectileBundle};
+pub use chain::Chain;
+pub use cluster::ProjectileCommand;
 pub use cluster::SpawnerCluster;
-use cluster::{ProjectileCommand, projectile_command_system};
+use cluster::projectile_command_system;
+pub use collision::{
+    ContinuousCollision, Hit, HitDetection, HitTarget, ProjectileCollider, ProjectileHit,
+    ProjectileHits,
+};
+pub use command::{OnCommand, TypedProjectileCommand};
 pub use control::ProjectileContext;
+pub use curve::{CurveAsset, CurveAssetLoader, CurveAssetLoaderError, CurveParam};
+#[cfg(feature = "debug")]
+pub use debug::{ProjectileDebugPlugin, ProjectileGizmos};
+pub use def::{
+    DefProjectile, EmitterDef, EmitterShape, FacCurve, ProjectileDef, ProjectileDefLoader,
+    ProjectileDefLoaderError, ProjectileDefPlugin,
+};
+pub use diagnostics::{ProjectileDiagnostics, ProjectileDiagnosticsPlugin};
+pub use dilation::TimeDilationZone;
+pub use events::{ProjectileExpired, ProjectileFinished, ProjectileSpawned};
+pub use expiry::{
+    ExpireOn, ExpireWithOwner, LingerAfterExpire, MaxDistance, MaxProjectileLifetime,
+    ProjectileOwner,
+};
+#[cfg(feature = "debug")]
+pub use fairness::{SpawnFairnessReport, SpawnFairnessSample, SpawnFairnessTest};
 pub use fastrand::Rng;
+pub use force::{AffectedByForceFields, ForceFields, ForceZone, ForceZoneShape};
+pub use group::{ProjectileGroup, ProjectileGroups};
 pub use hierarchy::*;
-pub use traits::{Projectile, ProjectileInstance, ProjectileSpace, ProjectileSpawner};
+pub use impact::{ImpactEffects, ImpactSurface};
+pub use metrics::SpawnerMetrics;
+pub use motion::{
+    MeasuredVelocity, ProjectileAcceleration, ProjectileAngularVelocity, ProjectileVelocity,
+};
+pub use path::PathProjectile;
+pub use pause::ProjectilePaused;
+pub use pool::ProjectilePool;
+pub use query::ProjectileQuery;
+use traits::ErasedProjectile;
+pub use traits::{
+    CommandResult, Projectile, ProjectileInstance, ProjectileSpace, ProjectileSpawner,
+};
+pub use typed::TypedProjectile;
+#[cfg(feature = "ui")]
+pub use ui::UiProjectile;
+pub use wobble::Wobble;
 pub mod loading;
 
+/// Procedural texture generation, re-exported from `bevy_texture_gen` with the `texture` feature.
+#[cfg(feature = "texture")]
+pub use bevy_texture_gen as texture;
+
 type DefaultProjectileBundle = (ProjectileInstance, Transform, GlobalTransform);
 
-pub fn projectile_update(
-    mut resources: FilteredResourcesMut,
-    mut commands: Commands,
-    query: Query<(
+type UpdateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
         Entity,
         &'static mut ProjectileInstance,
         &'static mut Transform,
         &'static GlobalTransform,
         EntityMutExcept<'static, DefaultProjectileBundle>,
-    )>,
-    mut tracking: Query<
-        (&'static Transform, &'static GlobalTransform),
-        Without<ProjectileInstance>,
-    >,
+    ),
+>;
+
+type TrackingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static GlobalTransform,
+        Option<&'static MeasuredVelocity>,
+        Option<&'static ProjectileVelocity>,
+    ),
+    Without<ProjectileInstance>,
+>;
+
+pub fn projectile_update(
+    mut resources: FilteredResourcesMut,
+    mut commands: Commands,
+    query: UpdateQuery,
+    mut tracking: TrackingQuery,
 ) {
     let Ok((dt, elapsed)) = resources
-        .get::<Time<Virtual>>()
+        .get::<Time>()
         .map(|x| (x.delta_secs(), x.elapsed_secs()))
     else {
         return;
     };
     // Safety: cannot access the same entity, enforced by `ProjectileContext`.
-    for (entity, projectile, transform, global_transform, entity_mut) in
-        unsafe { query.iter_unsafe() }
-    {
-        // Allow split borrow.
-        let projectile = projectile.into_inner();
-        if projectile.done {
-            if projectile.root && projectile.rc.should_drop() {
-                commands.entity(entity).despawn();
-            }
+    for item in unsafe { query.iter_unsafe() } {
+        if item.1.is_typed() {
             continue;
         }
-        projectile.lifetime += dt;
-        let cx = ProjectileContext {
-            transform,
-            global_transform,
-            entity_mut,
-            resources: resources.reborrow(),
-            commands: commands.reborrow(),
-            // Safety: cannot access the same entity, enforced by `ProjectileContext`.
-            unsafe_other: unsafe { query.reborrow_unsafe() },
-            tracking: tracking.reborrow(),
-            elapsed_time: elapsed,
-            lifetime: projectile.lifetime,
-            rc: &projectile.rc,
-            fac: 0.,
-        };
-        if projectile.projectile.update(cx, dt) {
-            projectile.done = true;
-            projectile.rc.release();
+        update_single(
+            item,
+            &mut resources,
+            &mut commands,
+            &query,
+            &mut tracking,
+            (dt, elapsed),
+            |projectile, cx, dt| projectile.update(cx, dt),
+        );
+    }
+}
+
+/// Update a single projectile, returns true if done.
+fn update_single(
+    (entity, projectile, mut transform, global_transform, mut entity_mut): (
+        Entity,
+        Mut<ProjectileInstance>,
+        Mut<Transform>,
+        &GlobalTransform,
+        EntityMutExcept<DefaultProjectileBundle>,
+    ),
+    resources: &mut FilteredResourcesMut,
+    commands: &mut Commands,
+    query: &UpdateQuery,
+    tracking: &mut TrackingQuery,
+    (dt, elapsed): (f32, f32),
+    update: impl FnOnce(&mut dyn ErasedProjectile, ProjectileContext, f32) -> bool,
+) -> bool {
+    // Allow split borrow.
+    let projectile = projectile.into_inner();
+    if projectile.done {
+        if projectile.root && projectile.rc.should_drop() {
+            commands.trigger_targets(ProjectileFinished { root: entity }, entity);
+            commands.entity(entity).despawn();
+        }
+        return true;
+    }
+    if pause::is_paused((&entity_mut).into(), query) {
+        return false;
+    }
+    let dt = match entity_mut.get::<ProjectileGroup>() {
+        Some(group) => match resources
+            .get::<ProjectileGroups>()
+            .ok()
+            .map(|x| x.delta_scale(*group))
+        {
+            Some(Some(scale)) => dt * scale,
+            Some(None) => return false,
+            None => dt,
+        },
+        None => dt,
+    };
+    let dt = match resources.get::<dilation::TimeDilationZones>() {
+        Ok(zones) => dt * zones.scale_at(global_transform.translation()),
+        Err(_) => dt,
+    };
+    projectile.lifetime += dt;
+    motion::integrate(&mut entity_mut, &mut transform, dt);
+    if let Ok(fields) = resources.get::<ForceFields>() {
+        let position = global_transform.translation();
+        force::apply_force_fields(&mut entity_mut, &fields, position, elapsed, dt);
+    }
+    if let Ok(attractors) = resources.get::<attractor::Attractors>() {
+        let position = global_transform.translation();
+        attractor::apply_attractors(&mut entity_mut, &attractors, position, dt);
+    }
+    let cx = ProjectileContext {
+        transform,
+        global_transform,
+        entity_mut,
+        resources: resources.reborrow(),
+        commands: commands.reborrow(),
+        // Safety: cannot access the same entity, enforced by `ProjectileContext`.
+        unsafe_other: unsafe { query.reborrow_unsafe() },
+        tracking: tracking.reborrow(),
+        elapsed_time: elapsed,
+        lifetime: projectile.lifetime,
+        rc: &projectile.rc,
+        fac: 0.,
+    };
+    if update(&mut *projectile.projectile, cx, dt) {
+        projectile.done = true;
+        projectile.rc.release();
+        // The root may have despawned itself in `on_expire`, finish it now.
+        if projectile.root && projectile.rc.should_drop() {
+            commands.trigger_targets(ProjectileFinished { root: entity }, entity);
+            if !resources
+                .get::<ProjectilePool>()
+                .is_ok_and(|pool| pool.is_pending(entity))
+            {
+                commands.entity(entity).try_despawn();
+            }
         }
     }
+    projectile.done
+}
+
+impl ProjectileInstance {
+    /// Advance a single projectile by `dt` outside of [`ProjectilePlugin`],
+    /// for tools, tests and custom schedules.
+    ///
+    /// Behaves like [`projectile_update`] for this entity, [`ProjectileGroup`] scaling still applies.
+    /// Commands queued by the projectile are applied before returning.
+    ///
+    /// Returns true if the projectile is done, or an error if `entity` is not a projectile.
+    pub fn tick(world: &mut World, entity: Entity, dt: f32) -> Result<bool, QueryEntityError> {
+        let elapsed = world.get_resource::<Time>().map_or(0., Time::elapsed_secs);
+        let mut state = (
+            FilteredResourcesMutParamBuilder::new(|builder| {
+                builder.add_write_all();
+            }),
+            ParamBuilder::of::<Commands>(),
+            ParamBuilder::of::<UpdateQuery>(),
+            ParamBuilder::of::<TrackingQuery>(),
+        )
+            .build_state(world);
+        let (mut resources, mut commands, query, mut tracking) = state.get_mut(world);
+        // Safety: `query` is not otherwise accessed while `item` is alive.
+        let item = unsafe { query.get_unchecked(entity) }?;
+        let done = update_single(
+            item,
+            &mut resources,
+            &mut commands,
+            &query,
+            &mut tracking,
+            (dt, elapsed),
+            |projectile, cx, dt| projectile.update(cx, dt),
+        );
+        state.apply(world);
+        Ok(done)
+    }
+}
+
+/// [`SystemSet`]s of [`ProjectilePlugin`] in its configured schedule, chained in order:
+///
+/// [`Commands`](ProjectileSystems::Commands), [`HitDetection`],
+/// [`Update`](ProjectileSystems::Update), [`Cleanup`](ProjectileSystems::Cleanup).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub enum ProjectileSystems {
+    /// Applies projectile commands and resets per frame state like [`ProjectileHits`].
+    Commands,
+    /// Runs [`projectile_update`].
+    Update,
+    /// Clears group operations, recycles projectiles and applies post update effects like [`Billboard`].
+    Cleanup,
 }
 
 /// Plugin for [`bevy_javelin`](crate).
-pub struct ProjectilePlugin;
+///
+/// By default projectiles are updated in [`Update`] with [`Time<Virtual>`](bevy::time::Virtual).
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// # #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)] enum MyGameSet { Vfx }
+/// # let mut app = App::new();
+/// app.add_plugins(ProjectilePlugin::in_schedule(PostUpdate).in_set(MyGameSet::Vfx));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProjectilePlugin {
+    schedule: InternedScheduleLabel,
+    set: Option<InternedSystemSet>,
+    extensions: Vec<fn(&mut App, InternedScheduleLabel)>,
+    max_lifetime: Option<f32>,
+}
+
+impl Default for ProjectilePlugin {
+    fn default() -> Self {
+        ProjectilePlugin::in_schedule(Update)
+    }
+}
+
+impl ProjectilePlugin {
+    /// Update projectiles in a schedule, [`Time`] is read as the default time of the schedule.
+    ///
+    /// Purely visual systems like [`UvAnimation`](material::UvAnimation) still run in [`Update`].
+    pub fn in_schedule(schedule: impl ScheduleLabel) -> Self {
+        ProjectilePlugin {
+            schedule: schedule.intern(),
+            set: None,
+            extensions: Vec::new(),
+            max_lifetime: None,
+        }
+    }
+
+    /// Update projectiles in [`FixedUpdate`] with [`Time<Fixed>`](bevy::time::Fixed),
+    /// so gameplay projectiles stay deterministic and in sync with physics.
+    pub fn fixed_update() -> Self {
+        ProjectilePlugin::in_schedule(FixedUpdate)
+    }
+
+    /// Put all [`ProjectileSystems`] in a parent [`SystemSet`].
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.set = Some(set.intern());
+        self
+    }
+
+    /// Update projectiles of type `T` in a dedicated system without dynamic dispatch,
+    /// as an optimization for the few types that dominate entity counts.
+    ///
+    /// Projectiles of this type spawned by spawners are stored in [`TypedProjectile`],
+    /// use [`ProjectileInstance::typed`] to spawn root projectiles of this type.
+    pub fn with_typed<T: Projectile>(mut self) -> Self {
+        self.extensions.push(typed::register_typed::<T>);
+        self
+    }
+
+    /// Register a [`TypedProjectileCommand`] that sends commands of type `C` to projectiles of type `P`.
+    pub fn with_command<P: OnCommand<C>, C: Send + Sync + 'static>(mut self) -> Self {
+        self.extensions.push(command::register_command::<P, C>);
+        self
+    }
+
+    /// Force expire projectiles alive longer than `seconds`, see [`MaxProjectileLifetime`].
+    pub fn with_max_lifetime(mut self, seconds: f32) -> Self {
+        self.max_lifetime = Some(seconds);
+        self
+    }
+
+    /// Register [`ExpireOn<E>`], expiring projectiles when `E` is sent or triggered.
+    pub fn with_expire_on<E: Event>(mut self) -> Self {
+        self.extensions.push(expiry::register_expire_on::<E>);
+        self
+    }
+}
 
 impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
+        let schedule = self.schedule;
         app.add_event::<ProjectileCommand>();
+        app.register_type::<ProjectileVelocity>();
+        app.register_type::<ProjectileAngularVelocity>();
+        app.register_type::<ProjectileAcceleration>();
+        app.init_resource::<ProjectileGroups>();
+        app.init_resource::<ForceFields>();
+        app.init_resource::<ImpactEffects>();
+        app.init_resource::<dilation::TimeDilationZones>();
+        if let Some(seconds) = self.max_lifetime {
+            app.insert_resource(MaxProjectileLifetime(seconds));
+        }
+        app.init_resource::<attractor::Attractors>();
         let system = (
             FilteredResourcesMutParamBuilder::new(|builder| {
                 builder.add_write_all();
@@ -106,7 +424,82 @@ impl Plugin for ProjectilePlugin {
         )
             .build_state(app.world_mut())
             .build_system(projectile_update);
-        app.add_systems(Update, projectile_command_system);
-        app.add_systems(Update, system.after(projectile_command_system));
+        let sets = (
+            ProjectileSystems::Commands,
+            HitDetection,
+            ProjectileSystems::Update,
+            ProjectileSystems::Cleanup,
+        )
+            .chain();
+        match self.set {
+            Some(set) => app.configure_sets(schedule, sets.in_set(set)),
+            None => app.configure_sets(schedule, sets),
+        };
+        app.add_systems(
+            schedule,
+            (
+                projectile_command_system,
+                collision::clear_hits,
+                expiry::expire_with_owner,
+                attractor::collect_attractors,
+                dilation::collect_time_dilation_zones,
+                wobble::remove_wobble,
+            )
+                .in_set(ProjectileSystems::Commands),
+        );
+        app.add_systems(
+            schedule,
+            collision::hit_detection_system.in_set(HitDetection),
+        );
+        #[cfg(feature = "avian")]
+        app.add_systems(schedule, avian::avian_hit_system.in_set(HitDetection));
+        app.add_systems(schedule, system.in_set(ProjectileSystems::Update));
+        #[cfg(feature = "ui")]
+        app.add_systems(
+            schedule,
+            (
+                ui::restore_ui_transform.in_set(ProjectileSystems::Commands),
+                ui::write_ui_node
+                    .in_set(ProjectileSystems::Cleanup)
+                    .before(bevy::ui::UiSystem::Layout),
+            ),
+        );
+        for register in &self.extensions {
+            register(app, schedule);
+        }
+        app.add_systems(
+            schedule,
+            (
+                group::clear_group_expiry,
+                pool::recycle_system,
+                expiry::linger_system,
+                dissolve::dissolve_system
+                    .run_if(resource_exists::<Assets<dissolve::DissolveMaterial>>),
+                billboard::billboard_system,
+                wobble::apply_wobble.before(billboard::billboard_system),
+            )
+                .in_set(ProjectileSystems::Cleanup),
+        );
+        app.add_systems(Update, material::uv_animation_system);
+        app.add_systems(
+            PostUpdate,
+            (
+                bounds::update_aabb
+                    .run_if(resource_exists::<Assets<Mesh>>)
+                    .after(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::CheckVisibility),
+                hierarchy::propagate_world_space_visibility
+                    .after(VisibilitySystems::VisibilityPropagate)
+                    .before(VisibilitySystems::CheckVisibility),
+                bounds::update_projectile_bounds
+                    .after(TransformSystem::TransformPropagate)
+                    .after(bounds::update_aabb),
+                motion::measure_velocity.after(TransformSystem::TransformPropagate),
+                trail::trail_system
+                    .run_if(resource_exists::<Assets<Mesh>>)
+                    .after(TransformSystem::TransformPropagate)
+                    .before(VisibilitySystems::CalculateBounds),
+            ),
+        );
     }
 }
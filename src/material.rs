@@ -0,0 +1,71 @@
+//! Material animation components driven by the projectile plugin.
+
+use bevy::{
+    asset::Assets,
+    ecs::{
+        component::Component,
+        system::{Query, Res, ResMut},
+    },
+    math::{Affine2, Vec2},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    sprite::{ColorMaterial, MeshMaterial2d},
+    time::Time,
+};
+
+/// Scroll and rotate the `uv_transform` of a [`StandardMaterial`] or [`ColorMaterial`] every frame.
+///
+/// # Note
+///
+/// The material asset is modified, so all entities sharing the material are animated.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct UvAnimation {
+    /// Uv offset per second.
+    pub velocity: Vec2,
+    /// Radians per second, rotates around the center of the uv space.
+    pub rotation_speed: f32,
+}
+
+impl UvAnimation {
+    pub const fn scroll(velocity: Vec2) -> Self {
+        UvAnimation {
+            velocity,
+            rotation_speed: 0.,
+        }
+    }
+
+    pub const fn rotate(rotation_speed: f32) -> Self {
+        UvAnimation {
+            velocity: Vec2::ZERO,
+            rotation_speed,
+        }
+    }
+
+    /// Advance a `uv_transform` by `dt`.
+    pub fn advance(&self, uv_transform: &mut Affine2, dt: f32) {
+        let center = Vec2::splat(0.5);
+        let rotation = Affine2::from_translation(center)
+            * Affine2::from_angle(self.rotation_speed * dt)
+            * Affine2::from_translation(-center);
+        *uv_transform = Affine2::from_translation(self.velocity * dt) * rotation * *uv_transform;
+    }
+}
+
+pub(crate) fn uv_animation_system(
+    time: Res<Time>,
+    mut standard: Option<ResMut<Assets<StandardMaterial>>>,
+    mut color: Option<ResMut<Assets<ColorMaterial>>>,
+    query3d: Query<(&UvAnimation, &MeshMaterial3d<StandardMaterial>)>,
+    query2d: Query<(&UvAnimation, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    let dt = time.delta_secs();
+    for (animation, material) in &query3d {
+        if let Some(material) = standard.as_mut().and_then(|x| x.get_mut(material.id())) {
+            animation.advance(&mut material.uv_transform, dt);
+        }
+    }
+    for (animation, material) in &query2d {
+        if let Some(material) = color.as_mut().and_then(|x| x.get_mut(material.id())) {
+            animation.advance(&mut material.uv_transform, dt);
+        }
+    }
+}
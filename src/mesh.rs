@@ -0,0 +1,95 @@
+//! Meshes of simple effects, with uv layouts suited for scrolling textures.
+//!
+//! Bevy's primitives map uvs for tiling surfaces, these meshes instead map `u` or `v`
+//! along the direction effects usually scroll, see [`UvAnimation`](crate::material::UvAnimation).
+
+use std::f32::consts::TAU;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    math::Vec3,
+    render::mesh::{Indices, Mesh, PrimitiveTopology},
+};
+
+pub(crate) fn build_mesh(
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+) -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// A `1x1` quad on the `XY` plane facing `Z`, centered at the origin.
+///
+/// `u` goes from left to right and `v` from top to bottom, matching the orientation of the texture.
+pub fn vfx_quad() -> Mesh {
+    build_mesh(
+        vec![
+            [-0.5, 0.5, 0.],
+            [0.5, 0.5, 0.],
+            [-0.5, -0.5, 0.],
+            [0.5, -0.5, 0.],
+        ],
+        vec![[0., 0., 1.]; 4],
+        vec![[0., 0.], [1., 0.], [0., 1.], [1., 1.]],
+        vec![0, 2, 1, 1, 2, 3],
+    )
+}
+
+/// An open cone with its tip at the origin, opening towards forward (`-Z`) with length `1` and radius `1`,
+/// for muzzle flashes and shockwaves.
+///
+/// `u` goes around the cone with a seam at `X`, `v` goes from the tip (`0`) to the base (`1`),
+/// so scrolling `v` moves the texture outwards.
+pub fn vfx_cone(segments: usize) -> Mesh {
+    let segments = segments.max(3);
+    let mut positions = Vec::with_capacity((segments + 1) * 2);
+    let mut normals = Vec::with_capacity((segments + 1) * 2);
+    let mut uvs = Vec::with_capacity((segments + 1) * 2);
+    for i in 0..=segments {
+        let u = i as f32 / segments as f32;
+        let (sin, cos) = (u * TAU).sin_cos();
+        let normal = Vec3::new(cos, sin, 1.).normalize().to_array();
+        positions.extend([[0., 0., 0.], [cos, sin, -1.]]);
+        normals.extend([normal, normal]);
+        uvs.extend([[u, 0.], [u, 1.]]);
+    }
+    let mut indices = Vec::with_capacity(segments * 3);
+    for i in 0..segments as u32 {
+        let (tip, base) = (i * 2, i * 2 + 1);
+        indices.extend([tip, base, base + 2]);
+    }
+    build_mesh(positions, normals, uvs, indices)
+}
+
+/// A flat strip of `n` segments on the `XZ` plane facing `Y`, with width `1` from the origin to forward (`-Z`)
+/// with length `1`, as the base mesh of trails and beams.
+///
+/// Vertices come in pairs of left (`-X`) and right (`+X`) per segment boundary,
+/// so vertex `2 * i` and `2 * i + 1` are at `i / n` along the strip.
+///
+/// `u` goes along the strip from `0` to `1` and `v` goes across from left (`0`) to right (`1`).
+pub fn trail_strip(n: usize) -> Mesh {
+    let n = n.max(1);
+    let mut positions = Vec::with_capacity((n + 1) * 2);
+    let mut uvs = Vec::with_capacity((n + 1) * 2);
+    for i in 0..=n {
+        let u = i as f32 / n as f32;
+        positions.extend([[-0.5, 0., -u], [0.5, 0., -u]]);
+        uvs.extend([[u, 0.], [u, 1.]]);
+    }
+    let mut indices = Vec::with_capacity(n * 6);
+    for i in 0..n as u32 {
+        let (left, right) = (i * 2, i * 2 + 1);
+        indices.extend([left, right, left + 2, right, right + 2, left + 2]);
+    }
+    build_mesh(positions, vec![[0., 1., 0.]; (n + 1) * 2], uvs, indices)
+}
@@ -0,0 +1,82 @@
+use bevy::ecs::{component::Component, error::ignore, world::EntityWorldMut};
+
+use crate::ProjectileContext;
+
+/// Counts projectiles spawned by this spawner and how its children expired.
+///
+/// Add this to a spawner to start counting, only direct children spawned via
+/// [`ProjectileSpawner::spawn_projectile`](crate::ProjectileSpawner::spawn_projectile) are counted.
+///
+/// Expiry counts are applied with commands, so they lag one frame behind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component)]
+pub struct SpawnerMetrics {
+    pub spawned: u64,
+    /// Expired while [`ProjectileContext::hits`] is not empty.
+    pub expired_by_hit: u64,
+    /// Expired after `lifetime` reached [`Projectile::duration`](crate::Projectile::duration).
+    pub expired_by_timeout: u64,
+    /// Expired by other means, like [`MaxDistance`](crate::MaxDistance) or a custom condition.
+    pub expired_other: u64,
+}
+
+impl SpawnerMetrics {
+    /// Total amount of expired children.
+    pub fn expired(&self) -> u64 {
+        self.expired_by_hit + self.expired_by_timeout + self.expired_other
+    }
+
+    /// Ratio of expired children that hit something, `0` if none expired.
+    pub fn accuracy(&self) -> f32 {
+        match self.expired() {
+            0 => 0.,
+            expired => self.expired_by_hit as f32 / expired as f32,
+        }
+    }
+}
+
+/// Reason of a projectile's expiry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExpiryReason {
+    Hit,
+    Timeout,
+    Other,
+}
+
+impl ProjectileContext<'_, '_> {
+    /// Obtain [`SpawnerMetrics`] of this spawner, if present.
+    pub fn spawner_metrics(&self) -> Option<&SpawnerMetrics> {
+        self.entity_mut.get::<SpawnerMetrics>()
+    }
+
+    pub(crate) fn record_spawn(&mut self) {
+        if let Some(mut metrics) = self.entity_mut.get_mut::<SpawnerMetrics>() {
+            metrics.spawned += 1;
+        }
+    }
+
+    /// Record the expiry of this projectile on the [`SpawnerMetrics`] of its parent.
+    pub(crate) fn record_expiry(&mut self, duration: f32) {
+        let Some(parent) = self.parent() else {
+            return;
+        };
+        let reason = if !self.hits().is_empty() {
+            ExpiryReason::Hit
+        } else if self.lifetime > duration {
+            ExpiryReason::Timeout
+        } else {
+            ExpiryReason::Other
+        };
+        self.commands.entity(parent).queue_handled(
+            move |mut entity: EntityWorldMut| {
+                if let Some(mut metrics) = entity.get_mut::<SpawnerMetrics>() {
+                    match reason {
+                        ExpiryReason::Hit => metrics.expired_by_hit += 1,
+                        ExpiryReason::Timeout => metrics.expired_by_timeout += 1,
+                        ExpiryReason::Other => metrics.expired_other += 1,
+                    }
+                }
+            },
+            ignore,
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        reflect::ReflectComponent,
+        system::{Query, Res},
+        world::EntityMutExcept,
+    },
+    math::{Quat, Vec3},
+    reflect::Reflect,
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+};
+
+use crate::DefaultProjectileBundle;
+
+/// Velocity in units per second, integrated into [`Transform::translation`](bevy::transform::components::Transform::translation)
+/// before [`Projectile::update`](crate::Projectile::update) is called.
+///
+/// This keeps motion state inspectable via the ECS instead of hidden in projectile fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileVelocity(pub Vec3);
+
+/// Angular velocity as an axis scaled by radians per second in parent space,
+/// integrated into [`Transform::rotation`](bevy::transform::components::Transform::rotation)
+/// before [`Projectile::update`](crate::Projectile::update) is called.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileAngularVelocity(pub Vec3);
+
+/// Constant acceleration in units per second squared, like gravity,
+/// integrated into [`ProjectileVelocity`] before [`Projectile::update`](crate::Projectile::update) is called.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Reflect)]
+#[reflect(Component)]
+#[require(ProjectileVelocity)]
+pub struct ProjectileAcceleration(pub Vec3);
+
+/// Integrate motion components, matching [`PhysicsExt::acceleration`](crate::util::PhysicsExt::acceleration).
+pub(crate) fn integrate(
+    entity_mut: &mut EntityMutExcept<DefaultProjectileBundle>,
+    transform: &mut Transform,
+    dt: f32,
+) {
+    let acceleration = entity_mut.get::<ProjectileAcceleration>().copied();
+    if let Some(mut velocity) = entity_mut.get_mut::<ProjectileVelocity>() {
+        transform.translation += velocity.0 * dt;
+        if let Some(acceleration) = acceleration {
+            velocity.0 += acceleration.0 * dt;
+        }
+    }
+    if let Some(angular) = entity_mut.get::<ProjectileAngularVelocity>()
+        && angular.0 != Vec3::ZERO
+    {
+        transform.rotation = Quat::from_scaled_axis(angular.0 * dt) * transform.rotation;
+    }
+}
+
+/// Measures world space velocity from the change of [`GlobalTransform`] between frames,
+/// including motion from parents and animations.
+///
+/// Used by [`detach_to_world_space_with_velocity`](crate::DetachToWorldSpaceExt::detach_to_world_space_with_velocity)
+/// when present on the parent, and by [`ProjectileContext::velocity_of`](crate::ProjectileContext::velocity_of).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component)]
+pub struct MeasuredVelocity {
+    previous: Option<Vec3>,
+    velocity: Vec3,
+}
+
+impl MeasuredVelocity {
+    /// Velocity in units per second during the last frame.
+    pub fn get(&self) -> Vec3 {
+        self.velocity
+    }
+}
+
+pub(crate) fn measure_velocity(
+    time: Res<Time>,
+    mut query: Query<(&mut MeasuredVelocity, &GlobalTransform)>,
+) {
+    let dt = time.delta_secs();
+    for (mut measured, transform) in &mut query {
+        let current = transform.translation();
+        if let Some(previous) = measured.previous
+            && dt > 0.
+        {
+            measured.velocity = (current - previous) / dt;
+        }
+        measured.previous = Some(current);
+    }
+}
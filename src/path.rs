@@ -0,0 +1,116 @@
+use bevy::math::{
+    Vec3,
+    curve::{Curve, derivatives::SampleDerivative},
+};
+use fastrand::Rng;
+
+use crate::{Projectile, ProjectileContext, util::ProjectileRng};
+
+/// A projectile that follows a [`SampleDerivative`] curve like a [`CubicCurve`](bevy::math::cubic_splines::CubicCurve)
+/// over its `duration`, facing forward along the path.
+///
+/// The curve is in the space of the projectile's [`Transform`](bevy::transform::components::Transform),
+/// i.e. world space for root and world space projectiles.
+/// The facing direction is the derivative of the curve and retained where the curve stops moving.
+///
+/// Combine with [`Projectile::with_spawner`] to spawn particles along the path.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::{*, spawning::*};
+/// let curve = CubicBezier::new([[
+///     Vec3::ZERO,
+///     Vec3::new(0., 5., -5.),
+///     Vec3::new(5., 5., -10.),
+///     Vec3::new(5., 0., -15.),
+/// ]])
+/// .to_curve()
+/// .unwrap();
+///
+/// let spawner = SpawnRate::new(4.).into_spawner_world(move |rng, _| {
+///     PathProjectile::new(curve.clone(), 2.).with_random_offset(rng, 0.5)
+/// });
+/// ```
+pub struct PathProjectile<C> {
+    pub curve: C,
+    pub duration: f32,
+    /// If true, restarts from the beginning of the curve instead of expiring.
+    pub looping: bool,
+    /// Offset added to every point of the curve.
+    pub offset: Vec3,
+    direction: Vec3,
+}
+
+impl<C: Curve<Vec3>> PathProjectile<C> {
+    /// Follow `curve` over `duration` seconds.
+    ///
+    /// # Panics
+    ///
+    /// If the domain of `curve` is not bounded.
+    pub fn new(curve: C, duration: f32) -> Self {
+        assert!(
+            curve.domain().is_bounded(),
+            "PathProjectile requires a curve with a bounded domain."
+        );
+        PathProjectile {
+            curve,
+            duration,
+            looping: false,
+            offset: Vec3::ZERO,
+            direction: Vec3::ZERO,
+        }
+    }
+
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Offset the curve by a random point within `radius`, so paths from a spawner spread out.
+    pub fn with_random_offset(mut self, rng: &mut Rng, radius: f32) -> Self {
+        self.offset = rng.random_sphere() * rng.f32().cbrt() * radius;
+        self
+    }
+
+    /// Returns the point of the curve at `fac` in `0..=1`, including `offset`.
+    pub fn position(&self, fac: f32) -> Vec3 {
+        let domain = self.curve.domain();
+        self.curve
+            .sample_clamped(domain.start() + fac * domain.length())
+            + self.offset
+    }
+}
+
+impl<C: SampleDerivative<Vec3> + Send + Sync + 'static> Projectile for PathProjectile<C> {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn is_expired(&self, cx: &ProjectileContext) -> bool {
+        !self.looping && cx.lifetime > self.duration
+    }
+
+    fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
+        let fac = match self.looping {
+            true => (cx.lifetime / self.duration).fract(),
+            false => (cx.lifetime / self.duration).min(1.),
+        };
+        let domain = self.curve.domain();
+        let sample = self
+            .curve
+            .sample_with_derivative_clamped(domain.start() + fac * domain.length());
+        if let Some(direction) = sample.derivative.try_normalize() {
+            self.direction = direction;
+        }
+        let transform = cx.transform_mut();
+        transform.translation = sample.value + self.offset;
+        if self.direction != Vec3::ZERO {
+            transform.look_to(self.direction, Vec3::Y);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::ecs::{component::Component, entity::Entity, hierarchy::ChildOf, world::EntityRefExcept};
+
+use crate::{DefaultProjectileBundle, UpdateQuery, WorldSpaceChildOf};
+
+/// Pause a projectile and its descendants, including world space children,
+/// for example during cutscenes or menus.
+///
+/// Paused projectiles do not accumulate `lifetime`, move by [`ProjectileVelocity`](crate::ProjectileVelocity)
+/// or update their spawners. Remove this component to resume.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct ProjectilePaused;
+
+fn parent_of(entity: &EntityRefExcept<DefaultProjectileBundle>) -> Option<Entity> {
+    entity.get::<ChildOf>().map(ChildOf::parent).or_else(|| {
+        entity
+            .get::<WorldSpaceChildOf>()
+            .map(WorldSpaceChildOf::parent)
+    })
+}
+
+/// Returns true if the projectile or one of its ancestor projectiles is [`ProjectilePaused`].
+pub(crate) fn is_paused(
+    entity: EntityRefExcept<DefaultProjectileBundle>,
+    query: &UpdateQuery,
+) -> bool {
+    let mut current = entity;
+    loop {
+        if current.contains::<ProjectilePaused>() {
+            return true;
+        }
+        match parent_of(&current).and_then(|parent| query.get(parent).ok()) {
+            Some((.., parent)) => current = parent,
+            None => return false,
+        }
+    }
+}
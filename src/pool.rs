@@ -0,0 +1,266 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        error::ignore,
+        hierarchy::{ChildOf, Children},
+        resource::Resource,
+        system::{Commands, Query, ResMut},
+        world::EntityWorldMut,
+    },
+    platform::collections::HashMap,
+    render::view::Visibility,
+    transform::components::Transform,
+};
+
+use crate::{
+    ContinuousCollision, MeasuredVelocity, ProjectileContext, ProjectileInstance, Wobble,
+    WorldSpaceChildOf, WorldSpaceChildren,
+    expiry::{DistanceOrigin, ExpireRequested},
+};
+
+/// Reuse expired projectiles instead of despawning them, to avoid archetype churn.
+///
+/// Projectiles recycled via [`ProjectileContext::recycle`] are hidden, detached and stored by projectile type,
+/// spawners reuse them when spawning a projectile of the same type.
+///
+/// This is opt-in, insert this resource to enable pooling, otherwise [`ProjectileContext::recycle`] despawns.
+///
+/// # Note
+///
+/// The spawned bundle is inserted on the reused entity, components not in the bundle
+/// keep their previous values, except [`Transform`] and [`Visibility`] which are reset.
+///
+/// Like despawning, recycling despawns local space children,
+/// world space children are detached and keep updating as roots.
+#[derive(Debug, Resource)]
+pub struct ProjectilePool {
+    /// Maximum amount of pooled entities per projectile type, further recycled entities are despawned.
+    pub max_per_type: usize,
+    pool: HashMap<TypeId, Vec<(Entity, Visibility)>>,
+    pending: Vec<Entity>,
+}
+
+impl Default for ProjectilePool {
+    fn default() -> Self {
+        ProjectilePool::new(256)
+    }
+}
+
+impl ProjectilePool {
+    pub fn new(max_per_type: usize) -> Self {
+        ProjectilePool {
+            max_per_type,
+            pool: HashMap::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the amount of pooled entities.
+    pub fn len(&self) -> usize {
+        self.pool.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pool.values().all(Vec::is_empty)
+    }
+
+    /// Returns true if `entity` is recycled this frame.
+    pub(crate) fn is_pending(&self, entity: Entity) -> bool {
+        self.pending.contains(&entity)
+    }
+
+    /// Take a pooled entity and reset it, the caller must insert a new [`ProjectileInstance`].
+    ///
+    /// Entities no longer alive according to `is_alive` are discarded.
+    pub(crate) fn take(
+        &mut self,
+        commands: &mut Commands,
+        type_id: TypeId,
+        is_alive: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        let entries = self.pool.get_mut(&type_id)?;
+        let (entity, visibility) = loop {
+            let (entity, visibility) = entries.pop()?;
+            if is_alive(entity) {
+                break (entity, visibility);
+            }
+        };
+        commands.entity(entity).queue_handled(
+            move |mut entity: EntityWorldMut| {
+                entity.insert((visibility, Transform::default()));
+                if let Some(mut origin) = entity.get_mut::<DistanceOrigin>() {
+                    *origin = DistanceOrigin::default();
+                }
+                if let Some(mut requested) = entity.get_mut::<ExpireRequested>() {
+                    *requested = ExpireRequested::default();
+                }
+                if let Some(mut continuous) = entity.get_mut::<ContinuousCollision>() {
+                    continuous.previous = None;
+                }
+                if let Some(mut measured) = entity.get_mut::<MeasuredVelocity>() {
+                    *measured = MeasuredVelocity::default();
+                }
+                if let Some(mut wobble) = entity.get_mut::<Wobble>() {
+                    wobble.reset();
+                }
+            },
+            ignore,
+        );
+        Some(entity)
+    }
+}
+
+impl ProjectileContext<'_, '_> {
+    /// Return this projectile to the [`ProjectilePool`] instead of despawning it,
+    /// usually called in [`Projectile::on_expire`](crate::Projectile::on_expire).
+    ///
+    /// Despawns if [`ProjectilePool`] is not present.
+    pub fn recycle(&mut self) {
+        let entity = self.entity();
+        match self.resources.get_mut::<ProjectilePool>() {
+            Ok(mut pool) => pool.pending.push(entity),
+            Err(_) => self.despawn(),
+        }
+    }
+}
+
+pub(crate) fn recycle_system(
+    mut commands: Commands,
+    pool: Option<ResMut<ProjectilePool>>,
+    mut query: Query<(&mut ProjectileInstance, &mut Visibility)>,
+) {
+    let Some(mut pool) = pool else {
+        return;
+    };
+    let pool = pool.as_mut();
+    for entity in pool.pending.drain(..) {
+        let Ok((mut instance, mut visibility)) = query.get_mut(entity) else {
+            continue;
+        };
+        let type_id = instance.projectile_type_id();
+        let entries = pool.pool.entry(type_id).or_default();
+        if entries.len() >= pool.max_per_type {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        entries.push((entity, *visibility));
+        *instance = ProjectileInstance::dormant();
+        *visibility = Visibility::Hidden;
+        // Like despawning, local children are despawned and world space children become roots.
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .remove::<(ChildOf, WorldSpaceChildOf, WorldSpaceChildren)>();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        ecs::{entity::Entity, hierarchy::ChildOf, query::With, system::RunSystemOnce},
+        platform::collections::HashSet,
+        prelude::MinimalPlugins,
+        time::TimeUpdateStrategy,
+        transform::TransformPlugin,
+    };
+
+    use super::{ProjectilePool, recycle_system};
+    use crate::{
+        Projectile, ProjectileContext, ProjectileInstance, ProjectilePlugin, WorldSpaceChildOf,
+        spawning::{ProjectileSpawning, SpawnRate},
+    };
+
+    struct Bullet;
+
+    impl Projectile for Bullet {
+        fn duration(&self) -> f32 {
+            0.25
+        }
+
+        fn on_expire(&mut self, cx: &mut ProjectileContext) {
+            cx.recycle();
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, ProjectilePlugin::default()));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app.insert_resource(ProjectilePool::default());
+        app.world_mut().spawn(ProjectileInstance::spawner(
+            SpawnRate::new(10.).into_spawner_world(|_, _| Bullet),
+        ));
+        app
+    }
+
+    /// Live bullets, pooled entities are detached from the spawner.
+    fn bullets(app: &mut App) -> Vec<Entity> {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, With<WorldSpaceChildOf>>();
+        query.iter(app.world()).collect()
+    }
+
+    #[test]
+    fn take_and_recycle() {
+        let mut app = app();
+        let mut seen = HashSet::new();
+        for _ in 0..30 {
+            app.update();
+            seen.extend(bullets(&mut app));
+        }
+        // About 30 bullets are spawned, but only a few entities are ever used.
+        assert!(seen.len() <= 8, "{}", seen.len());
+        assert!(!app.world().resource::<ProjectilePool>().is_empty());
+    }
+
+    #[test]
+    fn skip_despawned() {
+        let mut app = app();
+        for _ in 0..10 {
+            app.update();
+        }
+        let pooled: Vec<Entity> = app
+            .world()
+            .resource::<ProjectilePool>()
+            .pool
+            .values()
+            .flatten()
+            .map(|(entity, _)| *entity)
+            .collect();
+        assert!(!pooled.is_empty());
+        for entity in &pooled {
+            app.world_mut().despawn(*entity);
+        }
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(!bullets(&mut app).is_empty());
+    }
+
+    #[test]
+    fn recycle_children() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.spawn(ProjectileInstance::new(Bullet)).id();
+        let local = world
+            .spawn((ProjectileInstance::new(Bullet), ChildOf(entity)))
+            .id();
+        let world_space = world
+            .spawn((ProjectileInstance::new(Bullet), WorldSpaceChildOf(entity)))
+            .id();
+        world.resource_mut::<ProjectilePool>().pending.push(entity);
+        world.run_system_once(recycle_system).unwrap();
+        assert!(world.get_entity(entity).is_ok());
+        assert!(world.get_entity(local).is_err());
+        assert!(world.get::<WorldSpaceChildOf>(world_space).is_none());
+        assert_eq!(world.resource::<ProjectilePool>().len(), 1);
+    }
+}
@@ -0,0 +1,34 @@
+//! Commonly used traits, components and plugins.
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_javelin::prelude::*;
+//! ```
+
+#[cfg(feature = "debug")]
+pub use crate::ProjectileDebugPlugin;
+#[cfg(feature = "ui")]
+pub use crate::UiProjectile;
+#[cfg(feature = "texture")]
+pub use crate::texture;
+pub use crate::{
+    AffectedByForceFields, AttachToBoneExt, Billboard, Bones, BundleOrAsset, CommandResult,
+    DetachToWorldSpaceExt, ExpireOn, ExpireWithOwner, ForceFields, ForceZone, Hit, HitTarget,
+    ImpactEffects, ImpactSurface, LingerAfterExpire, MaxDistance, MaxProjectileLifetime,
+    MeasuredVelocity, OnCommand, PathProjectile, Projectile, ProjectileAcceleration,
+    ProjectileAngularVelocity, ProjectileAttractor, ProjectileBundle, ProjectileCollider,
+    ProjectileCommand, ProjectileContext, ProjectileDefPlugin, ProjectileDiagnosticsPlugin,
+    ProjectileExpired, ProjectileFinished, ProjectileHit, ProjectileInstance, ProjectileOwner,
+    ProjectilePaused, ProjectilePlugin, ProjectileQuery, ProjectileSpace, ProjectileSpawned,
+    ProjectileSpawner, ProjectileSystems, ProjectileVelocity, Rng, TimeDilationZone,
+    TypedProjectile, TypedProjectileCommand, Wobble, WorldSpaceChildOf, WorldSpaceChildren,
+    dissolve::{DissolveOnExpire, DissolvePlugin},
+    loading::{AddMat2, AddMat3, AddMesh2, AddMesh3, Load, LoadMesh2, LoadMesh3},
+    material::UvAnimation,
+    spawning::{
+        Burst, Limit, ProjectileSpawning, RandomBursts, SpawnPerDistance, SpawnRate,
+        StandardSpawner,
+    },
+    trail::Trail,
+    util::{ConditionOnce, PhysicsExt, ProjectileRng, SteeringExt},
+};
@@ -0,0 +1,137 @@
+use bevy::ecs::{change_detection::Mut, entity::Entity, system::Query, system::SystemParam};
+
+use crate::{Projectile, ProjectileInstance, TypedProjectile};
+
+/// A [`SystemParam`] to access live projectiles of type `P` from ordinary systems,
+/// including [`TypedProjectile`]s.
+///
+/// Finished projectiles are skipped. This accesses [`ProjectileInstance`] mutably,
+/// so it does not run in parallel with other systems accessing projectiles.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// struct Missile {
+///     target: Entity,
+/// }
+///
+/// impl Projectile for Missile {}
+///
+/// fn retarget(mut missiles: ProjectileQuery<Missile>, decoy: Single<Entity, With<Name>>) {
+///     for (_, mut missile) in missiles.iter_mut() {
+///         missile.target = *decoy;
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ProjectileQuery<'w, 's, P: Projectile> {
+    query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut ProjectileInstance,
+            Option<&'static mut TypedProjectile<P>>,
+        ),
+    >,
+}
+
+impl<P: Projectile> ProjectileQuery<'_, '_, P> {
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &P)> {
+        self.query
+            .iter()
+            .filter(|(_, instance, _)| !instance.done)
+            .filter_map(|(entity, instance, typed)| match typed {
+                Some(typed) => typed.get().map(|x| (entity, x)),
+                None => instance.downcast_ref::<P>().map(|x| (entity, x)),
+            })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, Mut<'_, P>)> {
+        self.query
+            .iter_mut()
+            .filter(|(_, instance, _)| !instance.done)
+            .filter_map(|(entity, instance, typed)| {
+                match typed {
+                    Some(typed) => Mut::filter_map_unchanged(typed, |x| x.get_mut()),
+                    None => ProjectileInstance::map_mut::<P>(instance),
+                }
+                .map(|x| (entity, x))
+            })
+    }
+
+    /// Obtain a live projectile of type `P`.
+    pub fn get(&self, entity: Entity) -> Option<&P> {
+        let (_, instance, typed) = self.query.get(entity).ok()?;
+        if instance.done {
+            return None;
+        }
+        match typed {
+            Some(typed) => typed.get(),
+            None => instance.downcast_ref::<P>(),
+        }
+    }
+
+    /// Obtain a live projectile of type `P` mutably.
+    pub fn get_mut(&mut self, entity: Entity) -> Option<Mut<'_, P>> {
+        let (_, instance, typed) = self.query.get_mut(entity).ok()?;
+        if instance.done {
+            return None;
+        }
+        match typed {
+            Some(typed) => Mut::filter_map_unchanged(typed, |x| x.get_mut()),
+            None => ProjectileInstance::map_mut::<P>(instance),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        app::{App, Update},
+        ecs::system::SystemState,
+        prelude::MinimalPlugins,
+    };
+
+    use super::ProjectileQuery;
+    use crate::{Projectile, ProjectileInstance, ProjectilePlugin};
+
+    struct Ammo(u32);
+
+    impl Projectile for Ammo {
+        fn duration(&self) -> f32 {
+            100.
+        }
+    }
+
+    struct Other;
+
+    impl Projectile for Other {}
+
+    #[test]
+    fn dynamic_and_typed() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            ProjectilePlugin::default().with_typed::<Ammo>(),
+        ));
+        let dynamic = app.world_mut().spawn(ProjectileInstance::new(Ammo(1))).id();
+        let typed = app
+            .world_mut()
+            .spawn(ProjectileInstance::typed(Ammo(2)))
+            .id();
+        let other = app.world_mut().spawn(ProjectileInstance::new(Other)).id();
+        app.add_systems(Update, |mut query: ProjectileQuery<Ammo>| {
+            for (_, mut ammo) in query.iter_mut() {
+                ammo.0 += 10;
+            }
+        });
+        app.update();
+        let mut state = SystemState::<ProjectileQuery<Ammo>>::new(app.world_mut());
+        let query = state.get_mut(app.world_mut());
+        assert_eq!(query.get(dynamic).map(|x| x.0), Some(11));
+        assert_eq!(query.get(typed).map(|x| x.0), Some(12));
+        assert!(query.get(other).is_none());
+        assert_eq!(query.iter().count(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 use std::ops::RangeInclusive;
 
-use bevy::ecs::hierarchy::Children;
+use bevy::{
+    ecs::hierarchy::Children, math::Vec3, render::view::Visibility,
+    transform::components::Transform,
+};
 use fastrand::Rng;
 
 use crate::{
@@ -8,13 +11,24 @@ use crate::{
 };
 
 /// A projectile spawning rate controller.
+#[allow(unused_variables)]
 pub trait ProjectileSpawning: Send + Sync + Sized + 'static {
     fn update(&mut self, dt: f32);
 
+    /// Called by [`StandardSpawner`] with the distance the spawner traveled this frame,
+    /// for spawn rates driven by distance like [`SpawnPerDistance`].
+    fn travel(&mut self, distance: f32) {}
+
     fn try_spawn(&mut self) -> bool;
 
     fn finished(&self) -> bool;
 
+    /// Seconds since the last successful [`try_spawn`](ProjectileSpawning::try_spawn) should have happened
+    /// within the current frame, used to stagger high rate streams.
+    fn spawn_offset(&self) -> f32 {
+        0.
+    }
+
     /// If should spawn, call the function.
     fn spawn<T>(&mut self, f: impl FnOnce() -> T) -> Option<T> {
         if self.try_spawn() { Some(f()) } else { None }
@@ -65,6 +79,14 @@ pub trait ProjectileSpawning: Send + Sync + Sized + 'static {
             spawn_fn,
             rng: Rng::new(),
             space: ProjectileSpace::Local,
+            origin: None,
+            visibility: None,
+            staggered: false,
+            interpolated: false,
+            previous: None,
+            latest: None,
+            dt: 0.,
+            last_position: None,
         }
     }
 
@@ -78,6 +100,14 @@ pub trait ProjectileSpawning: Send + Sync + Sized + 'static {
             spawn_fn,
             rng: Rng::new(),
             space: ProjectileSpace::World,
+            origin: None,
+            visibility: None,
+            staggered: false,
+            interpolated: false,
+            previous: None,
+            latest: None,
+            dt: 0.,
+            last_position: None,
         }
     }
 }
@@ -87,6 +117,7 @@ pub trait ProjectileSpawning: Send + Sync + Sized + 'static {
 pub struct SpawnRate {
     times_per_second: f32,
     meta: f32,
+    dt: f32,
 }
 
 impl SpawnRate {
@@ -94,6 +125,7 @@ impl SpawnRate {
         Self {
             times_per_second,
             meta: 0.0,
+            dt: 0.0,
         }
     }
 
@@ -135,6 +167,77 @@ impl ProjectileSpawning for SpawnRate {
 
     fn update(&mut self, dt: f32) {
         self.meta += self.times_per_second * dt;
+        self.dt = dt;
+    }
+
+    fn spawn_offset(&self) -> f32 {
+        if self.times_per_second > 0.0 {
+            (self.meta / self.times_per_second).min(self.dt)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Spawn projectiles evenly in space by the distance the spawner traveled instead of time,
+/// for bullet tracers and footstep dust that should not depend on speed.
+///
+/// Only works with [`StandardSpawner`], which reports the distance traveled by its [`GlobalTransform`](bevy::transform::components::GlobalTransform).
+/// Combine with [`StandardSpawner::interpolated`] to place projectiles along the traveled path.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpawnPerDistance {
+    per_unit: f32,
+    meta: f32,
+    dt: f32,
+    distance: f32,
+}
+
+impl SpawnPerDistance {
+    /// Spawn `per_unit` projectiles per unit of distance traveled.
+    pub const fn new(per_unit: f32) -> Self {
+        Self {
+            per_unit,
+            meta: 0.0,
+            dt: 0.0,
+            distance: 0.0,
+        }
+    }
+
+    pub const fn set(&mut self, per_unit: f32) {
+        self.per_unit = per_unit;
+    }
+}
+
+impl ProjectileSpawning for SpawnPerDistance {
+    fn finished(&self) -> bool {
+        false
+    }
+
+    fn try_spawn(&mut self) -> bool {
+        if self.meta >= 1.0 {
+            self.meta -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        self.dt = dt;
+        self.distance = 0.0;
+    }
+
+    fn travel(&mut self, distance: f32) {
+        self.meta += self.per_unit * distance;
+        self.distance = distance;
+    }
+
+    fn spawn_offset(&self) -> f32 {
+        if self.per_unit > 0.0 && self.distance > 0.0 {
+            (self.meta / self.per_unit / self.distance * self.dt).min(self.dt)
+        } else {
+            0.0
+        }
     }
 }
 
@@ -171,6 +274,10 @@ impl<T: ProjectileSpawning> ProjectileSpawning for Limit<T> {
         self.base.update(dt);
     }
 
+    fn travel(&mut self, distance: f32) {
+        self.base.travel(distance);
+    }
+
     fn try_spawn(&mut self) -> bool {
         if self.count > 0 && self.base.try_spawn() {
             self.count -= 1;
@@ -182,6 +289,10 @@ impl<T: ProjectileSpawning> ProjectileSpawning for Limit<T> {
     fn finished(&self) -> bool {
         self.count == 0 || self.base.finished()
     }
+
+    fn spawn_offset(&self) -> f32 {
+        self.base.spawn_offset()
+    }
 }
 
 /// Spawn projectiles in bursts.
@@ -198,6 +309,10 @@ impl<T: ProjectileSpawning> ProjectileSpawning for RandomBursts<T> {
         self.base.update(dt);
     }
 
+    fn travel(&mut self, distance: f32) {
+        self.base.travel(distance);
+    }
+
     fn try_spawn(&mut self) -> bool {
         if self.current > 0 {
             self.current -= 1;
@@ -218,6 +333,10 @@ impl<T: ProjectileSpawning> ProjectileSpawning for RandomBursts<T> {
     fn finished(&self) -> bool {
         self.base.finished()
     }
+
+    fn spawn_offset(&self) -> f32 {
+        self.base.spawn_offset()
+    }
 }
 
 pub struct StandardSpawner<T, F> {
@@ -225,6 +344,19 @@ pub struct StandardSpawner<T, F> {
     pub spawn_fn: F,
     pub rng: Rng,
     pub space: ProjectileSpace,
+    /// Offset from the spawner, if set, spawned [`Transform`]s are relative to it.
+    pub origin: Option<Transform>,
+    /// Visibility of spawned projectiles, see [`ProjectileSpawner::child_visibility`].
+    pub visibility: Option<Visibility>,
+    /// If true, offsets the `lifetime` of spawned projectiles by [`ProjectileSpawning::spawn_offset`].
+    pub staggered: bool,
+    /// If true, interpolates the origin between the previous and current transform of the spawner
+    /// by [`ProjectileSpawning::spawn_offset`].
+    pub interpolated: bool,
+    previous: Option<Transform>,
+    latest: Option<Transform>,
+    dt: f32,
+    last_position: Option<Vec3>,
 }
 
 impl<T, F> StandardSpawner<T, F> {
@@ -233,6 +365,42 @@ impl<T, F> StandardSpawner<T, F> {
         self.rng = Rng::with_seed(seed);
         self
     }
+
+    /// Spawn projectiles relative to the current transform of the spawner,
+    /// for world space spawners this seeds from its [`GlobalTransform`](bevy::transform::components::GlobalTransform).
+    ///
+    /// The [`Transform`] returned by `spawn_fn` becomes an offset from the spawner.
+    pub fn relative_to_spawner(self) -> Self {
+        self.relative_to_spawner_with_offset(Transform::IDENTITY)
+    }
+
+    /// Spawn projectiles relative to the current transform of the spawner with an additional offset.
+    pub fn relative_to_spawner_with_offset(mut self, offset: Transform) -> Self {
+        self.origin = Some(offset);
+        self
+    }
+
+    /// Offset the `lifetime` of spawned projectiles by when they should have spawned within the frame,
+    /// so high rate streams do not clump into per frame packets.
+    pub fn staggered(mut self) -> Self {
+        self.staggered = true;
+        self
+    }
+
+    /// Interpolate the spawner's transform across the frame when spawning,
+    /// so streams from a moving spawner are evenly spaced instead of spawning at the same point.
+    ///
+    /// Only affects world space spawners [`relative_to_spawner`](Self::relative_to_spawner).
+    pub fn interpolated(mut self) -> Self {
+        self.interpolated = true;
+        self
+    }
+
+    /// Overwrite the default [`Visibility::Visible`] of spawned projectiles.
+    pub fn with_child_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
 }
 
 impl<T, F, U> ProjectileSpawner for StandardSpawner<T, F>
@@ -252,8 +420,49 @@ where
         self.space
     }
 
-    fn update(&mut self, _: &mut crate::ProjectileContext, dt: f32) {
+    fn child_visibility(&self) -> Option<Visibility> {
+        self.visibility
+    }
+
+    fn lifetime_offset(&self) -> f32 {
+        match self.staggered {
+            true => self.spawning.spawn_offset(),
+            false => 0.,
+        }
+    }
+
+    fn spawn_origin(&self, cx: &ProjectileContext) -> Option<Transform> {
+        let offset = self.origin?;
+        match self.space {
+            ProjectileSpace::Local => Some(offset),
+            ProjectileSpace::World => {
+                let current = cx.global_transform().compute_transform();
+                let Some(previous) = self.previous.filter(|_| self.interpolated && self.dt > 0.)
+                else {
+                    return Some(current * offset);
+                };
+                let t = (1. - self.spawning.spawn_offset() / self.dt).clamp(0., 1.);
+                let origin = Transform {
+                    translation: previous.translation.lerp(current.translation, t),
+                    rotation: previous.rotation.slerp(current.rotation, t),
+                    scale: previous.scale.lerp(current.scale, t),
+                };
+                Some(origin * offset)
+            }
+        }
+    }
+
+    fn update(&mut self, cx: &mut crate::ProjectileContext, dt: f32) {
         self.spawning.update(dt);
+        let position = cx.global_transform().translation();
+        if let Some(last) = self.last_position.replace(position) {
+            self.spawning.travel(position.distance(last));
+        }
+        if self.interpolated && self.space == ProjectileSpace::World {
+            let current = cx.global_transform().compute_transform();
+            self.previous = self.latest.replace(current);
+            self.dt = dt;
+        }
     }
 
     fn is_complete(&self, _: &crate::ProjectileContext) -> bool {
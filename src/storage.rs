@@ -0,0 +1,207 @@
+use std::{
+    cell::UnsafeCell,
+    mem::{MaybeUninit, align_of, size_of},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::traits::ErasedProjectile;
+
+/// Size of the inline buffer of [`ProjectileBox`] in bytes.
+const INLINE_SIZE: usize = 48;
+
+/// Stored projectiles may mutate through `&self`, so the buffer is an `UnsafeCell`.
+#[repr(C, align(16))]
+struct Buffer(UnsafeCell<[MaybeUninit<u8>; INLINE_SIZE]>);
+
+/// A `Box<dyn ErasedProjectile>` that stores small projectiles inline to avoid a heap allocation.
+///
+/// Projectiles up to 48 bytes with alignment up to 16 are stored inline.
+pub struct ProjectileBox(Storage);
+
+enum Storage {
+    Inline {
+        buffer: Buffer,
+        /// Cast a pointer to the buffer into the stored type, then unsize it.
+        as_dyn: unsafe fn(*mut u8) -> *mut dyn ErasedProjectile,
+    },
+    Boxed(Box<dyn ErasedProjectile>),
+}
+
+// Safety: the buffer only contains an `ErasedProjectile`, which is `Send` and `Sync`.
+unsafe impl Send for ProjectileBox {}
+// Safety: the buffer only contains an `ErasedProjectile`, which is `Send` and `Sync`.
+unsafe impl Sync for ProjectileBox {}
+
+/// # Safety
+///
+/// `ptr` must point to a valid `T`.
+unsafe fn as_dyn<T: ErasedProjectile>(ptr: *mut u8) -> *mut dyn ErasedProjectile {
+    ptr.cast::<T>()
+}
+
+impl ProjectileBox {
+    pub fn new<T: ErasedProjectile>(projectile: T) -> Self {
+        if !ProjectileBox::is_inline::<T>() {
+            return ProjectileBox(Storage::Boxed(Box::new(projectile)));
+        }
+        let buffer = Buffer(UnsafeCell::new([MaybeUninit::uninit(); INLINE_SIZE]));
+        // Safety: size and alignment are checked above.
+        unsafe { ptr::write(buffer.0.get().cast::<T>(), projectile) };
+        ProjectileBox(Storage::Inline {
+            buffer,
+            as_dyn: as_dyn::<T>,
+        })
+    }
+
+    /// Returns true if `T` is stored inline.
+    pub const fn is_inline<T>() -> bool {
+        size_of::<T>() <= INLINE_SIZE && align_of::<T>() <= align_of::<Buffer>()
+    }
+}
+
+impl Deref for ProjectileBox {
+    type Target = dyn ErasedProjectile;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            // Safety: the buffer contains the type `as_dyn` is created from.
+            Storage::Inline { buffer, as_dyn } => unsafe { &*as_dyn(buffer.0.get().cast()) },
+            Storage::Boxed(boxed) => boxed.as_ref(),
+        }
+    }
+}
+
+impl DerefMut for ProjectileBox {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            // Safety: the buffer contains the type `as_dyn` is created from.
+            Storage::Inline { buffer, as_dyn } => unsafe { &mut *as_dyn(buffer.0.get().cast()) },
+            Storage::Boxed(boxed) => boxed.as_mut(),
+        }
+    }
+}
+
+impl Drop for ProjectileBox {
+    fn drop(&mut self) {
+        if let Storage::Inline { buffer, as_dyn } = &self.0 {
+            // Safety: the buffer contains the type `as_dyn` is created from and is dropped only once.
+            unsafe { ptr::drop_in_place(as_dyn(buffer.0.get().cast())) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        any::Any,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    use super::ProjectileBox;
+    use crate::{CommandResult, ProjectileContext, traits::ErasedProjectile};
+
+    struct Counted<const N: usize>(Arc<AtomicUsize>, [u8; N]);
+
+    impl<const N: usize> Drop for Counted<N> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    impl<const N: usize> ErasedProjectile for Counted<N> {
+        fn type_name(&self) -> &'static str {
+            "Counted"
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn get_fac(&self, lifetime: f32) -> f32 {
+            lifetime + N as f32
+        }
+
+        fn update(&mut self, _: ProjectileContext, _: f32) -> bool {
+            false
+        }
+
+        fn apply_command(&mut self, _: &dyn Any) -> CommandResult {
+            CommandResult::Consume
+        }
+    }
+
+    /// Counts calls to `get_fac` inline, through `&self`.
+    struct Interior(AtomicUsize);
+
+    impl ErasedProjectile for Interior {
+        fn type_name(&self) -> &'static str {
+            "Interior"
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn get_fac(&self, lifetime: f32) -> f32 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            lifetime
+        }
+
+        fn update(&mut self, _: ProjectileContext, _: f32) -> bool {
+            false
+        }
+
+        fn apply_command(&mut self, _: &dyn Any) -> CommandResult {
+            CommandResult::Consume
+        }
+    }
+
+    fn check<const N: usize>(inline: bool) {
+        let drops = Arc::new(AtomicUsize::new(0));
+        assert_eq!(ProjectileBox::is_inline::<Counted<N>>(), inline);
+        let mut boxed = ProjectileBox::new(Counted::<N>(drops.clone(), [7; N]));
+        let moved = vec![std::mem::replace(
+            &mut boxed,
+            ProjectileBox::new(Counted::<N>(drops.clone(), [8; N])),
+        )];
+        assert_eq!(boxed.get_fac(1.), 1. + N as f32);
+        let counted = moved[0].as_any().downcast_ref::<Counted<N>>().unwrap();
+        assert_eq!(counted.1, [7; N]);
+        boxed.as_any_mut().downcast_mut::<Counted<N>>().unwrap().1 = [9; N];
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+        drop(moved);
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+        drop(boxed);
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn inline_and_boxed() {
+        check::<0>(true);
+        check::<16>(true);
+        check::<64>(false);
+    }
+
+    #[test]
+    fn interior_mutability() {
+        assert!(ProjectileBox::is_inline::<Interior>());
+        let boxed = ProjectileBox::new(Interior(AtomicUsize::new(0)));
+        let shared = &boxed;
+        shared.get_fac(0.);
+        shared.get_fac(0.);
+        let interior = boxed.as_any().downcast_ref::<Interior>().unwrap();
+        assert_eq!(interior.0.load(Ordering::Relaxed), 2);
+    }
+}
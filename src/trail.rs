@@ -0,0 +1,235 @@
+//! Ribbon meshes that trail behind moving projectiles.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use bevy::{
+    asset::Assets,
+    color::{ColorToComponents, LinearRgba},
+    ecs::{
+        bundle::Bundle,
+        component::Component,
+        entity::Entity,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{Vec3, curve::Curve},
+    render::{
+        mesh::{Mesh, Mesh3d},
+        primitives::Aabb,
+        view::Visibility,
+    },
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+};
+
+use crate::{ProjectileContext, mesh::build_mesh};
+
+/// A point recorded from the target's [`GlobalTransform`].
+#[derive(Debug, Clone, Copy)]
+struct TrailPoint {
+    position: Vec3,
+    right: Vec3,
+    up: Vec3,
+    age: f32,
+}
+
+/// A ribbon mesh that follows the [`GlobalTransform`] of a target entity, usually a projectile.
+///
+/// The ribbon spans the target's local `X` axis. The [`Mesh3d`] of this entity is rewritten every frame,
+/// with `u` going from the head (`0`) to the end of its lifetime (`1`) and `v` across the ribbon,
+/// like [`trail_strip`](crate::mesh::trail_strip). Add any material, colors are written as vertex colors.
+///
+/// This entity should not have a parent. After the target despawns, the trail fades out and despawns itself.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::{*, trail::Trail};
+/// struct Arrow {
+///     material: Handle<StandardMaterial>,
+/// }
+///
+/// impl Projectile for Arrow {
+///     fn on_spawn(&mut self, cx: &mut ProjectileContext) {
+///         let trail = Trail::new(0.2, 0.5).with_width_curve(EasingCurve::new(1., 0., EaseFunction::Linear));
+///         cx.spawn_trail(trail, MeshMaterial3d(self.material.clone()));
+///     }
+/// }
+/// ```
+#[derive(Clone, Component)]
+#[require(Transform, Visibility)]
+pub struct Trail {
+    /// Entity to follow.
+    pub target: Entity,
+    /// Width of the ribbon at the head.
+    pub width: f32,
+    /// Seconds a recorded point lives.
+    pub lifetime: f32,
+    /// Maximum amount of recorded points, each point adds 2 vertices.
+    pub max_points: usize,
+    /// Minimum distance between recorded points.
+    pub min_distance: f32,
+    /// Multiplies `width`, sampled by `age / lifetime` of each point.
+    pub width_curve: Option<Arc<dyn Curve<f32> + Send + Sync>>,
+    /// Vertex color sampled by `age / lifetime` of each point, white if [`None`].
+    pub color: Option<Arc<dyn Curve<LinearRgba> + Send + Sync>>,
+    points: VecDeque<TrailPoint>,
+}
+
+impl Trail {
+    pub fn new(width: f32, lifetime: f32) -> Self {
+        Trail {
+            target: Entity::PLACEHOLDER,
+            width,
+            lifetime,
+            max_points: 64,
+            min_distance: 0.1,
+            width_curve: None,
+            color: None,
+            points: VecDeque::new(),
+        }
+    }
+
+    pub fn following(mut self, target: Entity) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_width_curve(mut self, curve: impl Curve<f32> + Send + Sync + 'static) -> Self {
+        self.width_curve = Some(Arc::new(curve));
+        self
+    }
+
+    pub fn with_color(mut self, curve: impl Curve<LinearRgba> + Send + Sync + 'static) -> Self {
+        self.color = Some(Arc::new(curve));
+        self
+    }
+
+    pub fn with_max_points(mut self, max_points: usize) -> Self {
+        self.max_points = max_points;
+        self
+    }
+
+    pub fn with_min_distance(mut self, min_distance: f32) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Age points and remove expired ones.
+    fn advance(&mut self, dt: f32) {
+        for point in &mut self.points {
+            point.age += dt;
+        }
+        while self.points.back().is_some_and(|x| x.age > self.lifetime) {
+            self.points.pop_back();
+        }
+    }
+
+    /// Move the head to the target, or add a new head if moved far enough.
+    fn record(&mut self, transform: &GlobalTransform) {
+        let point = TrailPoint {
+            position: transform.translation(),
+            right: transform.right().into(),
+            up: transform.up().into(),
+            age: 0.,
+        };
+        let moved = match (self.points.front(), self.points.get(1)) {
+            (Some(_), Some(previous)) => previous.position.distance(point.position),
+            (Some(head), None) => head.position.distance(point.position),
+            _ => f32::MAX,
+        };
+        if moved < self.min_distance && self.points.len() >= 2 {
+            self.points[0] = point;
+        } else {
+            self.points.push_front(point);
+        }
+        self.points.truncate(self.max_points.max(2));
+    }
+
+    fn build_mesh(&self) -> (Mesh, Option<Aabb>) {
+        let len = if self.points.len() >= 2 {
+            self.points.len()
+        } else {
+            0
+        };
+        let mut positions = Vec::with_capacity(len * 2);
+        let mut normals = Vec::with_capacity(len * 2);
+        let mut uvs = Vec::with_capacity(len * 2);
+        let mut colors = Vec::with_capacity(len * 2);
+        for point in self.points.iter().take(len) {
+            let fac = (point.age / self.lifetime).clamp(0., 1.);
+            let width = self.width
+                * (self.width_curve)
+                    .as_ref()
+                    .map_or(1., |x| x.sample_clamped(fac));
+            let color = (self.color)
+                .as_ref()
+                .map_or(LinearRgba::WHITE, |x| x.sample_clamped(fac));
+            let half = point.right * (width / 2.);
+            positions.extend([
+                (point.position - half).to_array(),
+                (point.position + half).to_array(),
+            ]);
+            normals.extend([point.up.to_array(); 2]);
+            uvs.extend([[fac, 0.], [fac, 1.]]);
+            colors.extend([color.to_f32_array(); 2]);
+        }
+        let mut indices = Vec::with_capacity(len.saturating_sub(1) * 6);
+        for i in 0..len.saturating_sub(1) as u32 {
+            let (left, right) = (i * 2, i * 2 + 1);
+            indices.extend([left, right, left + 2, right, right + 2, left + 2]);
+        }
+        let aabb = positions
+            .iter()
+            .copied()
+            .map(Vec3::from)
+            .fold(None, |acc, x| match acc {
+                Some((min, max)) => Some((Vec3::min(min, x), Vec3::max(max, x))),
+                None => Some((x, x)),
+            });
+        let mesh = build_mesh(positions, normals, uvs, indices)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        (mesh, aabb.map(|(min, max)| Aabb::from_min_max(min, max)))
+    }
+}
+
+impl ProjectileContext<'_, '_> {
+    /// Spawn a [`Trail`] following this projectile, with additional components like a material.
+    pub fn spawn_trail(&mut self, trail: Trail, bundle: impl Bundle) -> Entity {
+        let entity = self.entity();
+        self.commands.spawn((trail.following(entity), bundle)).id()
+    }
+}
+
+pub(crate) fn trail_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut trails: Query<(Entity, &mut Trail, Option<&Mesh3d>, Option<&mut Aabb>)>,
+    targets: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut trail, mesh3d, aabb) in &mut trails {
+        trail.advance(dt);
+        match targets.get(trail.target) {
+            Ok(transform) => trail.record(transform),
+            Err(_) if trail.points.is_empty() => {
+                commands.entity(entity).despawn();
+                continue;
+            }
+            Err(_) => (),
+        }
+        let (mesh, bounds) = trail.build_mesh();
+        match mesh3d.and_then(|x| meshes.get_mut(x)) {
+            Some(existing) => *existing = mesh,
+            None => {
+                commands.entity(entity).insert(Mesh3d(meshes.add(mesh)));
+            }
+        }
+        match (aabb, bounds) {
+            (Some(mut aabb), Some(bounds)) => *aabb = bounds,
+            (None, Some(bounds)) => {
+                commands.entity(entity).insert(bounds);
+            }
+            _ => (),
+        }
+    }
+}
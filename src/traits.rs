@@ -7,18 +7,21 @@ use std::{
 use bevy::{
     ecs::{
         bundle::Bundle,
-        component::Component,
+        component::{Component, HookContext},
         entity::Entity,
+        error::ignore,
         hierarchy::{ChildOf, Children},
-        world::{EntityMutExcept, Mut},
+        world::{DeferredWorld, EntityMutExcept, EntityWorldMut, Mut},
     },
     render::view::Visibility,
     transform::components::Transform,
 };
 
 use crate::{
-    ProjectileBundle, ProjectileContext, WorldSpaceChildOf, WorldSpaceChildren,
-    builder::WithSpawner,
+    Hit, ProjectileBundle, ProjectileContext, ProjectileExpired, ProjectilePool, ProjectileSpawned,
+    WorldSpaceChildOf, WorldSpaceChildren,
+    builder::{ActivateWithinRange, WithSpawner},
+    storage::ProjectileBox,
 };
 
 struct DummyProjectile;
@@ -33,6 +36,27 @@ pub enum ProjectileSpace {
     World,
 }
 
+/// Result of `apply_command`, determines if a [`ProjectileCommand`](crate::ProjectileCommand)
+/// propagates to children of the projectile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommandResult {
+    /// Do not apply the command to children.
+    #[default]
+    Consume,
+    /// Apply the command to children as well.
+    Propagate,
+}
+
+impl CommandResult {
+    /// Returns [`CommandResult::Propagate`] if either is [`CommandResult::Propagate`].
+    pub fn or(self, other: CommandResult) -> CommandResult {
+        match self {
+            CommandResult::Consume => other,
+            CommandResult::Propagate => CommandResult::Propagate,
+        }
+    }
+}
+
 /// The core projectile spawner trait.
 ///
 /// A [`Projectile`] can also be a spawner via implementing [`Projectile::as_spawner`].
@@ -87,12 +111,40 @@ pub trait ProjectileSpawner: Send + Sync + 'static {
         ProjectileSpace::World
     }
 
+    /// If [`Some`], the [`Transform`] of spawned projectiles is treated as relative to this transform,
+    /// i.e. the final [`Transform`] becomes `origin * transform`.
+    ///
+    /// For a world space spawner, returning `cx.global_transform().compute_transform()`
+    /// spawns children at the current position of this projectile.
+    ///
+    /// Called after each successful [`spawn_projectile`](ProjectileSpawner::spawn_projectile).
+    fn spawn_origin(&self, cx: &ProjectileContext) -> Option<Transform> {
+        None
+    }
+
+    /// If [`Some`], overwrites the default [`Visibility::Visible`] of spawned projectiles.
+    ///
+    /// Use [`Visibility::Inherited`] so hiding this projectile also hides its children,
+    /// including world space children.
+    fn child_visibility(&self) -> Option<Visibility> {
+        None
+    }
+
+    /// Initial `lifetime` of the projectile returned by the last call to [`ProjectileSpawner::spawn_projectile`].
+    fn lifetime_offset(&self) -> f32 {
+        0.
+    }
+
     /// Runs every frame to update its content.
     /// If is also a projectile, run after `update_projectile`.
     fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {}
 
     /// Run a dynamic command on this.
-    fn apply_command(&mut self, command: &dyn Any) {}
+    ///
+    /// Return [`CommandResult::Propagate`] to apply the command to children as well.
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+        CommandResult::Consume
+    }
 
     /// Optional value that is used to calculate `fac` and
     /// by default sets `is_complete` once `lifetime` reaches `duration`.
@@ -152,6 +204,14 @@ pub trait ProjectileSpawner: Send + Sync + 'static {
             spawner: extension,
         }
     }
+
+    /// Keep this spawner dormant until `target` is within `radius`, see [`ActivateWithinRange`].
+    fn activate_within_range(self, target: Entity, radius: f32) -> ActivateWithinRange<Self>
+    where
+        Self: Sized,
+    {
+        ActivateWithinRange::new(target, radius, self)
+    }
 }
 
 /// The core projectile trait.
@@ -179,18 +239,33 @@ pub trait Projectile: Send + Sync + 'static {
         cx.lifetime > self.duration()
     }
 
+    /// Run once on the first update, before [`Projectile::update`].
+    ///
+    /// Use this for initialization that needs the entity, like a random orientation or reading parent components.
+    fn on_spawn(&mut self, cx: &mut ProjectileContext) {}
+
+    /// Run for each hit registered by hit detection in this frame, before [`Projectile::update`].
+    ///
+    /// Use [`ProjectileContext::report_hit`] to emit a [`ProjectileHit`](crate::ProjectileHit) with a payload.
+    fn on_hit(&mut self, cx: &mut ProjectileContext, hit: &Hit) {}
+
     /// Updates the projectile, will not be called if expired.
     fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {}
 
     /// Run once when `is_expired` returns true for the first time.
     ///
-    /// By default this despawns the entity, if this is not desired, overwrite this behavior.
+    /// By default this despawns the entity via [`ProjectileContext::despawn_deferred`],
+    /// if this is not desired, overwrite this behavior.
     fn on_expire(&mut self, cx: &mut ProjectileContext) {
-        cx.despawn();
+        cx.despawn_deferred();
     }
 
     /// Run a dynamic command on this.
-    fn apply_command(&mut self, command: &dyn Any) {}
+    ///
+    /// Return [`CommandResult::Propagate`] to apply the command to children as well.
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+        CommandResult::Consume
+    }
 
     /// If this projectile spawns child projectiles, add them here.
     fn as_spawner(&mut self) -> Option<&mut impl ProjectileSpawner> {
@@ -221,8 +296,8 @@ pub trait ErasedProjectile: Send + Sync + 'static {
     /// Returns true if done.
     fn update(&mut self, cx: ProjectileContext, dt: f32) -> bool;
 
-    /// Run a dynamic command on this, returns true if propagating.
-    fn apply_command(&mut self, command: &dyn Any) -> bool;
+    /// Run a dynamic command on this.
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult;
 }
 
 #[derive(Debug, Clone)]
@@ -243,6 +318,14 @@ impl ProjectileRc {
         }
     }
 
+    /// Returns the amount of live projectiles in the tree, excluding this one.
+    pub fn live_others(&self) -> usize {
+        match self {
+            ProjectileRc::Owned(rc) => Arc::strong_count(rc) - 1,
+            ProjectileRc::Released(weak) => weak.strong_count(),
+        }
+    }
+
     pub fn should_drop(&mut self) -> bool {
         match self {
             ProjectileRc::Owned(_) => false,
@@ -260,10 +343,14 @@ impl ProjectileRc {
 /// By default we require [`Visibility::Visible`] over [`Visibility::Inherited`],
 /// this way we can disable parent projectiles without structural changes.
 /// Explicitly specify [`Visibility::Inherited`] to overwrite this behavior.
+///
+/// With the `debug` feature, a [`Name`](bevy::ecs::name::Name) of the projectile type is added if not specified.
 #[derive(Component)]
 #[require(Transform, Visibility::Visible)]
+#[component(on_insert = trigger_spawned)]
+#[cfg_attr(feature = "debug", component(on_add = debug_name))]
 pub struct ProjectileInstance {
-    pub(crate) projectile: Box<dyn ErasedProjectile>,
+    pub(crate) projectile: ProjectileBox,
     pub(crate) lifetime: f32,
     /// Tracks all children, despawns if 0.
     pub(crate) rc: ProjectileRc,
@@ -271,6 +358,44 @@ pub struct ProjectileInstance {
     pub(crate) root: bool,
 }
 
+fn trigger_spawned(mut world: DeferredWorld, cx: HookContext) {
+    world
+        .commands()
+        .trigger_targets(ProjectileSpawned, cx.entity);
+}
+
+#[cfg(feature = "debug")]
+fn debug_name(mut world: DeferredWorld, cx: HookContext) {
+    use bevy::ecs::name::Name;
+    if world.entity(cx.entity).contains::<Name>() {
+        return;
+    }
+    let Some(instance) = world.get::<ProjectileInstance>(cx.entity) else {
+        return;
+    };
+    let name = Name::new(short_type_name(instance.type_name()));
+    world.commands().entity(cx.entity).insert_if_new(name);
+}
+
+/// Remove module paths from a type name, `a::B<c::D>` becomes `B<D>`.
+#[cfg(feature = "debug")]
+fn short_type_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for (i, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            continue;
+        }
+        let segment = &name[segment_start..i];
+        result.push_str(segment.rsplit("::").next().unwrap_or(segment));
+        result.push(c);
+        segment_start = i + c.len_utf8();
+    }
+    let segment = &name[segment_start..];
+    result.push_str(segment.rsplit("::").next().unwrap_or(segment));
+    result
+}
+
 impl Default for ProjectileInstance {
     fn default() -> Self {
         Self::new(DummyProjectile)
@@ -280,8 +405,9 @@ impl Default for ProjectileInstance {
 impl ProjectileInstance {
     pub fn new(projectile: impl Projectile) -> Self {
         ProjectileInstance {
-            projectile: Box::new(ErasedProjectileInst {
+            projectile: ProjectileBox::new(ErasedProjectileInst {
                 projectile,
+                spawned: false,
                 expired: false,
             }),
             lifetime: 0.0,
@@ -291,13 +417,29 @@ impl ProjectileInstance {
         }
     }
 
+    /// A finished instance that does nothing, used by [`ProjectilePool`](crate::ProjectilePool).
+    pub(crate) fn dormant() -> Self {
+        ProjectileInstance {
+            projectile: ProjectileBox::new(ErasedProjectileInst {
+                projectile: DummyProjectile,
+                spawned: true,
+                expired: true,
+            }),
+            lifetime: 0.0,
+            rc: ProjectileRc::Released(Weak::new()),
+            done: true,
+            root: false,
+        }
+    }
+
     pub(crate) fn new_with_reference(
         projectile: impl Projectile,
         reference: &ProjectileRc,
     ) -> Self {
         ProjectileInstance {
-            projectile: Box::new(ErasedProjectileInst {
+            projectile: ProjectileBox::new(ErasedProjectileInst {
                 projectile,
+                spawned: false,
                 expired: false,
             }),
             lifetime: 0.0,
@@ -309,7 +451,7 @@ impl ProjectileInstance {
 
     pub fn spawner(projectile: impl ProjectileSpawner) -> Self {
         ProjectileInstance {
-            projectile: Box::new(ErasedSpawner(projectile)),
+            projectile: ProjectileBox::new(ErasedSpawner(projectile)),
             lifetime: 0.0,
             rc: ProjectileRc::new(),
             done: false,
@@ -322,7 +464,7 @@ impl ProjectileInstance {
         reference: &ProjectileRc,
     ) -> Self {
         ProjectileInstance {
-            projectile: Box::new(ErasedSpawner(projectile)),
+            projectile: ProjectileBox::new(ErasedSpawner(projectile)),
             lifetime: 0.0,
             rc: reference.clone(),
             done: false,
@@ -330,6 +472,36 @@ impl ProjectileInstance {
         }
     }
 
+    /// Returns the amount of live projectiles in this projectile tree, excluding this one.
+    ///
+    /// For a root projectile this is the amount of live descendants,
+    /// which reaches `0` when all sub-effects have finished.
+    pub fn live_descendants(&self) -> usize {
+        self.rc.live_others()
+    }
+
+    /// Set the initial `lifetime`.
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Swap the projectile in place, keeping the entity, its components, `lifetime` and reference count.
+    ///
+    /// The previous projectile is dropped. Has no effect on the behavior of a finished instance.
+    pub fn replace(&mut self, projectile: impl Projectile) {
+        self.projectile = ProjectileBox::new(ErasedProjectileInst {
+            projectile,
+            spawned: false,
+            expired: false,
+        });
+    }
+
+    /// Swap the projectile in place with a spawner, see [`ProjectileInstance::replace`].
+    pub fn replace_spawner(&mut self, spawner: impl ProjectileSpawner) {
+        self.projectile = ProjectileBox::new(ErasedSpawner(spawner));
+    }
+
     pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
         self.projectile.as_any().downcast_ref()
     }
@@ -347,13 +519,13 @@ impl Deref for ProjectileInstance {
     type Target = dyn ErasedProjectile;
 
     fn deref(&self) -> &Self::Target {
-        self.projectile.as_ref()
+        &*self.projectile
     }
 }
 
 impl DerefMut for ProjectileInstance {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.projectile.as_mut()
+        &mut *self.projectile
     }
 }
 
@@ -361,13 +533,15 @@ struct ErasedSpawner<T>(T);
 
 impl<T: ProjectileSpawner> ErasedProjectile for ErasedSpawner<T> {
     fn update(&mut self, mut cx: ProjectileContext, dt: f32) -> bool {
+        if cx.group_expired() || cx.exceeds_max_lifetime::<T>() {
+            return true;
+        }
         update_spawner(&mut self.0, &mut cx, dt);
         spawner_done(&mut self.0, &cx)
     }
 
-    fn apply_command(&mut self, command: &dyn Any) -> bool {
-        apply_command_on_spawner(&mut self.0, command);
-        false
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+        apply_command_on_spawner(&mut self.0, command)
     }
 
     fn get_fac(&self, lifetime: f32) -> f32 {
@@ -389,35 +563,22 @@ impl<T: ProjectileSpawner> ErasedProjectile for ErasedSpawner<T> {
 
 struct ErasedProjectileInst<T> {
     projectile: T,
+    spawned: bool,
     expired: bool,
 }
 
 impl<T: Projectile> ErasedProjectile for ErasedProjectileInst<T> {
     fn update(&mut self, mut cx: ProjectileContext, dt: f32) -> bool {
-        if !self.expired {
-            cx.fac = self
-                .projectile
-                .fac_curve(cx.lifetime / self.projectile.duration());
-            Projectile::update(&mut self.projectile, &mut cx, dt);
-            if self.projectile.is_expired(&cx) {
-                self.expired = true;
-                self.projectile.on_expire(&mut cx);
-            }
-        }
-        if let Some(spawner) = self.projectile.as_spawner() {
-            update_spawner(spawner, &mut cx, dt);
-            spawner_done(spawner, &cx) && self.expired
-        } else {
-            self.expired
-        }
+        update_projectile(
+            &mut self.projectile,
+            (&mut self.spawned, &mut self.expired),
+            &mut cx,
+            dt,
+        )
     }
 
-    fn apply_command(&mut self, command: &dyn Any) -> bool {
-        self.projectile.apply_command(command);
-        if let Some(spawner) = self.projectile.as_spawner() {
-            apply_command_on_spawner(spawner, command);
-        }
-        false
+    fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+        apply_command_on_projectile(&mut self.projectile, command)
     }
 
     fn get_fac(&self, lifetime: f32) -> f32 {
@@ -438,38 +599,124 @@ impl<T: Projectile> ErasedProjectile for ErasedProjectileInst<T> {
     }
 }
 
-fn spawner_done<T: ProjectileSpawner>(this: &mut T, cx: &ProjectileContext) -> bool {
+/// Update a projectile and its spawner, returns true if done.
+pub(crate) fn update_projectile<T: Projectile>(
+    projectile: &mut T,
+    (spawned, expired): (&mut bool, &mut bool),
+    cx: &mut ProjectileContext,
+    dt: f32,
+) -> bool {
+    let capped = cx.exceeds_max_lifetime::<T>();
+    if !*expired {
+        cx.fac = projectile.fac_curve(cx.lifetime / projectile.duration());
+        if !*spawned {
+            *spawned = true;
+            projectile.on_spawn(cx);
+        }
+        for i in 0..cx.hits().len() {
+            let hit = cx.hits()[i];
+            projectile.on_hit(cx, &hit);
+        }
+        Projectile::update(projectile, cx, dt);
+        if projectile.is_expired(cx)
+            || cx.exceeds_max_distance()
+            || cx.expire_requested()
+            || cx.group_expired()
+            || capped
+        {
+            *expired = true;
+            cx.record_expiry(projectile.duration());
+            cx.commands.trigger_targets(ProjectileExpired, cx.entity());
+            projectile.on_expire(cx);
+        }
+    }
+    if cx.group_expired() || capped {
+        return true;
+    }
+    if let Some(spawner) = projectile.as_spawner() {
+        update_spawner(spawner, cx, dt);
+        spawner_done(spawner, cx) && *expired
+    } else {
+        *expired
+    }
+}
+
+pub(crate) fn spawner_done<T: ProjectileSpawner>(this: &mut T, cx: &ProjectileContext) -> bool {
     this.is_complete(cx) && this.extension().is_none_or(|x| spawner_done(x, cx))
 }
 
-fn apply_command_on_spawner<T: ProjectileSpawner>(this: &mut T, command: &dyn Any) {
-    this.apply_command(command);
-    if let Some(ext) = this.extension() {
-        apply_command_on_spawner(ext, command);
+/// Apply a command to a projectile and its spawner, propagates if any of them propagates.
+pub(crate) fn apply_command_on_projectile<T: Projectile>(
+    this: &mut T,
+    command: &dyn Any,
+) -> CommandResult {
+    let result = this.apply_command(command);
+    match this.as_spawner() {
+        Some(spawner) => result.or(apply_command_on_spawner(spawner, command)),
+        None => result,
     }
 }
 
-fn update_spawner<T: ProjectileSpawner>(this: &mut T, cx: &mut ProjectileContext, dt: f32) {
+/// Apply a command to a spawner and its extensions, propagates if any of them propagates.
+pub(crate) fn apply_command_on_spawner<T: ProjectileSpawner>(
+    this: &mut T,
+    command: &dyn Any,
+) -> CommandResult {
+    let result = this.apply_command(command);
+    match this.extension() {
+        Some(ext) => result.or(apply_command_on_spawner(ext, command)),
+        None => result,
+    }
+}
+
+pub(crate) fn update_spawner<T: ProjectileSpawner>(
+    this: &mut T,
+    cx: &mut ProjectileContext,
+    dt: f32,
+) {
     if !this.is_complete(cx) {
         ProjectileSpawner::update(this, cx, dt);
+        let visibility = this.child_visibility();
         while let Some(projectile) = this.spawn_projectile(cx) {
             let (projectile, bundle) = projectile.into_projectile_bundle(&mut cx.resources);
             let entity = cx.entity();
+            let origin = this.spawn_origin(cx);
+            cx.record_spawn();
+            let pooled = match cx.resources.get_mut::<ProjectilePool>() {
+                Ok(mut pool) => pool.take(&mut cx.commands, projectile.type_id(), |pooled| {
+                    cx.unsafe_other.contains(pooled)
+                }),
+                Err(_) => None,
+            };
+            let (instance, typed) =
+                ProjectileInstance::new_maybe_typed(projectile, cx.rc, &cx.resources);
+            let instance = instance.with_lifetime(this.lifetime_offset());
+            let mut child = match pooled {
+                Some(pooled) => cx.commands.entity(pooled),
+                None => cx.commands.spawn_empty(),
+            };
+            // Pooled entities may be despawned before commands are applied.
+            if let Some(visibility) = visibility {
+                child.try_insert(visibility);
+            }
+            if let Some(typed) = typed {
+                child.try_insert(typed);
+            }
             match this.space() {
-                ProjectileSpace::Local => {
-                    cx.commands.spawn((
-                        ChildOf(entity),
-                        ProjectileInstance::new_with_reference(projectile, cx.rc),
-                        bundle,
-                    ));
-                }
+                ProjectileSpace::Local => child.try_insert((ChildOf(entity), instance, bundle)),
                 ProjectileSpace::World => {
-                    cx.commands.spawn((
-                        WorldSpaceChildOf(entity),
-                        ProjectileInstance::new_with_reference(projectile, cx.rc),
-                        bundle,
-                    ));
+                    child.try_insert((WorldSpaceChildOf(entity), instance, bundle))
                 }
+            };
+            if let Some(origin) = origin {
+                child.queue_handled(
+                    move |mut entity: EntityWorldMut| {
+                        if let Some(mut transform) = entity.get_mut::<Transform>() {
+                            *transform = origin * *transform;
+                        }
+                    },
+                    ignore,
+                );
             }
         }
     }
@@ -0,0 +1,385 @@
+use std::any::{Any, TypeId, type_name};
+
+use bevy::{
+    app::App,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        resource::Resource,
+        schedule::{InternedScheduleLabel, IntoScheduleConfigs},
+        system::{
+            Commands, FilteredResourcesMutParamBuilder, Local, ParamBuilder, ParamSet,
+            ParamSetBuilder, Query, SystemParamBuilder,
+        },
+        world::{EntityMutExcept, FilteredResourcesMut},
+    },
+    platform::collections::HashSet,
+    time::Time,
+};
+
+use crate::{
+    CommandResult, Projectile, ProjectileContext, ProjectileInstance, ProjectileSystems,
+    TrackingQuery, UpdateQuery,
+    storage::ProjectileBox,
+    traits::{ErasedProjectile, ProjectileRc, apply_command_on_projectile, update_projectile},
+    update_single,
+};
+
+/// Storage of a projectile type registered via [`ProjectilePlugin::with_typed`](crate::ProjectilePlugin::with_typed),
+/// updated by a dedicated system without dynamic dispatch.
+///
+/// The [`ProjectileInstance`] on the same entity only tracks `lifetime` and the projectile tree.
+///
+/// # Note
+///
+/// The projectile is not accessible via [`ProjectileInstance::downcast_ref`], use this component instead.
+/// While projectiles of type `T` are updating, [`TypedProjectile::get`] returns [`None`] on all of them.
+#[derive(Component)]
+pub struct TypedProjectile<T: Projectile> {
+    projectile: Option<T>,
+    spawned: bool,
+    expired: bool,
+}
+
+impl<T: Projectile> TypedProjectile<T> {
+    fn new(projectile: T) -> Self {
+        TypedProjectile {
+            projectile: Some(projectile),
+            spawned: false,
+            expired: false,
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.projectile.as_ref()
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.projectile.as_mut()
+    }
+}
+
+/// Entity access of [`projectile_command_system`](crate::cluster::projectile_command_system).
+pub(crate) type CommandTarget<'w> = EntityMutExcept<'w, ProjectileInstance>;
+
+/// Placeholder in [`ProjectileInstance`] of a [`TypedProjectile`],
+/// forwards updates and commands to the component on the same entity.
+pub(crate) struct TypedSlot {
+    type_id: TypeId,
+    type_name: &'static str,
+    update: fn(ProjectileContext, f32) -> bool,
+    apply_command: fn(&mut CommandTarget, &dyn Any) -> CommandResult,
+}
+
+impl TypedSlot {
+    fn new<T: Projectile>() -> Self {
+        TypedSlot {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            update: update_typed::<T>,
+            apply_command: apply_typed_command::<T>,
+        }
+    }
+}
+
+impl ErasedProjectile for TypedSlot {
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_fac(&self, _: f32) -> f32 {
+        0.
+    }
+
+    fn update(&mut self, cx: ProjectileContext, dt: f32) -> bool {
+        (self.update)(cx, dt)
+    }
+
+    fn apply_command(&mut self, _: &dyn Any) -> CommandResult {
+        CommandResult::Consume
+    }
+}
+
+/// Projectile types updated by dedicated systems.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct TypedProjectiles(HashSet<TypeId>);
+
+impl ProjectileInstance {
+    /// Create a root projectile of a type registered via [`ProjectilePlugin::with_typed`](crate::ProjectilePlugin::with_typed).
+    ///
+    /// Spawners spawn registered types as [`TypedProjectile`]s automatically.
+    pub fn typed<T: Projectile>(projectile: T) -> (ProjectileInstance, TypedProjectile<T>) {
+        (
+            ProjectileInstance {
+                projectile: ProjectileBox::new(TypedSlot::new::<T>()),
+                lifetime: 0.0,
+                rc: ProjectileRc::new(),
+                done: false,
+                root: true,
+            },
+            TypedProjectile::new(projectile),
+        )
+    }
+
+    /// Create a child projectile, as a [`TypedProjectile`] if its type is registered.
+    pub(crate) fn new_maybe_typed<T: Projectile>(
+        projectile: T,
+        reference: &ProjectileRc,
+        resources: &FilteredResourcesMut,
+    ) -> (ProjectileInstance, Option<TypedProjectile<T>>) {
+        let registered = resources
+            .get::<TypedProjectiles>()
+            .is_ok_and(|typed| typed.0.contains(&TypeId::of::<T>()));
+        if !registered {
+            return (
+                ProjectileInstance::new_with_reference(projectile, reference),
+                None,
+            );
+        }
+        (
+            ProjectileInstance {
+                projectile: ProjectileBox::new(TypedSlot::new::<T>()),
+                lifetime: 0.0,
+                rc: reference.clone(),
+                done: false,
+                root: false,
+            },
+            Some(TypedProjectile::new(projectile)),
+        )
+    }
+
+    /// Apply a command to the projectile, or its [`TypedProjectile`] on `entity`.
+    pub(crate) fn apply_command_on(
+        &mut self,
+        entity: &mut CommandTarget,
+        command: &dyn Any,
+    ) -> CommandResult {
+        match self.projectile.as_any().downcast_ref::<TypedSlot>() {
+            Some(slot) => (slot.apply_command)(entity, command),
+            None => self.projectile.apply_command(command),
+        }
+    }
+
+    /// Returns true if updated by a dedicated system.
+    pub(crate) fn is_typed(&self) -> bool {
+        self.projectile.as_any().is::<TypedSlot>()
+    }
+
+    /// Returns the [`TypeId`] of the projectile, including [`TypedProjectile`]s.
+    pub(crate) fn projectile_type_id(&self) -> TypeId {
+        match self.projectile.as_any().downcast_ref::<TypedSlot>() {
+            Some(slot) => slot.type_id,
+            None => self.projectile.as_any().type_id(),
+        }
+    }
+}
+
+/// Add the dedicated system of `T`.
+pub(crate) fn register_typed<T: Projectile>(app: &mut App, schedule: InternedScheduleLabel) {
+    app.world_mut()
+        .get_resource_or_init::<TypedProjectiles>()
+        .0
+        .insert(TypeId::of::<T>());
+    let system = (
+        FilteredResourcesMutParamBuilder::new(|builder| {
+            builder.add_write_all();
+        }),
+        ParamBuilder,
+        ParamSetBuilder((ParamBuilder, ParamBuilder)),
+        ParamBuilder,
+        ParamBuilder,
+    )
+        .build_state(app.world_mut())
+        .build_system(typed_projectile_update::<T>);
+    app.add_systems(schedule, system.in_set(ProjectileSystems::Update));
+}
+
+fn typed_projectile_update<T: Projectile>(
+    mut resources: FilteredResourcesMut,
+    mut commands: Commands,
+    mut queries: ParamSet<(UpdateQuery, Query<(Entity, &mut TypedProjectile<T>)>)>,
+    mut tracking: TrackingQuery,
+    mut updating: Local<Vec<(Entity, T, bool, bool)>>,
+) {
+    let Ok((dt, elapsed)) = resources
+        .get::<Time>()
+        .map(|x| (x.delta_secs(), x.elapsed_secs()))
+    else {
+        return;
+    };
+    // `UpdateQuery` has write access to `TypedProjectile<T>`,
+    // move projectiles out instead of looking them up on each entity.
+    for (entity, mut typed) in &mut queries.p1() {
+        if let Some(projectile) = typed.projectile.take() {
+            updating.push((entity, projectile, typed.spawned, typed.expired));
+        }
+    }
+    let query = queries.p0();
+    for (entity, projectile, spawned, expired) in updating.iter_mut() {
+        // Safety: cannot access the same entity, enforced by `ProjectileContext`.
+        let Ok(item) = (unsafe { query.get_unchecked(*entity) }) else {
+            continue;
+        };
+        if !item.1.is_typed() {
+            continue;
+        }
+        update_single(
+            item,
+            &mut resources,
+            &mut commands,
+            &query,
+            &mut tracking,
+            (dt, elapsed),
+            |_, mut cx, dt| update_projectile(projectile, (spawned, expired), &mut cx, dt),
+        );
+    }
+    let mut typed = queries.p1();
+    for (entity, projectile, spawned, expired) in updating.drain(..) {
+        if let Ok((_, mut typed)) = typed.get_mut(entity) {
+            typed.projectile = Some(projectile);
+            typed.spawned = spawned;
+            typed.expired = expired;
+        }
+    }
+}
+
+fn update_typed<T: Projectile>(mut cx: ProjectileContext, dt: f32) -> bool {
+    let Some((mut projectile, mut spawned, mut expired)) = cx
+        .entity_mut
+        .get_mut::<TypedProjectile<T>>()
+        .and_then(|mut typed| Some((typed.projectile.take()?, typed.spawned, typed.expired)))
+    else {
+        return true;
+    };
+    let done = update_projectile(&mut projectile, (&mut spawned, &mut expired), &mut cx, dt);
+    if let Some(mut typed) = cx.entity_mut.get_mut::<TypedProjectile<T>>() {
+        typed.projectile = Some(projectile);
+        typed.spawned = spawned;
+        typed.expired = expired;
+    }
+    done
+}
+
+fn apply_typed_command<T: Projectile>(
+    entity: &mut CommandTarget,
+    command: &dyn Any,
+) -> CommandResult {
+    match entity.get_mut::<TypedProjectile<T>>().and_then(|typed| {
+        typed
+            .map_unchanged(|x| &mut x.projectile)
+            .into_inner()
+            .as_mut()
+    }) {
+        Some(projectile) => apply_command_on_projectile(projectile, command),
+        None => CommandResult::Consume,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{any::Any, time::Duration};
+
+    use bevy::{
+        app::App,
+        ecs::entity::Entity,
+        math::Vec3,
+        prelude::MinimalPlugins,
+        time::TimeUpdateStrategy,
+        transform::{TransformPlugin, components::Transform},
+    };
+
+    use super::TypedProjectile;
+    use crate::{
+        CommandResult, Projectile, ProjectileCommand, ProjectileContext, ProjectileInstance,
+        ProjectilePlugin,
+    };
+
+    struct Mover(f32);
+
+    impl Projectile for Mover {
+        fn duration(&self) -> f32 {
+            1.
+        }
+
+        fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+            cx.transform_mut().translation.x += self.0 * dt;
+        }
+
+        fn apply_command(&mut self, command: &dyn Any) -> CommandResult {
+            if let Some(speed) = command.downcast_ref::<f32>() {
+                self.0 = *speed;
+            }
+            CommandResult::Consume
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            ProjectilePlugin::default().with_typed::<Mover>(),
+        ));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        app
+    }
+
+    fn x(app: &App, entity: Entity) -> Option<f32> {
+        app.world()
+            .get::<Transform>(entity)
+            .map(|x| x.translation.x)
+    }
+
+    #[test]
+    fn typed_matches_dynamic() {
+        let mut app = app();
+        let dynamic = app
+            .world_mut()
+            .spawn((ProjectileInstance::new(Mover(1.)), Transform::default()))
+            .id();
+        let typed = app
+            .world_mut()
+            .spawn((ProjectileInstance::typed(Mover(1.)), Transform::default()))
+            .id();
+        for frame in 0..15 {
+            if frame == 4 {
+                app.world_mut()
+                    .send_event(ProjectileCommand::new(dynamic, 5f32));
+                app.world_mut()
+                    .send_event(ProjectileCommand::new(typed, 5f32));
+            }
+            app.update();
+            assert_eq!(x(&app, dynamic), x(&app, typed), "frame {frame}");
+        }
+        // Both expired on the same frame.
+        assert!(app.world().get_entity(dynamic).is_err());
+        assert!(app.world().get_entity(typed).is_err());
+    }
+
+    #[test]
+    fn tick_typed() {
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .spawn((ProjectileInstance::typed(Mover(2.)), Transform::default()))
+            .id();
+        assert_eq!(
+            ProjectileInstance::tick(app.world_mut(), entity, 0.25),
+            Ok(false)
+        );
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation, Vec3::new(0.5, 0., 0.));
+        let typed = app.world().get::<TypedProjectile<Mover>>(entity).unwrap();
+        assert!(typed.get().is_some());
+    }
+}
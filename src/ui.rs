@@ -0,0 +1,83 @@
+use bevy::{
+    ecs::{component::Component, hierarchy::ChildOf, query::Has, system::Query},
+    math::Vec3,
+    transform::components::{GlobalTransform, Transform},
+    ui::{ComputedNode, Node, PositionType, Val},
+};
+
+/// Makes a projectile live in UI space, for damage numbers, collected coin flights and UI sparkles.
+///
+/// While projectiles update, [`Transform`] is in logical pixels with the origin at the top left
+/// and `Y` pointing down, the node is centered at its translation by setting [`Node::left`] and [`Node::top`].
+/// Root projectiles also see their [`GlobalTransform`] in UI space, so spawners place children
+/// at the correct position, local space children are positioned relative to the parent node.
+///
+/// UI layout overwrites the translation of nodes, the translation is restored before projectiles update.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::*;
+/// struct DamageNumber;
+///
+/// impl Projectile for DamageNumber {
+///     fn duration(&self) -> f32 {
+///         1.
+///     }
+/// }
+///
+/// fn damage_number(mut commands: Commands) {
+///     commands.spawn((
+///         ProjectileInstance::new(DamageNumber),
+///         UiProjectile::default(),
+///         ProjectileVelocity(Vec3::NEG_Y * 40.),
+///         Transform::from_xyz(200., 300., 0.),
+///         Text::new("42"),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, Component)]
+#[require(Node)]
+pub struct UiProjectile {
+    translation: Option<Vec3>,
+}
+
+/// Restore translations overwritten by UI layout.
+pub(crate) fn restore_ui_transform(
+    mut query: Query<(
+        &UiProjectile,
+        &mut Transform,
+        &mut GlobalTransform,
+        Has<ChildOf>,
+    )>,
+) {
+    for (ui, mut transform, mut global_transform, has_parent) in &mut query {
+        let Some(translation) = ui.translation else {
+            continue;
+        };
+        transform.translation = translation;
+        if !has_parent {
+            *global_transform = GlobalTransform::from(*transform);
+        }
+    }
+}
+
+/// Record translations and position nodes before UI layout.
+pub(crate) fn write_ui_node(
+    mut query: Query<(
+        &mut UiProjectile,
+        &Transform,
+        &mut Node,
+        Option<&ComputedNode>,
+    )>,
+) {
+    for (mut ui, transform, mut node, computed) in &mut query {
+        ui.translation = Some(transform.translation);
+        let half_size = computed
+            .map(|x| x.size() * x.inverse_scale_factor() / 2.)
+            .unwrap_or_default();
+        let corner = transform.translation.truncate() - half_size;
+        node.position_type = PositionType::Absolute;
+        node.left = Val::Px(corner.x);
+        node.top = Val::Px(corner.y);
+    }
+}
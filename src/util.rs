@@ -6,11 +6,14 @@ use std::{
 };
 
 use bevy::{
+    ecs::{hierarchy::Children, relationship::RelationshipTarget},
     math::{Quat, Vec2, Vec3, Vec4},
     transform::components::Transform,
 };
 use fastrand::Rng;
 
+use crate::{ProjectileContext, WorldSpaceChildren};
+
 fn lerp(a: f32, b: f32, fac: f32) -> f32 {
     a * (1.0 - fac) + b * fac
 }
@@ -191,6 +194,130 @@ impl PhysicsExt for Vec4 {
     }
 }
 
+/// Extension trait for steering velocities with a limited turn rate.
+///
+/// Unlike [`PhysicsExt::move_near`], which snaps straight toward the target,
+/// this turns in arcs like a missile.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::{*, util::SteeringExt};
+/// struct Missile {
+///     target: Entity,
+/// }
+///
+/// impl Projectile for Missile {
+///     fn update(&mut self, cx: &mut ProjectileContext, dt: f32) {
+///         let Some(target) = cx.translation_of(self.target) else {
+///             return;
+///         };
+///         let direction = target - cx.transform().translation;
+///         cx.component::<ProjectileVelocity>(|velocity| {
+///             velocity.0.steer_towards(direction, 2., 10., dt);
+///         });
+///     }
+/// }
+/// ```
+pub trait SteeringExt {
+    /// Rotate this velocity towards `direction` by at most `max_turn_rate` radians per second,
+    /// and set its length to `speed`.
+    ///
+    /// If this is zero, it starts facing `direction`.
+    fn steer_towards(&mut self, direction: Self, max_turn_rate: f32, speed: f32, dt: f32);
+}
+
+impl SteeringExt for Vec2 {
+    fn steer_towards(&mut self, direction: Self, max_turn_rate: f32, speed: f32, dt: f32) {
+        let desired = direction.normalize_or_zero();
+        let current = self.try_normalize().unwrap_or(desired);
+        if desired == Vec2::ZERO {
+            *self = current * speed;
+            return;
+        }
+        let max_angle = max_turn_rate * dt;
+        let angle = current.angle_to(desired).clamp(-max_angle, max_angle);
+        *self = Vec2::from_angle(angle).rotate(current) * speed;
+    }
+}
+
+impl SteeringExt for Vec3 {
+    fn steer_towards(&mut self, direction: Self, max_turn_rate: f32, speed: f32, dt: f32) {
+        let desired = direction.normalize_or_zero();
+        let current = self.try_normalize().unwrap_or(desired);
+        if desired == Vec3::ZERO {
+            *self = current * speed;
+            return;
+        }
+        let max_angle = max_turn_rate * dt;
+        let angle = current.angle_between(desired);
+        if angle <= max_angle {
+            *self = desired * speed;
+            return;
+        }
+        let axis = current
+            .cross(desired)
+            .try_normalize()
+            .unwrap_or_else(|| current.any_orthonormal_vector());
+        *self = Quat::from_axis_angle(axis, max_angle) * current * speed;
+    }
+}
+
+/// Predict where a projectile fired from `shooter_pos` at `projectile_speed` intercepts a target
+/// moving at a constant `target_velocity`, returns `None` if the projectile can never catch up.
+///
+/// Use [`ProjectileContext::velocity_of`] to obtain the velocity of a tracked entity.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_javelin::{*, util::lead_target};
+/// struct Turret {
+///     target: Entity,
+/// }
+///
+/// impl Projectile for Turret {
+///     fn update(&mut self, cx: &mut ProjectileContext, _: f32) {
+///         let (Some(target), velocity) = (cx.translation_of(self.target), cx.velocity_of(self.target)) else {
+///             return;
+///         };
+///         let position = cx.global_transform().translation();
+///         let aim = lead_target(position, target, velocity.unwrap_or_default(), 20.).unwrap_or(target);
+///         cx.transform_mut().look_at(aim, Vec3::Y);
+///     }
+/// }
+/// ```
+pub fn lead_target(
+    shooter_pos: Vec3,
+    target_pos: Vec3,
+    target_velocity: Vec3,
+    projectile_speed: f32,
+) -> Option<Vec3> {
+    let offset = target_pos - shooter_pos;
+    // |offset + target_velocity * t| = projectile_speed * t
+    let a = target_velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2. * offset.dot(target_velocity);
+    let c = offset.length_squared();
+    // Already on top of the target, avoids `0 / 0` when the speeds are equal.
+    if c <= f32::EPSILON {
+        return Some(target_pos);
+    }
+    let t = if a.abs() <= f32::EPSILON * projectile_speed * projectile_speed {
+        -c / b
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            return None;
+        }
+        let sqrt = discriminant.sqrt();
+        let t0 = (-b - sqrt) / (2. * a);
+        let t1 = (-b + sqrt) / (2. * a);
+        match (t0.min(t1), t0.max(t1)) {
+            (t, _) if t >= 0. => t,
+            (_, t) => t,
+        }
+    };
+    (t.is_finite() && t >= 0.).then(|| target_pos + target_velocity * t)
+}
+
 /// Calculate a factor in range `from` and apply to range `to`.
 pub fn map_range<A, B>(value: A, from: Range<A>, to: Range<B>) -> B
 where
@@ -270,3 +397,133 @@ impl<T: Copy> RetainedValue<T> {
         self.0
     }
 }
+
+/// Positions child projectiles of type `P` in a formation around the parent,
+/// call [`Formation::update_local`] or [`Formation::update_world`] in the parent's `update`.
+///
+/// `phase` in radians rotates [`Formation::Ring`] and [`Formation::Helix`],
+/// pass `elapsed * speed` for orbiting orbs.
+///
+/// Formations are in the parent's space, `-Z` is forward and `Y` is up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Formation {
+    /// Evenly spaced on a circle on the `XZ` plane.
+    Ring { radius: f32 },
+    /// A V shape trailing behind the parent with the first child at the tip,
+    /// `angle` in radians is the half angle between the arms.
+    V { spacing: f32, angle: f32 },
+    /// A spiral around the forward axis, from the parent to `length` in front.
+    Helix {
+        radius: f32,
+        length: f32,
+        turns: f32,
+    },
+}
+
+impl Formation {
+    /// Obtain the offset of child `index` out of `count`.
+    pub fn offset(&self, index: usize, count: usize, phase: f32) -> Vec3 {
+        let t = index as f32 / count.max(1) as f32;
+        match *self {
+            Formation::Ring { radius } => {
+                let (sin, cos) = (t * 2. * PI + phase).sin_cos();
+                Vec3::new(cos * radius, 0., sin * radius)
+            }
+            Formation::V { spacing, angle } => {
+                let row = index.div_ceil(2) as f32;
+                let side = if index.is_multiple_of(2) { 1. } else { -1. };
+                let (sin, cos) = angle.sin_cos();
+                Vec3::new(side * sin * spacing * row, 0., cos * spacing * row)
+            }
+            Formation::Helix {
+                radius,
+                length,
+                turns,
+            } => {
+                let (sin, cos) = (t * turns * 2. * PI + phase).sin_cos();
+                Vec3::new(cos * radius, sin * radius, -length * t)
+            }
+        }
+    }
+
+    /// Set the [`Transform::translation`] of local space children of type `P`.
+    pub fn update_local<P: 'static>(&self, cx: &mut ProjectileContext, phase: f32) {
+        let count = Self::count::<Children, P>(cx);
+        let mut index = 0;
+        cx.children::<Children, P>(|_, mut transform, _, _, _| {
+            transform.translation = self.offset(index, count, phase);
+            index += 1;
+        });
+    }
+
+    /// Set the [`Transform::translation`] of world space children of type `P`,
+    /// relative to the parent's [`GlobalTransform`](bevy::transform::components::GlobalTransform).
+    pub fn update_world<P: 'static>(&self, cx: &mut ProjectileContext, phase: f32) {
+        let count = Self::count::<WorldSpaceChildren, P>(cx);
+        let parent = cx.global_transform().affine();
+        let mut index = 0;
+        cx.children::<WorldSpaceChildren, P>(|_, mut transform, _, _, _| {
+            transform.translation = parent.transform_point3(self.offset(index, count, phase));
+            index += 1;
+        });
+    }
+
+    fn count<T: RelationshipTarget, P: 'static>(cx: &mut ProjectileContext) -> usize {
+        let mut count = 0;
+        cx.children::<T, P>(|_, _, _, _, _| count += 1);
+        count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::Vec3;
+
+    use super::lead_target;
+
+    #[test]
+    fn lead_stationary_target() {
+        let target = Vec3::new(3., 4., 0.);
+        assert_eq!(
+            lead_target(Vec3::ZERO, target, Vec3::ZERO, 10.),
+            Some(target)
+        );
+    }
+
+    #[test]
+    fn lead_moving_target() {
+        // Reaches `(8, 6, 0)` after 1 second, 10 units from the shooter.
+        let aim = lead_target(Vec3::ZERO, Vec3::new(0., 6., 0.), Vec3::X * 8., 10.).unwrap();
+        assert!(aim.abs_diff_eq(Vec3::new(8., 6., 0.), 1e-4), "{aim}");
+    }
+
+    #[test]
+    fn lead_outrunning_target() {
+        let target = Vec3::new(10., 0., 0.);
+        assert_eq!(lead_target(Vec3::ZERO, target, Vec3::X * 20., 10.), None);
+    }
+
+    #[test]
+    fn lead_equal_speed() {
+        // Approaching diagonally, the squared speeds differ only by rounding, meets halfway.
+        let velocity = Vec3::new(-1., -1., 0.).normalize() * 100.;
+        let aim = lead_target(Vec3::ZERO, Vec3::new(1000., 1000., 0.), velocity, 100.).unwrap();
+        assert!(aim.abs_diff_eq(Vec3::new(500., 500., 0.), 1e-1), "{aim}");
+        // Moving sideways can never be reached.
+        let target = Vec3::new(1000., 0., 0.);
+        assert_eq!(lead_target(Vec3::ZERO, target, Vec3::Y * 100., 100.), None);
+    }
+
+    #[test]
+    fn lead_coincident_target() {
+        let target = Vec3::new(1., 2., 3.);
+        assert_eq!(
+            lead_target(target, target, Vec3::X * 100., 100.),
+            Some(target)
+        );
+        assert_eq!(
+            lead_target(target, target, Vec3::X * 50., 100.),
+            Some(target)
+        );
+    }
+}
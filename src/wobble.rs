@@ -0,0 +1,109 @@
+use bevy::{
+    ecs::{component::Component, system::Query},
+    math::{Quat, Vec3},
+    transform::components::Transform,
+};
+
+use crate::ProjectileInstance;
+
+/// Smooth noise based jitter of position and rotation on top of a projectile's motion,
+/// for fireflies, wisps and unstable magic.
+///
+/// The offset is driven by `lifetime` and removed before projectiles update,
+/// so [`Projectile::update`](crate::Projectile::update) and velocity only see the base motion.
+///
+/// Use different seeds so projectiles don't wobble in sync.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct Wobble {
+    /// Maximum positional offset.
+    pub amplitude: f32,
+    /// Maximum rotational offset in radians.
+    pub angle: f32,
+    /// Changes of direction per second.
+    pub frequency: f32,
+    pub seed: u64,
+    offset: Vec3,
+    rotation: Quat,
+}
+
+impl Wobble {
+    pub fn new(amplitude: f32, frequency: f32) -> Self {
+        Wobble {
+            amplitude,
+            angle: 0.,
+            frequency,
+            seed: 0,
+            offset: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Forget the applied offset, for transforms that have been reset.
+    pub(crate) fn reset(&mut self) {
+        self.offset = Vec3::ZERO;
+        self.rotation = Quat::IDENTITY;
+    }
+
+    fn sample(&self, channel: u64, t: f32) -> Vec3 {
+        Vec3::new(
+            noise(self.seed, channel, t),
+            noise(self.seed, channel + 1, t),
+            noise(self.seed, channel + 2, t),
+        )
+    }
+}
+
+/// Hash to `[-1, 1]`.
+fn hash(seed: u64, channel: u64, i: i64) -> f32 {
+    // splitmix64
+    let mut x = seed
+        .wrapping_add(channel.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .wrapping_add((i as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9));
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 40) as f32 / (1u64 << 23) as f32 - 1.
+}
+
+/// Smooth value noise in `[-1, 1]`.
+fn noise(seed: u64, channel: u64, t: f32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let f = f * f * (3. - 2. * f);
+    let a = hash(seed, channel, i as i64);
+    let b = hash(seed, channel, i as i64 + 1);
+    a + (b - a) * f
+}
+
+/// Remove the offset applied last frame.
+pub(crate) fn remove_wobble(mut query: Query<(&Wobble, &mut Transform)>) {
+    for (wobble, mut transform) in &mut query {
+        transform.translation -= wobble.offset;
+        transform.rotation = wobble.rotation.inverse() * transform.rotation;
+    }
+}
+
+pub(crate) fn apply_wobble(mut query: Query<(&mut Wobble, &mut Transform, &ProjectileInstance)>) {
+    for (mut wobble, mut transform, instance) in &mut query {
+        let t = instance.lifetime * wobble.frequency;
+        let offset = wobble.sample(0, t) * wobble.amplitude;
+        let rotation = match wobble.angle {
+            0. => Quat::IDENTITY,
+            angle => Quat::from_scaled_axis(wobble.sample(3, t) * angle),
+        };
+        transform.translation += offset;
+        transform.rotation = rotation * transform.rotation;
+        wobble.offset = offset;
+        wobble.rotation = rotation;
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{Assets, Handle, RenderAssetUsages},
+    ecs::{
+        component::Component,
+        system::{Query, Res, ResMut},
+    },
+    image::Image,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    time::Time,
+};
+
+use crate::ImageBuilder;
+
+/// An image that changes over time, like a slice of a 3d noise.
+///
+/// Implemented for closures `Fn(f32) -> impl ImageBuilder` where the input is time in `0..1`.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// let animated = |t: f32| VoronoiImage {
+///     z: Some(t),
+///     ..VoronoiImage::new3d(5)
+/// };
+/// let sheet = animated.to_flipbook(128, 128, 16, 4, 4);
+/// ```
+pub trait AnimatedImageBuilder {
+    /// Obtain a static frame at time `t`, normally in `0..1`.
+    fn frame(&self, t: f32) -> impl ImageBuilder;
+
+    /// Generate an animation sheet of `frames` frames laid out in `columns * rows` cells,
+    /// left to right, top to bottom. Frame `i` is sampled at `t = i / frames`.
+    ///
+    /// Cells beyond `frames` are left transparent. Use `TextureAtlasLayout::from_grid`
+    /// with the frame size to index the result.
+    ///
+    /// # Panics
+    ///
+    /// If `frames > columns * rows`.
+    fn to_flipbook(
+        &self,
+        frame_width: usize,
+        frame_height: usize,
+        frames: usize,
+        columns: usize,
+        rows: usize,
+    ) -> Image {
+        assert!(
+            frames <= columns * rows,
+            "{frames} frames do not fit in a {columns}x{rows} sheet."
+        );
+        let width = frame_width * columns;
+        let height = frame_height * rows;
+        let mut data = vec![0; width * height * 4];
+        for i in 0..frames {
+            let frame = self.frame(i as f32 / frames as f32);
+            let image = frame.to_image(frame_width, frame_height);
+            let Some(frame_data) = image.data else {
+                continue;
+            };
+            let x0 = (i % columns) * frame_width;
+            let y0 = (i / columns) * frame_height;
+            for (y, row) in frame_data.chunks_exact(frame_width * 4).enumerate() {
+                let start = ((y0 + y) * width + x0) * 4;
+                data[start..start + row.len()].copy_from_slice(row);
+            }
+        }
+        Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        )
+    }
+}
+
+impl<F: Fn(f32) -> B, B: ImageBuilder> AnimatedImageBuilder for F {
+    fn frame(&self, t: f32) -> impl ImageBuilder {
+        self(t)
+    }
+}
+
+/// Regenerates an [`AnimatedImageBuilder`] into the same [`Image`] asset at a fixed framerate,
+/// materials referencing the handle will display the animation.
+///
+/// Time `t` loops in `0..1` every `period` seconds.
+///
+/// Requires [`AnimatedImagePlugin`].
+///
+/// # Note
+///
+/// Images are generated on the main thread, keep the size small.
+#[derive(Component)]
+pub struct AnimatedGeneratedImage {
+    handle: Handle<Image>,
+    generate: Box<dyn Fn(f32) -> Image + Send + Sync>,
+    /// Frames generated per second.
+    pub fps: f32,
+    /// Seconds for `t` to go from `0` to `1`.
+    pub period: f32,
+    elapsed: f32,
+    since_last_frame: f32,
+}
+
+impl AnimatedGeneratedImage {
+    /// Generates the first frame and adds it to [`Assets<Image>`].
+    pub fn new(
+        images: &mut Assets<Image>,
+        builder: impl AnimatedImageBuilder + Send + Sync + 'static,
+        width: usize,
+        height: usize,
+        fps: f32,
+        period: f32,
+    ) -> Self {
+        let generate = move |t: f32| builder.frame(t).to_image(width, height);
+        AnimatedGeneratedImage {
+            handle: images.add(generate(0.)),
+            generate: Box::new(generate),
+            fps,
+            period,
+            elapsed: 0.,
+            since_last_frame: 0.,
+        }
+    }
+
+    /// Obtain the handle of the generated image.
+    pub fn handle(&self) -> Handle<Image> {
+        self.handle.clone()
+    }
+}
+
+/// Plugin that updates [`AnimatedGeneratedImage`]s.
+pub struct AnimatedImagePlugin;
+
+impl Plugin for AnimatedImagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_generated_images);
+    }
+}
+
+pub fn animate_generated_images(
+    time: Res<Time>,
+    mut images: ResMut<Assets<Image>>,
+    mut query: Query<&mut AnimatedGeneratedImage>,
+) {
+    let dt = time.delta_secs();
+    for mut animated in &mut query {
+        animated.elapsed += dt;
+        animated.since_last_frame += dt;
+        if animated.since_last_frame < 1. / animated.fps {
+            continue;
+        }
+        animated.since_last_frame = 0.;
+        let t = (animated.elapsed / animated.period).fract();
+        let frame = (animated.generate)(t);
+        if let Some(image) = images.get_mut(&animated.handle) {
+            image.data = frame.data;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{Image, TextureAtlasLayout},
+    math::{Rect, URect, Vec2},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{DynImageBuilder, ImageBuilder};
+
+/// Transparent pixels between packed entries, prevents bleeding with linear filtering.
+const PADDING: usize = 1;
+
+/// Result of [`pack_atlas`].
+#[derive(Debug, Clone)]
+pub struct PackedAtlas {
+    pub image: Image,
+    /// Pixel rects of each entry, in input order.
+    pub rects: Vec<URect>,
+    /// UV rects of each entry in `0..1`, in input order.
+    pub uv_rects: Vec<Rect>,
+}
+
+impl PackedAtlas {
+    /// Create a [`TextureAtlasLayout`] with one texture per entry, in input order.
+    pub fn layout(&self) -> TextureAtlasLayout {
+        let mut layout = TextureAtlasLayout::new_empty(self.image.size());
+        for rect in &self.rects {
+            layout.add_texture(*rect);
+        }
+        layout
+    }
+}
+
+/// Generate several builders of `(width, height)` into a single atlas image,
+/// so many small generated sprites can share a texture binding.
+///
+/// Entries are shelf packed by height into a power of two wide image.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::Vec2;
+/// let dots = DotsImage::new(4., 0.3);
+/// let glow = GradientImage::radial(Vec2::splat(0.5), 0.5).invert();
+/// let atlas = pack_atlas(&[(&dots, (64, 64)), (&glow, (32, 32)), (&glow, (16, 16))]);
+/// let uv = atlas.uv_rects[1];
+/// ```
+pub fn pack_atlas(entries: &[(&dyn DynImageBuilder, (usize, usize))]) -> PackedAtlas {
+    let area: usize = entries
+        .iter()
+        .map(|(_, (w, h))| (w + PADDING) * (h + PADDING))
+        .sum();
+    let widest = entries.iter().map(|(_, (w, _))| *w).max().unwrap_or(1);
+    let width = ((area as f32).sqrt().ceil() as usize)
+        .max(widest)
+        .next_power_of_two();
+
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|i| std::cmp::Reverse(entries[*i].1.1));
+
+    let mut rects = vec![URect::default(); entries.len()];
+    let (mut x, mut y, mut shelf) = (0, 0, 0);
+    for i in order {
+        let (w, h) = entries[i].1;
+        if x + w > width {
+            x = 0;
+            y += shelf + PADDING;
+            shelf = 0;
+        }
+        rects[i] = URect::new(x as u32, y as u32, (x + w) as u32, (y + h) as u32);
+        x += w + PADDING;
+        shelf = shelf.max(h);
+    }
+    let height = (y + shelf).max(1);
+
+    let mut data = vec![0; width * height * 4];
+    for ((builder, (w, h)), rect) in entries.iter().zip(&rects) {
+        if *w == 0 || *h == 0 {
+            continue;
+        }
+        let Some(entry) = builder.to_image(*w, *h).data else {
+            continue;
+        };
+        let (x0, y0) = (rect.min.x as usize, rect.min.y as usize);
+        for (y, row) in entry.chunks_exact(w * 4).enumerate() {
+            let start = ((y0 + y) * width + x0) * 4;
+            data[start..start + row.len()].copy_from_slice(row);
+        }
+    }
+
+    let size = Vec2::new(width as f32, height as f32);
+    let uv_rects = rects
+        .iter()
+        .map(|r| Rect::from_corners(r.min.as_vec2() / size, r.max.as_vec2() / size))
+        .collect();
+    PackedAtlas {
+        image: Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        ),
+        rects,
+        uv_rects,
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::math::{Vec2, Vec4};
+
+use crate::{ImageBuilder, PureColorSampler};
+
+/// Packs four grayscale builders into the `r`, `g`, `b` and `a` channels of one image.
+///
+/// This allows a shader to read multiple masks from a single texture.
+pub struct ComposeRgba<R, G, B, A>(pub R, pub G, pub B, pub A);
+
+impl<R, G, B> ComposeRgba<R, G, B, PureColorSampler> {
+    /// Packs three grayscale builders with an opaque alpha channel.
+    pub fn rgb(r: R, g: G, b: B) -> Self {
+        ComposeRgba(r, g, b, PureColorSampler(Vec4::ONE))
+    }
+}
+
+impl<R: ImageBuilder, G: ImageBuilder, B: ImageBuilder, A: ImageBuilder> ImageBuilder
+    for ComposeRgba<R, G, B, A>
+{
+    fn sample(&self, position: Vec2) -> f32 {
+        self.0.sample(position)
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        Vec4::new(
+            self.0.sample(position),
+            self.1.sample(position),
+            self.2.sample(position),
+            self.3.sample(position),
+        )
+    }
+}
+
+/// A color channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Channel {
+    R,
+    G,
+    B,
+    A,
+}
+
+impl Channel {
+    /// Parse `r`, `g`, `b`, `a` or `x`, `y`, `z`, `w`.
+    pub fn from_char(c: char) -> Option<Channel> {
+        match c {
+            'r' | 'x' => Some(Channel::R),
+            'g' | 'y' => Some(Channel::G),
+            'b' | 'z' => Some(Channel::B),
+            'a' | 'w' => Some(Channel::A),
+            _ => None,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Extracts a single channel as a grayscale image.
+pub struct ChannelSampler<T> {
+    pub base: T,
+    pub channel: Channel,
+}
+
+impl<T: ImageBuilder> ImageBuilder for ChannelSampler<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.base.sample_color(position)[self.channel.index()]
+    }
+}
+
+/// Rearranges the channels of a color image.
+pub struct SwizzleSampler<T> {
+    pub base: T,
+    pub channels: [Channel; 4],
+}
+
+impl<T> SwizzleSampler<T> {
+    /// # Panics
+    ///
+    /// If `swizzle` is not 4 characters of `rgba` or `xyzw`.
+    pub fn new(base: T, swizzle: &str) -> Self {
+        let mut chars = swizzle.chars().map(|c| {
+            Channel::from_char(c).unwrap_or_else(|| panic!("Invalid swizzle {swizzle:?}."))
+        });
+        let channels = [(); 4].map(|_| {
+            chars
+                .next()
+                .unwrap_or_else(|| panic!("Swizzle {swizzle:?} must have 4 channels."))
+        });
+        assert!(
+            chars.next().is_none(),
+            "Swizzle {swizzle:?} must have 4 channels."
+        );
+        SwizzleSampler { base, channels }
+    }
+}
+
+impl<T: ImageBuilder> ImageBuilder for SwizzleSampler<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.sample_color(position).x
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        let color = self.base.sample_color(position);
+        Vec4::from_array(self.channels.map(|c| color[c.index()]))
+    }
+}
@@ -0,0 +1,171 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::{
+    asset::RenderAssetUsages,
+    image::Image,
+    math::{Vec2, Vec3, Vec4},
+    render::render_resource::{
+        Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
+};
+
+use crate::ImageBuilder;
+
+/// A builder sampled by a direction on the unit sphere, used for skyboxes and environment maps.
+///
+/// Implemented for closures `Fn(Vec3) -> Vec4`.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::{Vec3, Vec4};
+/// let sky = |dir: Vec3| Vec4::new(0.2, 0.4, 0.5 + dir.y * 0.5, 1.);
+/// let cubemap = sky.to_cubemap(64);
+/// ```
+pub trait DirectionalImageBuilder {
+    /// Sample a color at a normalized direction.
+    fn sample_direction(&self, direction: Vec3) -> Vec4;
+
+    /// Generate a cubemap with faces of `size * size`,
+    /// in the order `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z`.
+    fn to_cubemap(&self, size: usize) -> Image {
+        let mut data = vec![0; size * size * 6 * 4];
+        let s = size as f32;
+        let mut p = 0;
+        for face in 0..6 {
+            for y in 0..size {
+                for x in 0..size {
+                    let u = (x as f32 + 0.5) / s * 2. - 1.;
+                    let v = (y as f32 + 0.5) / s * 2. - 1.;
+                    let direction = match face {
+                        0 => Vec3::new(1., -v, -u),
+                        1 => Vec3::new(-1., -v, u),
+                        2 => Vec3::new(u, 1., v),
+                        3 => Vec3::new(u, -1., -v),
+                        4 => Vec3::new(u, -v, 1.),
+                        _ => Vec3::new(-u, -v, -1.),
+                    };
+                    let c = self.sample_direction(direction.normalize());
+                    let c = (c * 255.).as_u8vec4();
+                    data[p..p + 4].copy_from_slice(&c.to_array());
+                    p += 4;
+                }
+            }
+        }
+        let mut image = Image::new(
+            Extent3d {
+                width: size as u32,
+                height: (size * 6) as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        );
+        image.reinterpret_stacked_2d_as_array(6);
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+        image
+    }
+
+    /// Generate an equirectangular projection, `x` is longitude and `y` is latitude from the top.
+    ///
+    /// Pixels are sampled at their centers so the image tiles horizontally.
+    fn to_equirectangular(&self, width: usize, height: usize) -> Image {
+        let mut data = vec![0; width * height * 4];
+        let mut p = 0;
+        for y in 0..height {
+            for x in 0..width {
+                let position = Vec2::new(
+                    (x as f32 + 0.5) / width as f32,
+                    (y as f32 + 0.5) / height as f32,
+                );
+                let c = self.sample_direction(equirectangular_to_direction(position));
+                let c = (c * 255.).as_u8vec4();
+                data[p..p + 4].copy_from_slice(&c.to_array());
+                p += 4;
+            }
+        }
+        Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        )
+    }
+}
+
+impl<F: Fn(Vec3) -> Vec4> DirectionalImageBuilder for F {
+    fn sample_direction(&self, direction: Vec3) -> Vec4 {
+        self(direction)
+    }
+}
+
+/// Convert a direction to equirectangular coordinates in `0..1`.
+fn direction_to_equirectangular(direction: Vec3) -> Vec2 {
+    Vec2::new(
+        0.5 + direction.x.atan2(-direction.z) / TAU,
+        direction.y.clamp(-1., 1.).acos() / PI,
+    )
+}
+
+/// Convert equirectangular coordinates in `0..1` to a direction.
+fn equirectangular_to_direction(position: Vec2) -> Vec3 {
+    let phi = (position.x - 0.5) * TAU;
+    let theta = position.y * PI;
+    Vec3::new(
+        theta.sin() * phi.sin(),
+        theta.cos(),
+        -theta.sin() * phi.cos(),
+    )
+}
+
+/// Use a 2d builder as an equirectangular map, see [`ImageBuilder::equirectangular`].
+pub struct EquirectangularInput<T: ImageBuilder> {
+    pub base: T,
+}
+
+impl<T: ImageBuilder> DirectionalImageBuilder for EquirectangularInput<T> {
+    fn sample_direction(&self, direction: Vec3) -> Vec4 {
+        self.base
+            .sample_color(direction_to_equirectangular(direction))
+    }
+}
+
+/// Seamless 3d noise sampled on the unit sphere, in `0..1`.
+#[cfg(feature = "noise-rs")]
+pub struct DirectionalNoise<T: noise::NoiseFn<f64, 3>> {
+    pub noise: T,
+    pub frequency: f32,
+}
+
+#[cfg(feature = "noise-rs")]
+impl<T: noise::NoiseFn<f64, 3>> DirectionalNoise<T> {
+    pub fn new(noise: T) -> Self {
+        DirectionalNoise {
+            noise,
+            frequency: 2.,
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+}
+
+#[cfg(feature = "noise-rs")]
+impl<T: noise::NoiseFn<f64, 3>> DirectionalImageBuilder for DirectionalNoise<T> {
+    fn sample_direction(&self, direction: Vec3) -> Vec4 {
+        let position = (direction * self.frequency).as_dvec3();
+        let x = self.noise.get(position.to_array()) as f32 * 0.5 + 0.5;
+        Vec4::new(x, x, x, 1.)
+    }
+}
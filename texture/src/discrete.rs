@@ -0,0 +1,183 @@
+use bevy::math::Vec2;
+use fastrand::Rng;
+
+use crate::{
+    ImageBuilder, TexGenSeed,
+    util::{hash_to_unit, hash2},
+};
+
+/// Uncorrelated random value per cell.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhiteNoiseImage {
+    pub seed: u32,
+    /// Number of cells in `0..1`.
+    pub frequency: f32,
+}
+
+impl WhiteNoiseImage {
+    pub fn new() -> Self {
+        WhiteNoiseImage {
+            seed: TexGenSeed::get(),
+            frequency: 256.,
+        }
+    }
+
+    pub fn new_seeded(seed: u32) -> Self {
+        WhiteNoiseImage {
+            seed,
+            frequency: 256.,
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+}
+
+impl ImageBuilder for WhiteNoiseImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let cell = (position * self.frequency).floor().as_ivec2();
+        hash_to_unit(hash2(cell.x, cell.y, self.seed))
+    }
+}
+
+/// Tileable blue noise generated by the void-and-cluster algorithm.
+///
+/// Values are evenly distributed in `0..1` with no low frequency clumping,
+/// making this ideal for dithering and dissolve masks.
+///
+/// The tile is precomputed on construction and spans `0..1`,
+/// use [`ImageBuilder::zoom_out`] to repeat it.
+///
+/// # Note
+///
+/// Generation is `O(size⁴)`, sizes above `128` are not recommended.
+#[derive(Debug, Clone)]
+pub struct BlueNoiseImage {
+    size: usize,
+    data: Vec<f32>,
+}
+
+impl BlueNoiseImage {
+    pub fn new() -> Self {
+        Self::with_size(64, TexGenSeed::get())
+    }
+
+    pub fn new_seeded(seed: u32) -> Self {
+        Self::with_size(64, seed)
+    }
+
+    /// Generate a `size * size` tile.
+    pub fn with_size(size: usize, seed: u32) -> Self {
+        let size = size.max(1);
+        BlueNoiseImage {
+            size,
+            data: void_and_cluster(size, seed as u64),
+        }
+    }
+
+    /// Width and height of the precomputed tile.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl ImageBuilder for BlueNoiseImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let size = self.size as i32;
+        let cell = (position * self.size as f32).floor().as_ivec2();
+        let x = cell.x.rem_euclid(size) as usize;
+        let y = cell.y.rem_euclid(size) as usize;
+        self.data[y * self.size + x]
+    }
+}
+
+fn void_and_cluster(size: usize, seed: u64) -> Vec<f32> {
+    const SIGMA: f32 = 1.5;
+    let n = size * size;
+    let mut rng = Rng::with_seed(seed);
+
+    let mut kernel = vec![0.0; n];
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x.min(size - x) as f32;
+            let dy = y.min(size - y) as f32;
+            kernel[y * size + x] = (-(dx * dx + dy * dy) / (2. * SIGMA * SIGMA)).exp();
+        }
+    }
+
+    let splat = |energy: &mut [f32], index: usize, sign: f32| {
+        let (px, py) = (index % size, index / size);
+        for y in 0..size {
+            for x in 0..size {
+                let kx = (x + size - px) % size;
+                let ky = (y + size - py) % size;
+                energy[y * size + x] += sign * kernel[ky * size + kx];
+            }
+        }
+    };
+
+    let tightest_cluster = |pattern: &[bool], energy: &[f32]| {
+        (0..n)
+            .filter(|i| pattern[*i])
+            .max_by(|a, b| energy[*a].total_cmp(&energy[*b]))
+            .unwrap_or(0)
+    };
+
+    let largest_void = |pattern: &[bool], energy: &[f32]| {
+        (0..n)
+            .filter(|i| !pattern[*i])
+            .min_by(|a, b| energy[*a].total_cmp(&energy[*b]))
+            .unwrap_or(0)
+    };
+
+    let initial = (n / 10).max(1);
+    let mut pattern = vec![false; n];
+    let mut energy = vec![0.0; n];
+    let mut placed = 0;
+    while placed < initial {
+        let i = rng.usize(0..n);
+        if !pattern[i] {
+            pattern[i] = true;
+            splat(&mut energy, i, 1.);
+            placed += 1;
+        }
+    }
+
+    // Move points from clusters to voids until stable.
+    for _ in 0..n {
+        let cluster = tightest_cluster(&pattern, &energy);
+        pattern[cluster] = false;
+        splat(&mut energy, cluster, -1.);
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        splat(&mut energy, void, 1.);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut rank = vec![0; n];
+
+    let mut removing = pattern.clone();
+    let mut removing_energy = energy.clone();
+    for r in (0..initial).rev() {
+        let cluster = tightest_cluster(&removing, &removing_energy);
+        removing[cluster] = false;
+        splat(&mut removing_energy, cluster, -1.);
+        rank[cluster] = r;
+    }
+
+    for r in initial..n {
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        splat(&mut energy, void, 1.);
+        rank[void] = r;
+    }
+
+    rank.into_iter()
+        .map(|r| (r as f32 + 0.5) / n as f32)
+        .collect()
+}
@@ -1,5 +1,9 @@
-use crate::{FbmNoiseImage, ImageBuilder, PureColorSampler, SimpleNoise};
-use bevy::math::{Vec2, Vec4, Vec4Swizzles};
+use std::f32::consts::TAU;
+
+use crate::ImageBuilder;
+#[cfg(feature = "noise-rs")]
+use crate::{FbmNoiseImage, PureColorSampler, SimpleNoise};
+use bevy::math::{Affine2, Vec2, Vec4, Vec4Swizzles};
 
 pub struct DistortionImage<A, B> {
     pub base: A,
@@ -22,6 +26,7 @@ impl<A: ImageBuilder, B: ImageBuilder> ImageBuilder for DistortionImage<A, B> {
 
 pub struct JoinXY<X, Y>(pub X, pub Y);
 
+#[cfg(feature = "noise-rs")]
 impl JoinXY<PureColorSampler, PureColorSampler> {
     pub fn noise<T: SimpleNoise>() -> JoinXY<FbmNoiseImage<T>, FbmNoiseImage<T>> {
         JoinXY(
@@ -84,3 +89,89 @@ impl<T: ImageBuilder> ImageBuilder for ScaledInput<T> {
         self.base.sample_color(position * self.scale)
     }
 }
+
+/// Maps the input coordinate from cartesian to polar around a center.
+///
+/// The base is sampled at `(radius, angle)`, where `radius` is `1` at distance `0.5` from center
+/// and `angle` is in `0..1` counter-clockwise from the `x` axis.
+pub struct PolarInput<T> {
+    pub base: T,
+    pub center: Vec2,
+}
+
+impl<T> PolarInput<T> {
+    pub fn new(base: T) -> Self {
+        PolarInput {
+            base,
+            center: Vec2::splat(0.5),
+        }
+    }
+
+    fn map(&self, position: Vec2) -> Vec2 {
+        let offset = position - self.center;
+        let angle = offset.y.atan2(offset.x) / TAU;
+        Vec2::new(offset.length() * 2., angle.rem_euclid(1.))
+    }
+}
+
+impl<T: ImageBuilder> ImageBuilder for PolarInput<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.base.sample(self.map(position))
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        self.base.sample_color(self.map(position))
+    }
+}
+
+/// Inverse of [`PolarInput`], maps an input of `(radius, angle)` to cartesian coordinates around a center.
+pub struct InversePolarInput<T> {
+    pub base: T,
+    pub center: Vec2,
+}
+
+impl<T> InversePolarInput<T> {
+    pub fn new(base: T) -> Self {
+        InversePolarInput {
+            base,
+            center: Vec2::splat(0.5),
+        }
+    }
+
+    fn map(&self, position: Vec2) -> Vec2 {
+        self.center + Vec2::from_angle(position.y * TAU) * position.x * 0.5
+    }
+}
+
+impl<T: ImageBuilder> ImageBuilder for InversePolarInput<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.base.sample(self.map(position))
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        self.base.sample_color(self.map(position))
+    }
+}
+
+/// Applies an affine transform to the input coordinate of the sampler.
+pub struct TransformedInput<T> {
+    pub base: T,
+    pub transform: Affine2,
+}
+
+impl<T> TransformedInput<T> {
+    pub fn new(base: T, transform: Affine2) -> Self {
+        TransformedInput { base, transform }
+    }
+}
+
+impl<T: ImageBuilder> ImageBuilder for TransformedInput<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.base.sample(self.transform.transform_point2(position))
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        self.base
+            .sample_color(self.transform.transform_point2(position))
+    }
+}
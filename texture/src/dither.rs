@@ -0,0 +1,58 @@
+use std::sync::OnceLock;
+
+use bevy::math::{Vec2, Vec4};
+
+use crate::{BlueNoiseImage, ImageBuilder};
+
+/// Dithering applied when quantizing samples to 8 bits,
+/// hides banding in smooth gradients.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dither {
+    /// Truncate values.
+    #[default]
+    None,
+    /// An `8x8` Bayer matrix, cheap with a visible cross hatch pattern.
+    Ordered,
+    /// A tiled `64x64` blue noise threshold, unstructured and less noticeable.
+    BlueNoise,
+}
+
+const BAYER: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+fn blue_noise() -> &'static BlueNoiseImage {
+    static BLUE_NOISE: OnceLock<BlueNoiseImage> = OnceLock::new();
+    BLUE_NOISE.get_or_init(|| BlueNoiseImage::with_size(64, 0))
+}
+
+impl Dither {
+    /// Threshold in `0..1` added before truncation at a pixel.
+    fn threshold(self, x: usize, y: usize) -> f32 {
+        match self {
+            Dither::None => 0.,
+            Dither::Ordered => (BAYER[y % 8][x % 8] as f32 + 0.5) / 64.,
+            Dither::BlueNoise => {
+                let size = blue_noise().size();
+                let position =
+                    Vec2::new((x % size) as f32 + 0.5, (y % size) as f32 + 0.5) / size as f32;
+                blue_noise().sample(position)
+            }
+        }
+    }
+
+    /// Quantize a color in `0..1` to 8 bits per channel at a pixel.
+    pub(crate) fn quantize(self, color: Vec4, x: usize, y: usize) -> [u8; 4] {
+        (color * 255. + Vec4::splat(self.threshold(x, y)))
+            .as_u8vec4()
+            .to_array()
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::math::{Vec2, Vec4};
+
+use crate::ImageBuilder;
+
+/// Object safe version of [`ImageBuilder`], implemented for all thread safe builders.
+///
+/// `Box<dyn DynImageBuilder>` implements [`ImageBuilder`], so boxed nodes can be combined with
+/// every combinator, allowing graphs to be assembled at runtime.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// fn layer(base: Box<dyn DynImageBuilder>, op: &str) -> Box<dyn DynImageBuilder> {
+///     match op {
+///         "invert" => base.invert().boxed(),
+///         "checker" => base.mix(CheckerImage::new(8.)).boxed(),
+///         _ => base,
+///     }
+/// }
+///
+/// let graph = ["invert", "checker"]
+///     .into_iter()
+///     .fold(VoronoiImage::new(5).boxed(), layer);
+/// let image = graph.to_image(64, 64);
+/// ```
+pub trait DynImageBuilder: Send + Sync {
+    /// Dynamic version of [`ImageBuilder::sample`].
+    fn dyn_sample(&self, position: Vec2) -> f32;
+
+    /// Dynamic version of [`ImageBuilder::sample_color`].
+    fn dyn_sample_color(&self, position: Vec2) -> Vec4;
+}
+
+impl<T: ImageBuilder + Send + Sync> DynImageBuilder for T {
+    fn dyn_sample(&self, position: Vec2) -> f32 {
+        self.sample(position)
+    }
+
+    fn dyn_sample_color(&self, position: Vec2) -> Vec4 {
+        self.sample_color(position)
+    }
+}
+
+impl ImageBuilder for Box<dyn DynImageBuilder> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.as_ref().dyn_sample(position)
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        self.as_ref().dyn_sample_color(position)
+    }
+}
+
+impl ImageBuilder for &dyn DynImageBuilder {
+    fn sample(&self, position: Vec2) -> f32 {
+        (*self).dyn_sample(position)
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        (*self).dyn_sample_color(position)
+    }
+}
@@ -0,0 +1,90 @@
+use std::f32::consts::TAU;
+
+use bevy::math::Vec2;
+
+use crate::{ImageBuilder, TexGenSeed, VoronoiImage, VoronoiOutput};
+
+/// Water or energy caustics, bright networks formed by layered inverted Voronoi edges
+/// with a domain warp.
+///
+/// Animate by changing `time`, which moves through a 3d Voronoi slice.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// let caustics = |t: f32| CausticsImage::new(4).with_time(t);
+/// let sheet = caustics.to_flipbook(64, 64, 16, 4, 4);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CausticsImage {
+    /// Number of cells in `0..1` of the first layer.
+    pub frequency: i32,
+    pub seed: u32,
+    /// Position of the 3d slice, shifts the cells smoothly.
+    pub time: f32,
+    /// Number of layers, each doubling the frequency.
+    pub layers: usize,
+    /// Strength of the domain warp relative to a cell.
+    pub warp: f32,
+    /// Exponent applied to the inverted edges, higher values produce thinner lines.
+    pub sharpness: f32,
+}
+
+impl CausticsImage {
+    pub fn new(frequency: i32) -> Self {
+        CausticsImage {
+            frequency,
+            seed: TexGenSeed::get(),
+            time: 0.,
+            layers: 2,
+            warp: 0.3,
+            sharpness: 6.,
+        }
+    }
+
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.time = time;
+        self
+    }
+
+    pub fn with_layers(mut self, layers: usize) -> Self {
+        self.layers = layers.max(1);
+        self
+    }
+
+    pub fn with_warp(mut self, warp: f32) -> Self {
+        self.warp = warp;
+        self
+    }
+
+    pub fn with_sharpness(mut self, sharpness: f32) -> Self {
+        self.sharpness = sharpness;
+        self
+    }
+}
+
+impl ImageBuilder for CausticsImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let mut result = 0.;
+        let mut weight = 0.;
+        for i in 0..self.layers {
+            let frequency = self.frequency.saturating_mul(2i32.saturating_pow(i as u32));
+            let k = frequency as f32 * TAU;
+            let phase = (self.time + i as f32 * 0.37) * TAU;
+            let warp = Vec2::new(
+                (position.y * k + phase).sin(),
+                (position.x * k - phase).cos(),
+            ) * self.warp
+                / frequency as f32;
+            let voronoi = VoronoiImage {
+                z: Some(self.time + i as f32 * 0.37),
+                ..VoronoiImage::new_seeded(frequency, self.seed.wrapping_add(i as u32))
+            }
+            .set_output(VoronoiOutput::Edges);
+            let edge = voronoi.sample(position + warp).clamp(0., 1.);
+            let amplitude = 1. / (i + 1) as f32;
+            result += (1. - edge).powf(self.sharpness) * amplitude;
+            weight += amplitude;
+        }
+        (result / weight).clamp(0., 1.)
+    }
+}
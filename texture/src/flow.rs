@@ -0,0 +1,107 @@
+use bevy::{
+    image::Image,
+    math::{Vec2, Vec4},
+};
+
+use crate::{ImageBuilder, JoinXY};
+
+/// A 2d vector field in uv space, used to generate flow maps for scrolling distortion shaders.
+///
+/// Implemented for closures `Fn(Vec2) -> Vec2` and [`JoinXY`], which maps `0..1` to `-1..1`.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::Vec2;
+/// let swirl = |p: Vec2| (p - 0.5).perp() * 2.;
+/// let image = swirl.to_flow_map(64, 64);
+/// let currents = GradientField::curl(NoiseBuilder::simplex().frequency(3.)).to_flow_map(64, 64);
+/// ```
+pub trait VectorFieldBuilder {
+    /// Sample a vector, the length is expected to be in `0..=1`.
+    fn sample_vector(&self, position: Vec2) -> Vec2;
+
+    /// Encode as a flow map, `rg` is the vector mapped from `-1..1` to `0..1`,
+    /// so `(0.5, 0.5)` is no flow. Vectors longer than `1` are clamped.
+    fn to_flow_map(&self, width: usize, height: usize) -> Image {
+        FlowMap(self).to_image(width, height)
+    }
+}
+
+impl<F: Fn(Vec2) -> Vec2> VectorFieldBuilder for F {
+    fn sample_vector(&self, position: Vec2) -> Vec2 {
+        self(position)
+    }
+}
+
+impl<X: ImageBuilder, Y: ImageBuilder> VectorFieldBuilder for JoinXY<X, Y> {
+    fn sample_vector(&self, position: Vec2) -> Vec2 {
+        Vec2::new(self.0.sample(position), self.1.sample(position)) * 2. - 1.
+    }
+}
+
+/// Flow map encoding of a [`VectorFieldBuilder`].
+struct FlowMap<'t, T: ?Sized>(&'t T);
+
+impl<T: VectorFieldBuilder + ?Sized> ImageBuilder for FlowMap<'_, T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.sample_color(position).x
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        let v = self.0.sample_vector(position).clamp_length_max(1.) * 0.5 + 0.5;
+        Vec4::new(v.x, v.y, 0., 1.)
+    }
+}
+
+/// The gradient of a heightfield, or its curl for divergence free swirling flow.
+///
+/// The gradient points uphill, the curl flows along the contour lines.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientField<T> {
+    pub base: T,
+    /// Scale applied to the gradient,
+    /// the gradient of a noise is roughly proportional to its frequency.
+    pub strength: f32,
+    /// Distance between samples of the central difference.
+    pub epsilon: f32,
+    /// Rotate the gradient by 90 degrees.
+    pub curl: bool,
+}
+
+impl<T: ImageBuilder> GradientField<T> {
+    pub fn new(base: T) -> Self {
+        GradientField {
+            base,
+            strength: 0.1,
+            epsilon: 1. / 512.,
+            curl: false,
+        }
+    }
+
+    pub fn curl(base: T) -> Self {
+        GradientField {
+            curl: true,
+            ..Self::new(base)
+        }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl<T: ImageBuilder> VectorFieldBuilder for GradientField<T> {
+    fn sample_vector(&self, position: Vec2) -> Vec2 {
+        let e = self.epsilon;
+        let dx = self.base.sample(position + Vec2::new(e, 0.))
+            - self.base.sample(position - Vec2::new(e, 0.));
+        let dy = self.base.sample(position + Vec2::new(0., e))
+            - self.base.sample(position - Vec2::new(0., e));
+        let gradient = Vec2::new(dx, dy) / (2. * e) * self.strength;
+        match self.curl {
+            true => gradient.perp(),
+            false => gradient,
+        }
+    }
+}
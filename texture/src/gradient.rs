@@ -0,0 +1,117 @@
+use std::f32::consts::TAU;
+
+use bevy::math::{Vec2, Vec4};
+
+use crate::ImageBuilder;
+
+/// Shape of a [`GradientImage`], mapping a position to `t` in `0..1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientShape {
+    /// Along a direction at `angle` radians, `0` is left to right,
+    /// spanning the `0..1` square corner to corner.
+    Linear { angle: f32 },
+    /// Distance from `center`, `1` at `radius`.
+    Radial { center: Vec2, radius: f32 },
+    /// Counter-clockwise angle around `center`, starting at `angle` radians.
+    Angular { center: Vec2, angle: f32 },
+}
+
+impl GradientShape {
+    fn t(&self, position: Vec2) -> f32 {
+        match *self {
+            GradientShape::Linear { angle } => {
+                let dir = Vec2::from_angle(angle);
+                let extent = dir.abs().element_sum();
+                ((position - 0.5).dot(dir) / extent + 0.5).clamp(0., 1.)
+            }
+            GradientShape::Radial { center, radius } => {
+                (position.distance(center) / radius).clamp(0., 1.)
+            }
+            GradientShape::Angular { center, angle } => {
+                let offset = position - center;
+                ((offset.y.atan2(offset.x) - angle) / TAU).rem_euclid(1.)
+            }
+        }
+    }
+}
+
+/// A gradient with multiple color stops, linearly interpolated.
+///
+/// Without stops the gradient is black to white.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::{Vec2, Vec4};
+/// let fire = GradientImage::radial(Vec2::splat(0.5), 0.5)
+///     .with_stop(0., Vec4::new(1., 1., 0.8, 1.))
+///     .with_stop(0.4, Vec4::new(1., 0.5, 0., 1.))
+///     .with_stop(1., Vec4::new(0.3, 0., 0., 0.));
+/// let sweep = GradientImage::angular(Vec2::splat(0.5)).to_image(64, 64);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GradientImage {
+    pub shape: GradientShape,
+    /// Sorted by position.
+    stops: Vec<(f32, Vec4)>,
+}
+
+impl GradientImage {
+    pub fn new(shape: GradientShape) -> Self {
+        GradientImage {
+            shape,
+            stops: Vec::new(),
+        }
+    }
+
+    pub fn linear(angle: f32) -> Self {
+        Self::new(GradientShape::Linear { angle })
+    }
+
+    pub fn radial(center: Vec2, radius: f32) -> Self {
+        Self::new(GradientShape::Radial { center, radius })
+    }
+
+    pub fn angular(center: Vec2) -> Self {
+        Self::new(GradientShape::Angular { center, angle: 0. })
+    }
+
+    /// Add a color stop at `t` in `0..1`.
+    pub fn with_stop(mut self, t: f32, color: Vec4) -> Self {
+        let index = self.stops.partition_point(|(s, _)| *s <= t);
+        self.stops.insert(index, (t, color));
+        self
+    }
+
+    pub fn stops(&self) -> &[(f32, Vec4)] {
+        &self.stops
+    }
+
+    /// Obtain the color at `t`, clamped to the first and last stop.
+    pub fn color_at(&self, t: f32) -> Vec4 {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Vec4::new(t, t, t, 1.);
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+        let i = self.stops.partition_point(|(s, _)| *s <= t);
+        let (a, b) = (self.stops[i - 1], self.stops[i]);
+        a.1.lerp(b.1, (t - a.0) / (b.0 - a.0))
+    }
+}
+
+impl ImageBuilder for GradientImage {
+    /// Returns `t` of the shape, ignoring stops.
+    fn sample(&self, position: Vec2) -> f32 {
+        self.shape.t(position)
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        self.color_at(self.shape.t(position))
+    }
+}
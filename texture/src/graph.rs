@@ -0,0 +1,479 @@
+use std::fmt::Display;
+
+use bevy::{
+    image::{Image, ImageAddressMode},
+    math::{Vec2, Vec4},
+    render::render_resource::TextureFormat,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BlueNoiseImage, Channel, CheckerImage, ComposeRgba, DotsImage, DynImageBuilder, GridImage,
+    HexImage, ImageBuilder, NoiseBuilder, NoiseKind, PureColorSampler, StripesImage,
+    SwizzleSampler, WhiteNoiseImage,
+};
+#[cfg(feature = "noiz")]
+use crate::{VoronoiDistance, VoronoiImage, VoronoiOutput};
+
+/// Describes a generated texture, the content of a `.texgen.ron` file.
+///
+/// Can be serialized with any `serde` format to share texture definitions between tools.
+///
+/// ```ron
+/// (
+///     width: 256,
+///     height: 256,
+///     address_mode_u: Repeat,
+///     address_mode_v: Repeat,
+///     builder: Pow(Voronoi(frequency: 4), 3.0),
+/// )
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TexGenDescriptor {
+    pub width: usize,
+    pub height: usize,
+    #[serde(default)]
+    pub format: TexGenFormat,
+    #[serde(default)]
+    pub address_mode_u: ImageAddressMode,
+    #[serde(default)]
+    pub address_mode_v: ImageAddressMode,
+    pub builder: TexGenNode,
+}
+
+impl TexGenDescriptor {
+    /// Generate the described image.
+    pub fn generate(&self) -> Result<Image, TexGenError> {
+        let mut image = self.builder.build()?.to_image(self.width, self.height);
+        image.texture_descriptor.format = match self.format {
+            TexGenFormat::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+            TexGenFormat::Rgba8UnormSrgb => TextureFormat::Rgba8UnormSrgb,
+        };
+        let descriptor = image.sampler.get_or_init_descriptor();
+        descriptor.address_mode_u = self.address_mode_u;
+        descriptor.address_mode_v = self.address_mode_v;
+        Ok(image)
+    }
+}
+
+/// Output format of a [`TexGenDescriptor`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TexGenFormat {
+    /// Linear data, like masks and noises.
+    #[default]
+    Rgba8Unorm,
+    /// Color data.
+    Rgba8UnormSrgb,
+}
+
+/// Optional parameters of fractal noises, unspecified values use the defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FractalParameters {
+    #[serde(default)]
+    pub octaves: Option<usize>,
+    #[serde(default)]
+    pub frequency: Option<f32>,
+    #[serde(default)]
+    pub lacunarity: Option<f32>,
+    #[serde(default)]
+    pub persistence: Option<f32>,
+}
+
+impl FractalParameters {
+    fn apply(&self, mut noise: NoiseBuilder) -> NoiseBuilder {
+        if let Some(octaves) = self.octaves {
+            noise = noise.octaves(octaves);
+        }
+        if let Some(frequency) = self.frequency {
+            noise = noise.frequency(frequency);
+        }
+        if let Some(lacunarity) = self.lacunarity {
+            noise = noise.lacunarity(lacunarity);
+        }
+        if let Some(persistence) = self.persistence {
+            noise = noise.persistence(persistence);
+        }
+        noise
+    }
+}
+
+#[cfg(feature = "noiz")]
+fn default_frequency() -> i32 {
+    5
+}
+
+#[cfg(feature = "noiz")]
+fn default_stretch() -> [f32; 2] {
+    [1., 1.]
+}
+
+fn default_white_frequency() -> f32 {
+    256.
+}
+
+fn default_blue_size() -> usize {
+    64
+}
+
+fn default_stripe_width() -> f32 {
+    0.5
+}
+
+/// A serializable description of an [`ImageBuilder`] graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TexGenNode {
+    /// A constant color.
+    Color([f32; 4]),
+    Noise {
+        #[serde(default)]
+        noise: NoiseKind,
+        #[serde(default)]
+        seed: u32,
+    },
+    Fbm {
+        #[serde(default)]
+        noise: NoiseKind,
+        #[serde(default)]
+        seed: u32,
+        #[serde(default)]
+        parameters: FractalParameters,
+    },
+    Ridged {
+        #[serde(default)]
+        noise: NoiseKind,
+        #[serde(default)]
+        seed: u32,
+        #[serde(default)]
+        parameters: FractalParameters,
+    },
+    Billow {
+        #[serde(default)]
+        noise: NoiseKind,
+        #[serde(default)]
+        seed: u32,
+        #[serde(default)]
+        parameters: FractalParameters,
+    },
+    #[cfg(feature = "noiz")]
+    Voronoi {
+        #[serde(default = "default_frequency")]
+        frequency: i32,
+        #[serde(default)]
+        seed: u32,
+        #[serde(default)]
+        z: Option<f32>,
+        #[serde(default)]
+        output: VoronoiOutput,
+        #[serde(default)]
+        distance: VoronoiDistance,
+        #[serde(default = "default_stretch")]
+        stretch: [f32; 2],
+        #[serde(default)]
+        angle: f32,
+    },
+    WhiteNoise {
+        #[serde(default)]
+        seed: u32,
+        #[serde(default = "default_white_frequency")]
+        frequency: f32,
+    },
+    BlueNoise {
+        #[serde(default = "default_blue_size")]
+        size: usize,
+        #[serde(default)]
+        seed: u32,
+    },
+    Checker {
+        cells: [f32; 2],
+    },
+    Stripes {
+        frequency: f32,
+        #[serde(default)]
+        angle: f32,
+        #[serde(default = "default_stripe_width")]
+        width: f32,
+    },
+    Grid {
+        cells: [f32; 2],
+        thickness: f32,
+    },
+    Dots {
+        cells: [f32; 2],
+        radius: f32,
+        #[serde(default)]
+        staggered: bool,
+    },
+    Hex {
+        frequency: f32,
+        thickness: f32,
+    },
+    Mix(Box<TexGenNode>, Box<TexGenNode>),
+    Add(Box<TexGenNode>, Box<TexGenNode>),
+    Subtract(Box<TexGenNode>, Box<TexGenNode>),
+    Min(Box<TexGenNode>, Box<TexGenNode>),
+    Max(Box<TexGenNode>, Box<TexGenNode>),
+    Screen(Box<TexGenNode>, Box<TexGenNode>),
+    Overlay(Box<TexGenNode>, Box<TexGenNode>),
+    Lerp {
+        base: Box<TexGenNode>,
+        layer: Box<TexGenNode>,
+        mask: Box<TexGenNode>,
+    },
+    Invert(Box<TexGenNode>),
+    Threshold(Box<TexGenNode>, f32),
+    Smoothstep(Box<TexGenNode>, f32, f32),
+    Levels(Box<TexGenNode>, f32, f32, f32),
+    Posterize(Box<TexGenNode>, usize),
+    /// Raise the grayscale value to a power.
+    Pow(Box<TexGenNode>, f32),
+    Amplify(Box<TexGenNode>, f32),
+    AlphaWhite(Box<TexGenNode>),
+    ZoomIn(Box<TexGenNode>, [f32; 2]),
+    ZoomOut(Box<TexGenNode>, [f32; 2]),
+    Rotate(Box<TexGenNode>, f32),
+    /// Frequency multiplier along axes rotated by an angle.
+    Anisotropic(Box<TexGenNode>, [f32; 2], f32),
+    Translate(Box<TexGenNode>, [f32; 2]),
+    MirrorX(Box<TexGenNode>),
+    MirrorY(Box<TexGenNode>),
+    Polar(Box<TexGenNode>),
+    InversePolar(Box<TexGenNode>),
+    Distort {
+        base: Box<TexGenNode>,
+        x: Box<TexGenNode>,
+        y: Box<TexGenNode>,
+    },
+    Channel(Box<TexGenNode>, Channel),
+    Swizzle(Box<TexGenNode>, String),
+    ComposeRgba(
+        Box<TexGenNode>,
+        Box<TexGenNode>,
+        Box<TexGenNode>,
+        Box<TexGenNode>,
+    ),
+}
+
+type Compiled = Box<dyn DynImageBuilder>;
+
+fn boxed(builder: impl ImageBuilder + Send + Sync + 'static) -> Compiled {
+    builder.boxed()
+}
+
+impl TexGenNode {
+    /// Compile into an [`ImageBuilder`].
+    pub fn build(&self) -> Result<Box<dyn DynImageBuilder>, TexGenError> {
+        use TexGenNode as N;
+        let node = Self::build;
+        Ok(match self {
+            N::Color(color) => boxed(PureColorSampler(Vec4::from_array(*color))),
+            N::Noise { noise, seed } => boxed(NoiseBuilder::new(*noise).seeded(*seed)),
+            N::Fbm {
+                noise,
+                seed,
+                parameters,
+            } => boxed(parameters.apply(NoiseBuilder::new(*noise).seeded(*seed).fbm())),
+            N::Ridged {
+                noise,
+                seed,
+                parameters,
+            } => boxed(parameters.apply(NoiseBuilder::new(*noise).seeded(*seed).ridged())),
+            N::Billow {
+                noise,
+                seed,
+                parameters,
+            } => boxed(parameters.apply(NoiseBuilder::new(*noise).seeded(*seed).billow())),
+            #[cfg(feature = "noiz")]
+            N::Voronoi {
+                frequency,
+                seed,
+                z,
+                output,
+                distance,
+                stretch,
+                angle,
+            } => boxed(VoronoiImage {
+                z: *z,
+                ..VoronoiImage::new_seeded(*frequency, *seed)
+                    .set_output(*output)
+                    .set_distance_function(*distance)
+                    .set_anisotropy(Vec2::from_array(*stretch), *angle)
+            }),
+            N::WhiteNoise { seed, frequency } => {
+                boxed(WhiteNoiseImage::new_seeded(*seed).with_frequency(*frequency))
+            }
+            N::BlueNoise { size, seed } => boxed(BlueNoiseImage::with_size(*size, *seed)),
+            N::Checker { cells } => boxed(CheckerImage {
+                cells: Vec2::from_array(*cells),
+            }),
+            N::Stripes {
+                frequency,
+                angle,
+                width,
+            } => boxed(StripesImage {
+                frequency: *frequency,
+                angle: *angle,
+                width: *width,
+            }),
+            N::Grid { cells, thickness } => boxed(GridImage {
+                cells: Vec2::from_array(*cells),
+                thickness: *thickness,
+            }),
+            N::Dots {
+                cells,
+                radius,
+                staggered,
+            } => boxed(DotsImage {
+                cells: Vec2::from_array(*cells),
+                radius: *radius,
+                staggered: *staggered,
+            }),
+            N::Hex {
+                frequency,
+                thickness,
+            } => boxed(HexImage::new(*frequency, *thickness)),
+            N::Mix(a, b) => boxed(node(a)?.mix(node(b)?)),
+            N::Add(a, b) => boxed(node(a)?.add(node(b)?)),
+            N::Subtract(a, b) => boxed(node(a)?.subtract(node(b)?)),
+            N::Min(a, b) => boxed(node(a)?.min(node(b)?)),
+            N::Max(a, b) => boxed(node(a)?.max(node(b)?)),
+            N::Screen(a, b) => boxed(node(a)?.screen(node(b)?)),
+            N::Overlay(a, b) => boxed(node(a)?.overlay(node(b)?)),
+            N::Lerp { base, layer, mask } => {
+                boxed(node(base)?.lerp_masked(node(layer)?, node(mask)?))
+            }
+            N::Invert(a) => boxed(node(a)?.invert()),
+            N::Threshold(a, t) => boxed(node(a)?.threshold(*t)),
+            N::Smoothstep(a, low, high) => boxed(node(a)?.smoothstep(*low, *high)),
+            N::Levels(a, low, high, gamma) => boxed(node(a)?.levels(*low, *high, *gamma)),
+            N::Posterize(a, n) => boxed(node(a)?.posterize(*n)),
+            N::Pow(a, p) => {
+                let p = *p;
+                boxed(node(a)?.map_value(move |_, x| x.powf(p)))
+            }
+            N::Amplify(a, fac) => boxed(node(a)?.amplify(*fac)),
+            N::AlphaWhite(a) => boxed(node(a)?.alpha_white()),
+            N::ZoomIn(a, scale) => boxed(node(a)?.zoom_in(Vec2::from_array(*scale))),
+            N::ZoomOut(a, scale) => boxed(node(a)?.zoom_out(Vec2::from_array(*scale))),
+            N::Rotate(a, angle) => boxed(node(a)?.rotate(*angle)),
+            N::Anisotropic(a, stretch, angle) => {
+                boxed(node(a)?.anisotropic(Vec2::from_array(*stretch), *angle))
+            }
+            N::Translate(a, offset) => boxed(node(a)?.translate(Vec2::from_array(*offset))),
+            N::MirrorX(a) => boxed(node(a)?.mirror_x()),
+            N::MirrorY(a) => boxed(node(a)?.mirror_y()),
+            N::Polar(a) => boxed(node(a)?.polar()),
+            N::InversePolar(a) => boxed(node(a)?.inverse_polar()),
+            N::Distort { base, x, y } => boxed(node(base)?.distort(node(x)?, node(y)?)),
+            N::Channel(a, channel) => boxed(node(a)?.channel(*channel)),
+            N::Swizzle(a, swizzle) => {
+                if swizzle.chars().count() != 4
+                    || swizzle.chars().any(|c| Channel::from_char(c).is_none())
+                {
+                    return Err(TexGenError::InvalidSwizzle(swizzle.clone()));
+                }
+                boxed(SwizzleSampler::new(node(a)?, swizzle))
+            }
+            N::ComposeRgba(r, g, b, a) => {
+                boxed(ComposeRgba(node(r)?, node(g)?, node(b)?, node(a)?))
+            }
+        })
+    }
+}
+
+/// Error from loading or building a [`TexGenDescriptor`].
+#[derive(Debug)]
+pub enum TexGenError {
+    Io(std::io::Error),
+    #[cfg(feature = "ron")]
+    Ron(ron::error::SpannedError),
+    InvalidSwizzle(String),
+}
+
+impl Display for TexGenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TexGenError::Io(err) => write!(f, "Could not read texgen file: {err}"),
+            #[cfg(feature = "ron")]
+            TexGenError::Ron(err) => write!(f, "Could not parse texgen file: {err}"),
+            TexGenError::InvalidSwizzle(swizzle) => write!(f, "Invalid swizzle {swizzle:?}."),
+        }
+    }
+}
+
+impl std::error::Error for TexGenError {}
+
+impl From<std::io::Error> for TexGenError {
+    fn from(value: std::io::Error) -> Self {
+        TexGenError::Io(value)
+    }
+}
+
+#[cfg(feature = "noiz")]
+impl From<VoronoiImage> for TexGenNode {
+    fn from(value: VoronoiImage) -> Self {
+        TexGenNode::Voronoi {
+            frequency: value.frequency,
+            seed: value.seed,
+            z: value.z,
+            output: value.output,
+            distance: value.distance,
+            stretch: value.stretch.to_array(),
+            angle: value.angle,
+        }
+    }
+}
+
+impl From<WhiteNoiseImage> for TexGenNode {
+    fn from(value: WhiteNoiseImage) -> Self {
+        TexGenNode::WhiteNoise {
+            seed: value.seed,
+            frequency: value.frequency,
+        }
+    }
+}
+
+impl From<CheckerImage> for TexGenNode {
+    fn from(value: CheckerImage) -> Self {
+        TexGenNode::Checker {
+            cells: value.cells.to_array(),
+        }
+    }
+}
+
+impl From<StripesImage> for TexGenNode {
+    fn from(value: StripesImage) -> Self {
+        TexGenNode::Stripes {
+            frequency: value.frequency,
+            angle: value.angle,
+            width: value.width,
+        }
+    }
+}
+
+impl From<GridImage> for TexGenNode {
+    fn from(value: GridImage) -> Self {
+        TexGenNode::Grid {
+            cells: value.cells.to_array(),
+            thickness: value.thickness,
+        }
+    }
+}
+
+impl From<DotsImage> for TexGenNode {
+    fn from(value: DotsImage) -> Self {
+        TexGenNode::Dots {
+            cells: value.cells.to_array(),
+            radius: value.radius,
+            staggered: value.staggered,
+        }
+    }
+}
+
+impl From<HexImage> for TexGenNode {
+    fn from(value: HexImage) -> Self {
+        TexGenNode::Hex {
+            frequency: value.frequency,
+            thickness: value.thickness,
+        }
+    }
+}
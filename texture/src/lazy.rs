@@ -1,19 +1,34 @@
-use std::sync::OnceLock;
+use std::{
+    fmt::Debug,
+    sync::{OnceLock, RwLock},
+};
 
 use bevy::{
     app::App,
-    asset::{Assets, DirectAssetAccessExt, Handle},
+    asset::{Asset, Assets, DirectAssetAccessExt, Handle},
     ecs::world::World,
-    image::Image,
+    image::{Image, ImageAddressMode},
+    render::mesh::Mesh,
 };
 
+type ImageFn = Box<dyn Fn() -> Image + Send + Sync>;
+
 /// A static compatible lazily initialized image.
-#[derive(Debug)]
 pub struct LazyImage {
     get: fn() -> Image,
+    builder: RwLock<Option<ImageFn>>,
+    address_mode: Option<(ImageAddressMode, ImageAddressMode)>,
     cell: OnceLock<Handle<Image>>,
 }
 
+impl Debug for LazyImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyImage")
+            .field("cell", &self.cell)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Create a static compatible lazily initialized image.
 ///
 /// Syntax:
@@ -44,28 +59,20 @@ macro_rules! lazy_image {
         $crate::LazyImage::new(|| $crate::ImageBuilder::to_image(&$builder, $width, $height))
     };
     ($width: expr, $height: expr, $builder: expr, $address_mode: expr $(,)?) => {
-        $crate::LazyImage::new(|| {
-            let mut image = $crate::ImageBuilder::to_image(&$builder, $width, $height);
-            {
-                use $crate::ImageAddressMode::*;
-                let descriptor = image.sampler.get_or_init_descriptor();
-                descriptor.address_mode_u = $address_mode;
-                descriptor.address_mode_v = $address_mode;
-            }
-            image
-        })
+        $crate::lazy_image!($width, $height, $builder, $address_mode, $address_mode)
     };
     ($width: expr, $height: expr, $builder: expr, $address_mode_u: expr, $address_mode_v: expr $(,)?) => {
-        $crate::LazyImage::new(|| {
-            let mut image = $crate::ImageBuilder::to_image(&$builder, $width, $height);
-            {
-                use $crate::ImageAddressMode::*;
-                let descriptor = image.sampler.get_or_init_descriptor();
-                descriptor.address_mode_u = $address_mode_u;
-                descriptor.address_mode_v = $address_mode_v;
-            }
-            image
-        })
+        $crate::LazyImage::new(|| $crate::ImageBuilder::to_image(&$builder, $width, $height))
+            .with_address_mode(
+                {
+                    use $crate::ImageAddressMode::*;
+                    $address_mode_u
+                },
+                {
+                    use $crate::ImageAddressMode::*;
+                    $address_mode_v
+                },
+            )
     };
 }
 
@@ -73,16 +80,72 @@ impl LazyImage {
     pub const fn new(f: fn() -> Image) -> LazyImage {
         LazyImage {
             get: f,
+            builder: RwLock::new(None),
+            address_mode: None,
             cell: OnceLock::new(),
         }
     }
 
+    /// Set the address modes of the image in `u` and `v`,
+    /// applied to every generated image, including ones from [`set_builder`](LazyImage::set_builder).
+    pub const fn with_address_mode(
+        mut self,
+        address_mode_u: ImageAddressMode,
+        address_mode_v: ImageAddressMode,
+    ) -> LazyImage {
+        self.address_mode = Some((address_mode_u, address_mode_v));
+        self
+    }
+
+    fn generate(&self) -> Image {
+        let mut image = match self.builder.read().unwrap().as_ref() {
+            Some(builder) => builder(),
+            None => (self.get)(),
+        };
+        if let Some((address_mode_u, address_mode_v)) = self.address_mode {
+            let descriptor = image.sampler.get_or_init_descriptor();
+            descriptor.address_mode_u = address_mode_u;
+            descriptor.address_mode_v = address_mode_v;
+        }
+        image
+    }
+
     pub fn load(&self, world: &mut World) {
-        let _ = self.cell.get_or_init(|| world.add_asset((self.get)()));
+        let _ = self.cell.get_or_init(|| world.add_asset(self.generate()));
     }
 
     pub fn get_or_load(&self, assets: &mut Assets<Image>) -> &Handle<Image> {
-        self.cell.get_or_init(|| assets.add((self.get)()))
+        self.cell.get_or_init(|| assets.add(self.generate()))
+    }
+
+    /// Replace the function used to generate the image,
+    /// takes effect on the next load or [`regenerate`](LazyImage::regenerate).
+    ///
+    /// Address modes of [`with_address_mode`](LazyImage::with_address_mode) are kept.
+    ///
+    /// ```
+    /// # use bevy_texture_gen::*;
+    /// # static VORONOI: LazyImage = lazy_image!(512, 512, VoronoiImage::new(5));
+    /// let frequency = 8;
+    /// VORONOI.set_builder(move || VoronoiImage::new(frequency).to_image(512, 512));
+    /// ```
+    pub fn set_builder(&self, builder: impl Fn() -> Image + Send + Sync + 'static) {
+        *self.builder.write().unwrap() = Some(Box::new(builder));
+    }
+
+    /// Rebuild the image in place, keeping the same handle,
+    /// so every material using the handle is updated.
+    ///
+    /// Loads the image if not initialized.
+    pub fn regenerate(&self, assets: &mut Assets<Image>) -> &Handle<Image> {
+        match self.cell.get() {
+            Some(handle) => {
+                // Only panics if the index was reused, which the strong handle in `cell` prevents.
+                assets.insert(handle, self.generate());
+                handle
+            }
+            None => self.get_or_load(assets),
+        }
     }
 
     /// # Panics
@@ -93,9 +156,38 @@ impl LazyImage {
     }
 }
 
+/// A [`LazyImage`] registered by [`register_lazy_image!`](crate::register_lazy_image).
+#[cfg(feature = "inventory")]
+pub struct RegisteredLazyImage(pub &'static LazyImage);
+
+#[cfg(feature = "inventory")]
+inventory::collect!(RegisteredLazyImage);
+
+/// Register a static [`LazyImage`] to be loaded by
+/// [`load_registered_lazy_images`](LoadLazyImageExt::load_registered_lazy_images).
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// static VORONOI: LazyImage = lazy_image!(512, 512, VoronoiImage::new(5));
+/// register_lazy_image!(VORONOI);
+/// ```
+#[cfg(feature = "inventory")]
+#[macro_export]
+macro_rules! register_lazy_image {
+    ($($image: path),* $(,)?) => {
+        $($crate::inventory::submit! {
+            $crate::RegisteredLazyImage(&$image)
+        })*
+    };
+}
+
 /// Extension for loading lazy image.
 pub trait LoadLazyImageExt {
     fn load_lazy_image(&mut self, image: &LazyImage) -> &mut Self;
+
+    /// Load all [`LazyImage`]s registered by [`register_lazy_image!`](crate::register_lazy_image).
+    #[cfg(feature = "inventory")]
+    fn load_registered_lazy_images(&mut self) -> &mut Self;
 }
 
 impl LoadLazyImageExt for World {
@@ -103,6 +195,14 @@ impl LoadLazyImageExt for World {
         image.load(self);
         self
     }
+
+    #[cfg(feature = "inventory")]
+    fn load_registered_lazy_images(&mut self) -> &mut Self {
+        for image in inventory::iter::<RegisteredLazyImage> {
+            image.0.load(self);
+        }
+        self
+    }
 }
 
 impl LoadLazyImageExt for App {
@@ -110,4 +210,133 @@ impl LoadLazyImageExt for App {
         image.load(self.world_mut());
         self
     }
+
+    #[cfg(feature = "inventory")]
+    fn load_registered_lazy_images(&mut self) -> &mut Self {
+        self.world_mut().load_registered_lazy_images();
+        self
+    }
+}
+
+/// A static compatible lazily initialized [`Mesh`].
+#[derive(Debug)]
+pub struct LazyMesh {
+    get: fn() -> Mesh,
+    cell: OnceLock<Handle<Mesh>>,
+}
+
+/// Create a static compatible lazily initialized mesh from anything that converts into a [`Mesh`].
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::primitives::Circle;
+/// static CIRCLE: LazyMesh = lazy_mesh!(Circle::new(1.));
+/// ```
+#[macro_export]
+macro_rules! lazy_mesh {
+    ($mesh: expr $(,)?) => {
+        $crate::LazyMesh::new(|| ::core::convert::Into::into($mesh))
+    };
+}
+
+impl LazyMesh {
+    pub const fn new(f: fn() -> Mesh) -> LazyMesh {
+        LazyMesh {
+            get: f,
+            cell: OnceLock::new(),
+        }
+    }
+
+    pub fn load(&self, world: &mut World) {
+        let _ = self.cell.get_or_init(|| world.add_asset((self.get)()));
+    }
+
+    pub fn get_or_load(&self, assets: &mut Assets<Mesh>) -> &Handle<Mesh> {
+        self.cell.get_or_init(|| assets.add((self.get)()))
+    }
+
+    /// # Panics
+    ///
+    /// If not initialized.
+    pub fn get(&self) -> Handle<Mesh> {
+        self.cell.get().unwrap().clone()
+    }
+}
+
+/// A static compatible lazily initialized material, or any other [`Asset`].
+#[derive(Debug)]
+pub struct LazyMaterial<M: Asset> {
+    get: fn() -> M,
+    cell: OnceLock<Handle<M>>,
+}
+
+/// Create a static compatible lazily initialized material.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::pbr::StandardMaterial;
+/// static RED: LazyMaterial<StandardMaterial> = lazy_material!(StandardMaterial {
+///     base_color: bevy::color::Color::srgb(1., 0., 0.),
+///     ..Default::default()
+/// });
+/// ```
+#[macro_export]
+macro_rules! lazy_material {
+    ($material: expr $(,)?) => {
+        $crate::LazyMaterial::new(|| $material)
+    };
+}
+
+impl<M: Asset> LazyMaterial<M> {
+    pub const fn new(f: fn() -> M) -> LazyMaterial<M> {
+        LazyMaterial {
+            get: f,
+            cell: OnceLock::new(),
+        }
+    }
+
+    pub fn load(&self, world: &mut World) {
+        let _ = self.cell.get_or_init(|| world.add_asset((self.get)()));
+    }
+
+    pub fn get_or_load(&self, assets: &mut Assets<M>) -> &Handle<M> {
+        self.cell.get_or_init(|| assets.add((self.get)()))
+    }
+
+    /// # Panics
+    ///
+    /// If not initialized.
+    pub fn get(&self) -> Handle<M> {
+        self.cell.get().unwrap().clone()
+    }
+}
+
+/// Extension for loading [`LazyMesh`] and [`LazyMaterial`].
+pub trait LoadLazyAssetExt {
+    fn load_lazy_mesh(&mut self, mesh: &LazyMesh) -> &mut Self;
+    fn load_lazy_material<M: Asset>(&mut self, material: &LazyMaterial<M>) -> &mut Self;
+}
+
+impl LoadLazyAssetExt for World {
+    fn load_lazy_mesh(&mut self, mesh: &LazyMesh) -> &mut Self {
+        mesh.load(self);
+        self
+    }
+
+    fn load_lazy_material<M: Asset>(&mut self, material: &LazyMaterial<M>) -> &mut Self {
+        material.load(self);
+        self
+    }
+}
+
+impl LoadLazyAssetExt for App {
+    fn load_lazy_mesh(&mut self, mesh: &LazyMesh) -> &mut Self {
+        mesh.load(self.world_mut());
+        self
+    }
+
+    fn load_lazy_material<M: Asset>(&mut self, material: &LazyMaterial<M>) -> &mut Self {
+        material.load(self.world_mut());
+        self
+    }
 }
@@ -1,20 +1,84 @@
 #![allow(clippy::new_without_default)]
 #![allow(clippy::field_reassign_with_default)]
+mod animation;
+mod atlas;
+mod channel;
+mod cubemap;
+mod discrete;
 mod distortion;
+mod dither;
+mod dynamic;
+#[cfg(feature = "noiz")]
+mod effects;
+mod flow;
+mod gradient;
+#[cfg(all(feature = "serde", any(feature = "noise-rs", feature = "noiz")))]
+mod graph;
 mod lazy;
+mod lightning;
+#[cfg(feature = "noise-rs")]
 mod noise;
+mod pattern;
+mod raster;
+mod sampler;
+#[cfg(any(feature = "png", feature = "exr"))]
+mod save;
+mod seed;
+#[cfg(feature = "shaders")]
+mod shaders;
+mod stars;
+#[cfg(all(feature = "ron", any(feature = "noise-rs", feature = "noiz")))]
+mod texgen;
+#[cfg(any(feature = "noise-rs", feature = "noiz"))]
+mod unified;
 mod util;
+#[cfg(feature = "noiz")]
 mod voronoi;
+#[cfg(feature = "noise-rs")]
 pub use ::noise as noise_rs;
+pub use animation::*;
+pub use atlas::*;
 use bevy::{
     asset::RenderAssetUsages,
     image::Image,
-    math::{Vec2, Vec3, Vec4, Vec4Swizzles},
+    math::{Affine2, Vec2, Vec3, Vec4, Vec4Swizzles},
     render::render_resource::{Extent3d, TextureDimension, TextureFormat},
 };
+pub use channel::*;
+pub use cubemap::*;
+pub use discrete::*;
 pub use distortion::*;
+pub use dither::*;
+pub use dynamic::*;
+#[cfg(feature = "noiz")]
+pub use effects::*;
+pub use flow::*;
+pub use gradient::*;
+#[cfg(all(feature = "serde", any(feature = "noise-rs", feature = "noiz")))]
+pub use graph::*;
+#[doc(hidden)]
+#[cfg(feature = "inventory")]
+pub use inventory;
 pub use lazy::*;
+pub use lightning::*;
+#[cfg(feature = "noise-rs")]
 pub use noise::*;
+pub use pattern::*;
+pub use raster::*;
+pub use sampler::*;
+#[cfg(any(feature = "png", feature = "exr"))]
+pub use save::*;
+pub use seed::*;
+#[cfg(feature = "shaders")]
+pub use shaders::*;
+pub use stars::*;
+use std::ops::ControlFlow;
+#[cfg(all(feature = "ron", any(feature = "noise-rs", feature = "noiz")))]
+pub use texgen::*;
+#[cfg(any(feature = "noise-rs", feature = "noiz"))]
+pub use unified::*;
+use util::{anisotropy, sample_range, supersample_offset};
+#[cfg(feature = "noiz")]
 pub use voronoi::*;
 
 #[doc(hidden)]
@@ -42,6 +106,51 @@ pub trait ImageBuilder: Sized {
         ImageMultiply(self, node)
     }
 
+    /// Adds two nodes.
+    fn add(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, |a, b| a + b)
+    }
+
+    /// Subtracts a node from this node.
+    fn subtract(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, |a, b| a - b)
+    }
+
+    /// Takes the minimum of two nodes.
+    fn min(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, f32::min)
+    }
+
+    /// Takes the maximum of two nodes.
+    fn max(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, f32::max)
+    }
+
+    /// Inverse of multiplying the inverses, brightens without exceeding `1`.
+    fn screen(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, |a, b| 1. - (1. - a) * (1. - b))
+    }
+
+    /// Multiply or screen depending on the value of this node, increases contrast.
+    fn overlay(self, node: impl ImageBuilder) -> impl ImageBuilder {
+        ImageBlend::new(self, node, |a, b| {
+            if a < 0.5 {
+                2. * a * b
+            } else {
+                1. - 2. * (1. - a) * (1. - b)
+            }
+        })
+    }
+
+    /// Linearly interpolate from this node to another node by the value of a mask.
+    fn lerp_masked(self, node: impl ImageBuilder, mask: impl ImageBuilder) -> impl ImageBuilder {
+        MaskedLerp {
+            base: self,
+            layer: node,
+            mask,
+        }
+    }
+
     /// Maps sampled grayscale value into a grayscale image.
     fn map_value(self, f: impl Fn(Vec2, f32) -> f32) -> impl ImageBuilder {
         NoiseMappedSampler {
@@ -50,6 +159,58 @@ pub trait ImageBuilder: Sized {
         }
     }
 
+    /// Maps `x` to `1 - x`.
+    fn invert(self) -> impl ImageBuilder {
+        self.map_value(|_, x| 1. - x)
+    }
+
+    /// Returns `1` if value is greater than or equal to `threshold`, `0` otherwise.
+    fn threshold(self, threshold: f32) -> impl ImageBuilder {
+        self.map_value(move |_, x| if x >= threshold { 1. } else { 0. })
+    }
+
+    /// Smooth hermite interpolation from `0` at `low` to `1` at `high`.
+    fn smoothstep(self, low: f32, high: f32) -> impl ImageBuilder {
+        self.map_value(move |_, x| {
+            let t = ((x - low) / (high - low)).clamp(0., 1.);
+            t * t * (3. - 2. * t)
+        })
+    }
+
+    /// Remaps `in_low..in_high` to `0..1` with clamping, then applies a gamma curve.
+    ///
+    /// Gamma values above `1` brighten the midtones while values below `1` darken them.
+    fn levels(self, in_low: f32, in_high: f32, gamma: f32) -> impl ImageBuilder {
+        self.map_value(move |_, x| {
+            ((x - in_low) / (in_high - in_low))
+                .clamp(0., 1.)
+                .powf(1. / gamma)
+        })
+    }
+
+    /// Quantize the value into `n` evenly spaced levels in `0..=1`.
+    fn posterize(self, n: usize) -> impl ImageBuilder {
+        let steps = n.max(2) as f32 - 1.;
+        self.map_value(move |_, x| (x.clamp(0., 1.) * steps).round() / steps)
+    }
+
+    /// Rescale the value range to fill `0..1`, useful for fractal noises that only
+    /// occupy a narrow band.
+    ///
+    /// The range is measured once on construction by scanning a `128x128` grid over `0..1`,
+    /// and applied to the rgb channels.
+    fn normalized(self) -> impl ImageBuilder {
+        self.normalized_percentile(0., 1.)
+    }
+
+    /// Like [`ImageBuilder::normalized`], but maps the `low` and `high` percentiles in `0..=1`
+    /// to `0` and `1` and clamps the rest, so a few outliers do not compress the result.
+    fn normalized_percentile(self, low: f32, high: f32) -> impl ImageBuilder {
+        let (min, max) = sample_range(&self, low, high);
+        let scale = if max > min { 1. / (max - min) } else { 0. };
+        self.map_rgb(move |_, c| ((c - min) * scale).clamp(Vec3::ZERO, Vec3::ONE))
+    }
+
     /// Map colors while maintaining the alpha value.
     fn map_rgb(self, f: impl Fn(Vec2, Vec3) -> Vec3) -> impl ImageBuilder {
         ColorMappedSampler {
@@ -66,6 +227,25 @@ pub trait ImageBuilder: Sized {
         }
     }
 
+    /// Extract a single channel of a color image as a grayscale image.
+    fn channel(self, channel: Channel) -> impl ImageBuilder {
+        ChannelSampler {
+            base: self,
+            channel,
+        }
+    }
+
+    /// Rearrange channels of a color image, e.g. `"grba"` swaps red and green.
+    ///
+    /// Accepts 4 characters of `rgba` or `xyzw`.
+    ///
+    /// # Panics
+    ///
+    /// If swizzle is invalid.
+    fn swizzle(self, swizzle: &str) -> impl ImageBuilder {
+        SwizzleSampler::new(self, swizzle)
+    }
+
     /// Turn a grayscale image into a white image with an alpha channel.
     fn alpha_white(self) -> impl ImageBuilder {
         ColorMappedSampler {
@@ -96,6 +276,64 @@ pub trait ImageBuilder: Sized {
         ScaledInput::new(self, scale)
     }
 
+    /// Rotates the image counter-clockwise around the origin by an angle in radians.
+    fn rotate(self, angle: f32) -> impl ImageBuilder {
+        TransformedInput::new(self, Affine2::from_angle(-angle))
+    }
+
+    /// Moves the image by an offset.
+    fn translate(self, offset: Vec2) -> impl ImageBuilder {
+        TransformedInput::new(self, Affine2::from_translation(-offset))
+    }
+
+    /// Flips the image horizontally in `0..1`.
+    fn mirror_x(self) -> impl ImageBuilder {
+        TransformedInput::new(
+            self,
+            Affine2::from_scale_angle_translation(Vec2::new(-1., 1.), 0., Vec2::new(1., 0.)),
+        )
+    }
+
+    /// Flips the image vertically in `0..1`.
+    fn mirror_y(self) -> impl ImageBuilder {
+        TransformedInput::new(
+            self,
+            Affine2::from_scale_angle_translation(Vec2::new(1., -1.), 0., Vec2::new(0., 1.)),
+        )
+    }
+
+    /// Multiplies the frequency by `stretch` along axes rotated by `angle` in radians,
+    /// e.g. `Vec2::new(0.1, 1.)` turns noise into streaks along `angle`.
+    fn anisotropic(self, stretch: Vec2, angle: f32) -> impl ImageBuilder {
+        TransformedInput::new(self, anisotropy(stretch, angle))
+    }
+
+    /// Maps the sampled position by an arbitrary affine transform.
+    fn transform_input(self, transform: Affine2) -> impl ImageBuilder {
+        TransformedInput::new(self, transform)
+    }
+
+    /// Sample in polar coordinates around the center,
+    /// the base is sampled at `(radius, angle)` both normalized to `0..1`.
+    ///
+    /// This turns linear builders into radial ones, e.g. horizontal gradients into radial
+    /// gradients and vertical stripes into sunbursts.
+    fn polar(self) -> impl ImageBuilder {
+        PolarInput::new(self)
+    }
+
+    /// Treat this builder as an equirectangular map, `x` being longitude and `y` latitude,
+    /// to generate cubemaps with [`DirectionalImageBuilder::to_cubemap`].
+    fn equirectangular(self) -> EquirectangularInput<Self> {
+        EquirectangularInput { base: self }
+    }
+
+    /// Inverse of [`ImageBuilder::polar`], treats the input as `(radius, angle)`
+    /// and samples the base around its center.
+    fn inverse_polar(self) -> impl ImageBuilder {
+        InversePolarInput::new(self)
+    }
+
     /// Distort the image with noises.
     fn distort(self, x: impl ImageBuilder, y: impl ImageBuilder) -> impl ImageBuilder {
         DistortionImage {
@@ -104,8 +342,52 @@ pub trait ImageBuilder: Sized {
         }
     }
 
+    /// Erase the type of the builder, see [`DynImageBuilder`].
+    fn boxed(self) -> Box<dyn DynImageBuilder>
+    where
+        Self: Send + Sync + 'static,
+    {
+        Box::new(self)
+    }
+
+    /// Sample into a pixel grid for filters like blur, sharpen, dilate and erode.
+    fn rasterize(&self, width: usize, height: usize) -> RasterImage {
+        RasterImage::new(self, width, height)
+    }
+
     /// Convert the builder to an image, with size.
     fn to_image(&self, width: usize, height: usize) -> Image {
+        self.to_image_dithered(width, height, Dither::None)
+    }
+
+    /// Like [`ImageBuilder::to_image`] but applies [`Dither`] when quantizing to 8 bits.
+    fn to_image_dithered(&self, width: usize, height: usize, dither: Dither) -> Image {
+        self.to_image_with_progress(width, height, dither, |_| ControlFlow::Continue(()))
+            .expect("Generation is never cancelled.")
+    }
+
+    /// Like [`ImageBuilder::to_image_dithered`] but reports progress in `0..=1` after each row,
+    /// returning [`ControlFlow::Break`] cancels generation and returns [`None`].
+    ///
+    /// ```rust
+    /// # use bevy_texture_gen::*;
+    /// # use std::{ops::ControlFlow, sync::atomic::{AtomicBool, Ordering}};
+    /// let cancelled = AtomicBool::new(false);
+    /// let image = VoronoiImage::new(5).to_image_with_progress(512, 512, Dither::None, |progress| {
+    ///     println!("{:.0}%", progress * 100.);
+    ///     match cancelled.load(Ordering::Relaxed) {
+    ///         true => ControlFlow::Break(()),
+    ///         false => ControlFlow::Continue(()),
+    ///     }
+    /// });
+    /// ```
+    fn to_image_with_progress(
+        &self,
+        width: usize,
+        height: usize,
+        dither: Dither,
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Option<Image> {
         let mut data = vec![0; width * height * 4];
         let w = (width - 1) as f32;
         let h = (height - 1) as f32;
@@ -113,11 +395,48 @@ pub trait ImageBuilder: Sized {
         for y in 0..height {
             for x in 0..width {
                 let v = self.sample_color(Vec2::new(x as f32 / w, y as f32 / h));
-                let v = (v * 255.).as_u8vec4();
-                data[p] = v.x;
-                data[p + 1] = v.y;
-                data[p + 2] = v.z;
-                data[p + 3] = v.w;
+                data[p..p + 4].copy_from_slice(&dither.quantize(v, x, y));
+                p += 4;
+            }
+            if progress((y + 1) as f32 / height as f32).is_break() {
+                return None;
+            }
+        }
+        Some(Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        ))
+    }
+
+    /// Like [`ImageBuilder::to_image`] but averages `samples_per_pixel` jittered samples
+    /// around each pixel, anti-aliasing hard edges like Voronoi borders and patterns.
+    ///
+    /// Sample offsets are deterministic so the result is stable across runs.
+    fn to_image_supersampled(
+        &self,
+        width: usize,
+        height: usize,
+        samples_per_pixel: usize,
+    ) -> Image {
+        let samples = samples_per_pixel.max(1);
+        let step = Vec2::new(1. / (width - 1) as f32, 1. / (height - 1) as f32);
+        let mut data = vec![0; width * height * 4];
+        let mut p = 0;
+        for y in 0..height {
+            for x in 0..width {
+                let center = Vec2::new(x as f32, y as f32) * step;
+                let v = (0..samples)
+                    .map(|i| self.sample_color(center + supersample_offset(x, y, i) * step))
+                    .sum::<Vec4>()
+                    / samples as f32;
+                data[p..p + 4].copy_from_slice(&Dither::None.quantize(v, x, y));
                 p += 4;
             }
         }
@@ -158,55 +477,78 @@ impl<A: ImageBuilder, B: ImageBuilder> ImageBuilder for ImageMultiply<A, B> {
     }
 }
 
-struct FunctionSampler<F: Fn(Vec2) -> Vec4>(F);
+/// Blends the color channels of two nodes, alpha is taken from the base.
+struct ImageBlend<A: ImageBuilder, B: ImageBuilder> {
+    base: A,
+    layer: B,
+    function: fn(f32, f32) -> f32,
+}
+
+impl<A: ImageBuilder, B: ImageBuilder> ImageBlend<A, B> {
+    fn new(base: A, layer: B, function: fn(f32, f32) -> f32) -> Self {
+        ImageBlend {
+            base,
+            layer,
+            function,
+        }
+    }
+}
 
-impl<F: Fn(Vec2) -> Vec4> ImageBuilder for FunctionSampler<F> {
+impl<A: ImageBuilder, B: ImageBuilder> ImageBuilder for ImageBlend<A, B> {
     fn sample(&self, position: Vec2) -> f32 {
-        (self.0)(position).x
+        (self.function)(self.base.sample(position), self.layer.sample(position))
     }
 
     fn sample_color(&self, position: Vec2) -> Vec4 {
-        (self.0)(position)
+        let a = self.base.sample_color(position);
+        let b = self.layer.sample_color(position);
+        let f = self.function;
+        Vec4::new(f(a.x, b.x), f(a.y, b.y), f(a.z, b.z), a.w)
     }
 }
 
-struct ColorMappedSampler<B: ImageBuilder, F: Fn(Vec2, Vec4) -> Vec4> {
-    base: B,
-    function: F,
+struct MaskedLerp<A: ImageBuilder, B: ImageBuilder, M: ImageBuilder> {
+    base: A,
+    layer: B,
+    mask: M,
 }
 
-impl<B: ImageBuilder, F: Fn(Vec2, Vec4) -> Vec4> ImageBuilder for ColorMappedSampler<B, F> {
+impl<A: ImageBuilder, B: ImageBuilder, M: ImageBuilder> ImageBuilder for MaskedLerp<A, B, M> {
     fn sample(&self, position: Vec2) -> f32 {
-        self.sample_color(position).x
+        let fac = self.mask.sample(position);
+        self.base.sample(position) * (1. - fac) + self.layer.sample(position) * fac
     }
 
     fn sample_color(&self, position: Vec2) -> Vec4 {
-        (self.function)(position, self.base.sample_color(position))
+        let fac = self.mask.sample(position);
+        self.base
+            .sample_color(position)
+            .lerp(self.layer.sample_color(position), fac)
     }
 }
 
-struct NoiseMappedSampler<B: ImageBuilder, F: Fn(Vec2, f32) -> f32> {
+struct ColorMappedSampler<B: ImageBuilder, F: Fn(Vec2, Vec4) -> Vec4> {
     base: B,
     function: F,
 }
 
-impl<B: ImageBuilder, F: Fn(Vec2, f32) -> f32> ImageBuilder for NoiseMappedSampler<B, F> {
+impl<B: ImageBuilder, F: Fn(Vec2, Vec4) -> Vec4> ImageBuilder for ColorMappedSampler<B, F> {
     fn sample(&self, position: Vec2) -> f32 {
-        (self.function)(position, self.base.sample(position))
+        self.sample_color(position).x
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        (self.function)(position, self.base.sample_color(position))
     }
 }
 
-struct SampleToColorSampler<B: ImageBuilder, F: Fn(Vec2, f32) -> Vec4> {
+struct NoiseMappedSampler<B: ImageBuilder, F: Fn(Vec2, f32) -> f32> {
     base: B,
     function: F,
 }
 
-impl<B: ImageBuilder, F: Fn(Vec2, f32) -> Vec4> ImageBuilder for SampleToColorSampler<B, F> {
+impl<B: ImageBuilder, F: Fn(Vec2, f32) -> f32> ImageBuilder for NoiseMappedSampler<B, F> {
     fn sample(&self, position: Vec2) -> f32 {
-        self.sample_color(position).x
-    }
-
-    fn sample_color(&self, position: Vec2) -> Vec4 {
         (self.function)(position, self.base.sample(position))
     }
 }
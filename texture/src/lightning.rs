@@ -0,0 +1,135 @@
+use bevy::math::{Rot2, Vec2};
+use fastrand::Rng;
+
+use crate::{ImageBuilder, TexGenSeed};
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    a: Vec2,
+    b: Vec2,
+    intensity: f32,
+}
+
+impl Segment {
+    fn distance(&self, position: Vec2) -> f32 {
+        let ab = self.b - self.a;
+        let t = ((position - self.a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0., 1.);
+        position.distance(self.a + ab * t)
+    }
+}
+
+/// Branching lightning bolts or cracks with a glow falloff,
+/// generated by midpoint displacement with random branches.
+///
+/// Segments are generated on construction, branches are dimmer than their parent.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// # use bevy::math::Vec2;
+/// let bolt = LightningImage::bolt(Vec2::new(0.5, 0.), Vec2::new(0.5, 1.));
+/// let shatter = LightningImage::cracks(Vec2::splat(0.5), 7).with_glow(0.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LightningImage {
+    segments: Vec<Segment>,
+    /// Half width of the solid core.
+    pub thickness: f32,
+    /// Falloff distance of the glow, `0` disables the glow.
+    pub glow: f32,
+}
+
+/// Number of midpoint subdivisions.
+const DETAIL: usize = 5;
+/// Chance of spawning a branch at each subdivision, only the main bolt branches.
+const BRANCH_CHANCE: f32 = 0.15;
+
+fn subdivide(rng: &mut Rng, a: Vec2, b: Vec2, intensity: f32, segments: &mut Vec<Segment>) {
+    let mut current = vec![Segment { a, b, intensity }];
+    let mut offset = a.distance(b) * 0.2;
+    for _ in 0..DETAIL {
+        let mut next = Vec::with_capacity(current.len() * 2);
+        for segment in current {
+            let dir = segment.b - segment.a;
+            let mid = (segment.a + segment.b) * 0.5
+                + dir.perp().normalize_or_zero() * offset * (rng.f32() * 2. - 1.);
+            next.push(Segment { b: mid, ..segment });
+            next.push(Segment { a: mid, ..segment });
+            if segment.intensity >= 1. && rng.f32() < BRANCH_CHANCE {
+                let angle = (rng.f32() * 2. - 1.) * 0.8;
+                let end = mid + Rot2::radians(angle) * (mid - segment.a) * 1.5;
+                subdivide(rng, mid, end, segment.intensity * 0.5, segments);
+            }
+        }
+        current = next;
+        offset *= 0.5;
+    }
+    segments.extend(current);
+}
+
+impl LightningImage {
+    fn new(thickness: f32) -> Self {
+        LightningImage {
+            segments: Vec::new(),
+            thickness,
+            glow: 0.05,
+        }
+    }
+
+    /// A bolt from `start` to `end`, seeded by [`TexGenSeed`].
+    pub fn bolt(start: Vec2, end: Vec2) -> Self {
+        Self::bolt_seeded(start, end, TexGenSeed::get())
+    }
+
+    pub fn bolt_seeded(start: Vec2, end: Vec2, seed: u32) -> Self {
+        let mut rng = Rng::with_seed(seed as u64);
+        let mut result = Self::new(0.004);
+        subdivide(&mut rng, start, end, 1., &mut result.segments);
+        result
+    }
+
+    /// `count` cracks radiating from `center` towards the edges, seeded by [`TexGenSeed`].
+    pub fn cracks(center: Vec2, count: usize) -> Self {
+        Self::cracks_seeded(center, count, TexGenSeed::get())
+    }
+
+    pub fn cracks_seeded(center: Vec2, count: usize, seed: u32) -> Self {
+        let mut rng = Rng::with_seed(seed as u64);
+        let mut result = Self::new(0.002);
+        for i in 0..count {
+            let angle = (i as f32 + rng.f32() * 0.6) / count as f32 * std::f32::consts::TAU;
+            let length = 0.35 + rng.f32() * 0.3;
+            let end = center + Vec2::from_angle(angle) * length;
+            subdivide(&mut rng, center, end, 1., &mut result.segments);
+        }
+        result
+    }
+
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn with_glow(mut self, glow: f32) -> Self {
+        self.glow = glow;
+        self
+    }
+}
+
+impl ImageBuilder for LightningImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.segments
+            .iter()
+            .map(|segment| {
+                let d = segment.distance(position);
+                let thickness = self.thickness * segment.intensity;
+                let core = if d <= thickness { 1. } else { 0. };
+                let glow = if self.glow > 0. {
+                    (-(d - thickness).max(0.) / self.glow).exp() * 0.6
+                } else {
+                    0.
+                };
+                f32::max(core, glow) * segment.intensity
+            })
+            .fold(0., f32::max)
+    }
+}
@@ -1,6 +1,8 @@
-use crate::ImageBuilder;
+use crate::{ImageBuilder, TexGenSeed};
 use bevy::math::Vec2;
-use noise::{Fbm, MultiFractal, NoiseFn, Perlin, Seedable, Simplex, SuperSimplex};
+use noise::{
+    Billow, Fbm, MultiFractal, NoiseFn, Perlin, RidgedMulti, Seedable, Simplex, SuperSimplex, Value,
+};
 
 /// Represents simple seeded noises like `Perlin` and `Simplex`.
 pub trait SimpleNoise: NoiseFn<f64, 2> + Seedable + Default {}
@@ -13,10 +15,11 @@ pub struct NoiseImage<T: NoiseFn<f64, 2>>(pub T);
 pub type PerlinImage = NoiseImage<Perlin>;
 pub type SimpleXImage = NoiseImage<Simplex>;
 pub type SuperSimpleXImage = NoiseImage<SuperSimplex>;
+pub type ValueNoiseImage = NoiseImage<Value>;
 
 impl<T: SimpleNoise> NoiseImage<T> {
     pub fn new() -> Self {
-        Self(T::default())
+        Self::new_seeded(TexGenSeed::get())
     }
 
     pub fn new_seeded(seed: u32) -> Self {
@@ -36,10 +39,11 @@ pub struct FbmNoiseImage<T: SimpleNoise>(pub Fbm<T>);
 pub type FbmPerlinImage = FbmNoiseImage<Perlin>;
 pub type FbmSimpleXImage = FbmNoiseImage<Simplex>;
 pub type FbmSuperSimpleXImage = FbmNoiseImage<SuperSimplex>;
+pub type FbmValueImage = FbmNoiseImage<Value>;
 
 impl<T: SimpleNoise> FbmNoiseImage<T> {
     pub fn new() -> Self {
-        FbmNoiseImage(Fbm::new(0).set_frequency(5.))
+        FbmNoiseImage(Fbm::new(TexGenSeed::get()).set_frequency(5.))
     }
 
     pub fn new_seeded(seed: u32) -> Self {
@@ -57,3 +61,59 @@ impl<T: SimpleNoise> ImageBuilder for FbmNoiseImage<T> {
         self.0.get(position.as_dvec2().to_array()) as f32 * 0.5 + 0.5
     }
 }
+
+/// Ridged multifractal noise, sharp creases suitable for fire, lightning and rocky textures.
+pub struct RidgedNoiseImage<T: SimpleNoise>(pub RidgedMulti<T>);
+
+pub type RidgedPerlinImage = RidgedNoiseImage<Perlin>;
+pub type RidgedSimpleXImage = RidgedNoiseImage<Simplex>;
+pub type RidgedSuperSimpleXImage = RidgedNoiseImage<SuperSimplex>;
+
+impl<T: SimpleNoise> RidgedNoiseImage<T> {
+    pub fn new() -> Self {
+        RidgedNoiseImage(RidgedMulti::new(TexGenSeed::get()).set_frequency(5.))
+    }
+
+    pub fn new_seeded(seed: u32) -> Self {
+        RidgedNoiseImage(RidgedMulti::new(seed).set_frequency(5.))
+    }
+
+    pub fn with_parameters(mut self, f: impl FnOnce(&mut RidgedMulti<T>)) -> Self {
+        f(&mut self.0);
+        self
+    }
+}
+
+impl<T: SimpleNoise> ImageBuilder for RidgedNoiseImage<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.0.get(position.as_dvec2().to_array()) as f32 * 0.5 + 0.5
+    }
+}
+
+/// Billow noise, puffy rounded shapes suitable for clouds and smoke.
+pub struct BillowNoiseImage<T: SimpleNoise>(pub Billow<T>);
+
+pub type BillowPerlinImage = BillowNoiseImage<Perlin>;
+pub type BillowSimpleXImage = BillowNoiseImage<Simplex>;
+pub type BillowSuperSimpleXImage = BillowNoiseImage<SuperSimplex>;
+
+impl<T: SimpleNoise> BillowNoiseImage<T> {
+    pub fn new() -> Self {
+        BillowNoiseImage(Billow::new(TexGenSeed::get()).set_frequency(5.))
+    }
+
+    pub fn new_seeded(seed: u32) -> Self {
+        BillowNoiseImage(Billow::new(seed).set_frequency(5.))
+    }
+
+    pub fn with_parameters(mut self, f: impl FnOnce(&mut Billow<T>)) -> Self {
+        f(&mut self.0);
+        self
+    }
+}
+
+impl<T: SimpleNoise> ImageBuilder for BillowNoiseImage<T> {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.0.get(position.as_dvec2().to_array()) as f32 * 0.5 + 0.5
+    }
+}
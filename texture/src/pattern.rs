@@ -0,0 +1,182 @@
+use bevy::math::Vec2;
+
+use crate::ImageBuilder;
+
+/// Alternating `0` and `1` squares.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheckerImage {
+    /// Number of cells in `0..1` on each axis.
+    pub cells: Vec2,
+}
+
+impl CheckerImage {
+    pub fn new(cells: f32) -> Self {
+        CheckerImage {
+            cells: Vec2::splat(cells),
+        }
+    }
+}
+
+impl ImageBuilder for CheckerImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let cell = (position * self.cells).floor();
+        (cell.x + cell.y).rem_euclid(2.)
+    }
+}
+
+/// Parallel stripes rotated by an angle.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StripesImage {
+    /// Number of stripes per unit perpendicular to the stripes.
+    pub frequency: f32,
+    /// Angle of the stripes in radians, `0` is vertical.
+    pub angle: f32,
+    /// Portion of each period that is `1`, in `0..1`.
+    pub width: f32,
+}
+
+impl StripesImage {
+    pub fn new(frequency: f32) -> Self {
+        StripesImage {
+            frequency,
+            angle: 0.,
+            width: 0.5,
+        }
+    }
+
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+impl ImageBuilder for StripesImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let t = (position.dot(Vec2::from_angle(self.angle)) * self.frequency).rem_euclid(1.);
+        if t < self.width { 1. } else { 0. }
+    }
+}
+
+/// Grid lines, `1` on lines and `0` elsewhere.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridImage {
+    /// Number of cells in `0..1` on each axis.
+    pub cells: Vec2,
+    /// Thickness of the lines, relative to a cell.
+    pub thickness: f32,
+}
+
+impl GridImage {
+    pub fn new(cells: f32, thickness: f32) -> Self {
+        GridImage {
+            cells: Vec2::splat(cells),
+            thickness,
+        }
+    }
+}
+
+impl ImageBuilder for GridImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let p = position * self.cells;
+        let dist = (p - p.round()).abs();
+        if dist.min_element() < self.thickness * 0.5 {
+            1.
+        } else {
+            0.
+        }
+    }
+}
+
+/// Polka dots centered in grid cells, `1` inside dots and `0` elsewhere.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DotsImage {
+    /// Number of cells in `0..1` on each axis.
+    pub cells: Vec2,
+    /// Radius of the dots, relative to a cell, in `0..0.5`.
+    pub radius: f32,
+    /// If true, offset every other row by half a cell.
+    pub staggered: bool,
+}
+
+impl DotsImage {
+    pub fn new(cells: f32, radius: f32) -> Self {
+        DotsImage {
+            cells: Vec2::splat(cells),
+            radius,
+            staggered: false,
+        }
+    }
+
+    pub fn staggered(mut self) -> Self {
+        self.staggered = true;
+        self
+    }
+}
+
+impl ImageBuilder for DotsImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let mut p = position * self.cells;
+        if self.staggered && p.y.floor().rem_euclid(2.) == 1. {
+            p.x += 0.5;
+        }
+        let local = p - p.floor() - Vec2::splat(0.5);
+        if local.length() < self.radius { 1. } else { 0. }
+    }
+}
+
+/// Hexagonal tiling, `1` on cell borders and `0` elsewhere.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexImage {
+    /// Number of cells in `0..1` horizontally.
+    pub frequency: f32,
+    /// Thickness of the borders, relative to a cell.
+    pub thickness: f32,
+}
+
+impl HexImage {
+    pub fn new(frequency: f32, thickness: f32) -> Self {
+        HexImage {
+            frequency,
+            thickness,
+        }
+    }
+
+    /// Returns the distance to the nearest border, `0.5` at the center of a cell.
+    pub fn edge_distance(&self, position: Vec2) -> f32 {
+        const SQRT_3: f32 = 1.732_050_8;
+        let p = position * self.frequency;
+        let period = Vec2::new(1., SQRT_3);
+        let half = period * 0.5;
+        let a = p.rem_euclid(period) - half;
+        let b = (p - half).rem_euclid(period) - half;
+        let local = if a.length_squared() < b.length_squared() {
+            a
+        } else {
+            b
+        }
+        .abs();
+        // Equivalent to `normalize(1, sqrt(3))`.
+        let diagonal = Vec2::new(0.5, SQRT_3 * 0.5);
+        0.5 - local.dot(diagonal).max(local.x)
+    }
+}
+
+impl ImageBuilder for HexImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        if self.edge_distance(position) < self.thickness * 0.5 {
+            1.
+        } else {
+            0.
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{Image, ImageAddressMode},
+    math::{Vec2, Vec4},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{Dither, ImageBuilder, ImageSampler};
+
+/// A rasterized pixel grid for filters that need neighboring pixels,
+/// obtained from [`ImageBuilder::rasterize`].
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// let glow = DotsImage::new(4., 0.2)
+///     .rasterize(256, 256)
+///     .dilate(2)
+///     .blur(4.)
+///     .to_image();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RasterImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec4>,
+    /// How filters read pixels outside of the grid, [`ImageAddressMode::Repeat`] for tiling textures.
+    pub address_mode: ImageAddressMode,
+}
+
+impl RasterImage {
+    /// Sample a builder at the same positions as [`ImageBuilder::to_image`].
+    pub fn new(builder: &impl ImageBuilder, width: usize, height: usize) -> Self {
+        let w = (width - 1) as f32;
+        let h = (height - 1) as f32;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(builder.sample_color(Vec2::new(x as f32 / w, y as f32 / h)));
+            }
+        }
+        RasterImage {
+            width,
+            height,
+            pixels,
+            address_mode: ImageAddressMode::ClampToEdge,
+        }
+    }
+
+    pub fn with_address_mode(mut self, address_mode: ImageAddressMode) -> Self {
+        self.address_mode = address_mode;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Obtain a pixel by integer coordinates, applying the address mode.
+    pub fn pixel(&self, x: i64, y: i64) -> Vec4 {
+        let Some(x) = ImageSampler::address(self.address_mode, x, self.width) else {
+            return Vec4::ZERO;
+        };
+        let Some(y) = ImageSampler::address(self.address_mode, y, self.height) else {
+            return Vec4::ZERO;
+        };
+        self.pixels[y * self.width + x]
+    }
+
+    fn map_pixels(&self, f: impl Fn(i64, i64) -> Vec4) -> Self {
+        let mut pixels = Vec::with_capacity(self.pixels.len());
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                pixels.push(f(x, y));
+            }
+        }
+        RasterImage { pixels, ..*self }
+    }
+
+    /// Separable gaussian blur with standard deviation `sigma` in pixels.
+    pub fn blur(self, sigma: f32) -> Self {
+        if sigma <= 0. {
+            return self;
+        }
+        let radius = (sigma * 3.).ceil() as i64;
+        let mut kernel: Vec<f32> = (-radius..=radius)
+            .map(|i| (-(i * i) as f32 / (2. * sigma * sigma)).exp())
+            .collect();
+        let sum: f32 = kernel.iter().sum();
+        kernel.iter_mut().for_each(|k| *k /= sum);
+        let horizontal = self.map_pixels(|x, y| {
+            (-radius..=radius)
+                .zip(&kernel)
+                .map(|(i, k)| self.pixel(x + i, y) * *k)
+                .sum()
+        });
+        horizontal.map_pixels(|x, y| {
+            (-radius..=radius)
+                .zip(&kernel)
+                .map(|(i, k)| horizontal.pixel(x, y + i) * *k)
+                .sum()
+        })
+    }
+
+    /// Unsharp mask, adds `amount` times the difference from a blurred copy.
+    pub fn sharpen(self, amount: f32) -> Self {
+        let blurred = self.clone().blur(1.);
+        self.map_pixels(|x, y| {
+            let p = self.pixel(x, y);
+            p + (p - blurred.pixel(x, y)) * amount
+        })
+    }
+
+    /// Per channel maximum in a disk of `radius` pixels, grows bright shapes.
+    pub fn dilate(self, radius: usize) -> Self {
+        self.morphology(radius, Vec4::max)
+    }
+
+    /// Per channel minimum in a disk of `radius` pixels, shrinks bright shapes.
+    pub fn erode(self, radius: usize) -> Self {
+        self.morphology(radius, Vec4::min)
+    }
+
+    fn morphology(self, radius: usize, f: fn(Vec4, Vec4) -> Vec4) -> Self {
+        let r = radius as i64;
+        self.map_pixels(|x, y| {
+            let mut result = self.pixel(x, y);
+            for j in -r..=r {
+                for i in -r..=r {
+                    if i * i + j * j <= r * r {
+                        result = f(result, self.pixel(x + i, y + j));
+                    }
+                }
+            }
+            result
+        })
+    }
+
+    /// Convert the red channel thresholded at `threshold` into a signed distance field.
+    ///
+    /// The output is `0.5` on the edge, above `0.5` inside and below `0.5` outside,
+    /// reaching `1` and `0` at `spread` pixels from the edge. Alpha is `1`.
+    pub fn distance_field(self, threshold: f32, spread: f32) -> Self {
+        let inside: Vec<bool> = self.pixels.iter().map(|p| p.x >= threshold).collect();
+        let outside_distance = self.distance_transform(|i| inside[i]);
+        let inside_distance = self.distance_transform(|i| !inside[i]);
+        let spread = spread.max(f32::EPSILON);
+        let pixels = outside_distance
+            .iter()
+            .zip(&inside_distance)
+            .map(|(outside, inside)| {
+                // Distances are to the nearest opposite pixel center, offset to the edge between them.
+                let d = if *inside > 0. {
+                    inside - 0.5
+                } else {
+                    -(outside - 0.5)
+                };
+                let v = (0.5 + d / (2. * spread)).clamp(0., 1.);
+                Vec4::new(v, v, v, 1.)
+            })
+            .collect();
+        RasterImage { pixels, ..self }
+    }
+
+    /// Euclidean distance in pixels to the nearest pixel where `feature` is true.
+    fn distance_transform(&self, feature: impl Fn(usize) -> bool) -> Vec<f32> {
+        let (w, h) = (self.width, self.height);
+        let mut grid: Vec<f32> = (0..w * h)
+            .map(|i| if feature(i) { 0. } else { f32::INFINITY })
+            .collect();
+        let mut buffer = vec![0.; w.max(h)];
+        for x in 0..w {
+            let column: Vec<f32> = (0..h).map(|y| grid[y * w + x]).collect();
+            distance_transform_1d(&column, &mut buffer[..h]);
+            for y in 0..h {
+                grid[y * w + x] = buffer[y];
+            }
+        }
+        for y in 0..h {
+            let row = grid[y * w..(y + 1) * w].to_vec();
+            distance_transform_1d(&row, &mut buffer[..w]);
+            grid[y * w..(y + 1) * w].copy_from_slice(&buffer[..w]);
+        }
+        grid.into_iter().map(f32::sqrt).collect()
+    }
+
+    /// Convert back into an [`ImageBuilder`] for further point-sampled operations.
+    pub fn into_sampler(self) -> ImageSampler {
+        ImageSampler::from_pixels(self.width, self.height, self.pixels, self.address_mode)
+    }
+
+    /// Convert to an `Rgba8Unorm` [`Image`] of the same size.
+    pub fn to_image(&self) -> Image {
+        self.to_image_dithered(Dither::None)
+    }
+
+    /// Like [`RasterImage::to_image`] but applies [`Dither`] when quantizing to 8 bits.
+    pub fn to_image_dithered(&self, dither: Dither) -> Image {
+        let data = self
+            .pixels
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| dither.quantize(*p, i % self.width, i / self.width))
+            .collect();
+        let mut image = Image::new(
+            Extent3d {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::all(),
+        );
+        if !matches!(self.address_mode, ImageAddressMode::ClampToEdge) {
+            let descriptor = image.sampler.get_or_init_descriptor();
+            descriptor.address_mode_u = self.address_mode;
+            descriptor.address_mode_v = self.address_mode;
+        }
+        image
+    }
+}
+
+/// Squared distance transform of a sampled function (Felzenszwalb and Huttenlocher).
+fn distance_transform_1d(f: &[f32], output: &mut [f32]) {
+    let n = f.len();
+    let mut v = vec![0; n];
+    let mut z = vec![0.; n + 1];
+    let mut k = 0;
+    // Skip leading infinite samples so parabola intersections stay finite.
+    let Some(first) = f.iter().position(|x| x.is_finite()) else {
+        output.fill(f32::INFINITY);
+        return;
+    };
+    v[0] = first;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in first + 1..n {
+        if !f[q].is_finite() {
+            continue;
+        }
+        loop {
+            let p = v[k];
+            let s = ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32;
+            if s <= z[k] && k > 0 {
+                k -= 1;
+            } else {
+                k += 1;
+                v[k] = q;
+                z[k] = s;
+                z[k + 1] = f32::INFINITY;
+                break;
+            }
+        }
+    }
+    k = 0;
+    for (q, out) in output.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let d = q as f32 - v[k] as f32;
+        *out = d * d + f[v[k]];
+    }
+}
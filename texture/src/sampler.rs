@@ -0,0 +1,163 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{Image, ImageAddressMode, ImageFilterMode, ImageSampler as BevyImageSampler},
+    math::{Vec2, Vec4},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{ImageBuilder, util::color_to_raw};
+
+/// Samples an existing [`Image`] as an [`ImageBuilder`],
+/// so hand-painted textures can be combined with generated ones.
+///
+/// Pixels are decoded on construction, color values are stored as is
+/// without color space conversion.
+#[derive(Debug, Clone)]
+pub struct ImageSampler {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec4>,
+    pub address_mode_u: ImageAddressMode,
+    pub address_mode_v: ImageAddressMode,
+    /// [`ImageFilterMode::Linear`] for bilinear sampling.
+    pub filter: ImageFilterMode,
+}
+
+impl ImageSampler {
+    /// Decode a 2d [`Image`], address modes are copied from the image's sampler descriptor if specified.
+    ///
+    /// Returns [`None`] if the image has no data or has an unsupported format.
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let width = image.width() as usize;
+        let height = image.height() as usize;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let color = image.get_color_at(x as u32, y as u32).ok()?;
+                pixels.push(color_to_raw(color));
+            }
+        }
+        let (address_mode_u, address_mode_v) = match &image.sampler {
+            BevyImageSampler::Descriptor(d) => (d.address_mode_u, d.address_mode_v),
+            BevyImageSampler::Default => {
+                (ImageAddressMode::ClampToEdge, ImageAddressMode::ClampToEdge)
+            }
+        };
+        Some(ImageSampler {
+            width,
+            height,
+            pixels,
+            address_mode_u,
+            address_mode_v,
+            filter: ImageFilterMode::Linear,
+        })
+    }
+
+    /// Decode raw texture data.
+    ///
+    /// Returns [`None`] if the format is unsupported.
+    ///
+    /// # Panics
+    ///
+    /// If length of data does not match the size and format.
+    pub fn from_bytes(width: u32, height: u32, data: &[u8], format: TextureFormat) -> Option<Self> {
+        Self::from_image(&Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data.to_vec(),
+            format,
+            RenderAssetUsages::all(),
+        ))
+    }
+
+    pub(crate) fn from_pixels(
+        width: usize,
+        height: usize,
+        pixels: Vec<Vec4>,
+        address_mode: ImageAddressMode,
+    ) -> Self {
+        ImageSampler {
+            width,
+            height,
+            pixels,
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            filter: ImageFilterMode::Linear,
+        }
+    }
+
+    pub fn with_address_mode(mut self, u: ImageAddressMode, v: ImageAddressMode) -> Self {
+        self.address_mode_u = u;
+        self.address_mode_v = v;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: ImageFilterMode) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub(crate) fn address(mode: ImageAddressMode, x: i64, size: usize) -> Option<usize> {
+        let size = size as i64;
+        let x = match mode {
+            ImageAddressMode::ClampToEdge => x.clamp(0, size - 1),
+            ImageAddressMode::Repeat => x.rem_euclid(size),
+            ImageAddressMode::MirrorRepeat => {
+                let x = x.rem_euclid(size * 2);
+                if x >= size { size * 2 - 1 - x } else { x }
+            }
+            ImageAddressMode::ClampToBorder => {
+                if x < 0 || x >= size {
+                    return None;
+                }
+                x
+            }
+        };
+        Some(x as usize)
+    }
+
+    /// Obtain a pixel by integer coordinates, applying address modes.
+    ///
+    /// Pixels outside of [`ImageAddressMode::ClampToBorder`] are transparent black.
+    pub fn texel(&self, x: i64, y: i64) -> Vec4 {
+        let Some(x) = Self::address(self.address_mode_u, x, self.width) else {
+            return Vec4::ZERO;
+        };
+        let Some(y) = Self::address(self.address_mode_v, y, self.height) else {
+            return Vec4::ZERO;
+        };
+        self.pixels[y * self.width + x]
+    }
+}
+
+impl ImageBuilder for ImageSampler {
+    fn sample(&self, position: Vec2) -> f32 {
+        self.sample_color(position).x
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        if self.pixels.is_empty() {
+            return Vec4::ZERO;
+        }
+        let p = position * Vec2::new(self.width as f32, self.height as f32);
+        match self.filter {
+            ImageFilterMode::Nearest => {
+                let p = p.floor();
+                self.texel(p.x as i64, p.y as i64)
+            }
+            ImageFilterMode::Linear => {
+                let p = p - Vec2::splat(0.5);
+                let base = p.floor();
+                let t = p - base;
+                let (x, y) = (base.x as i64, base.y as i64);
+                let top = self.texel(x, y).lerp(self.texel(x + 1, y), t.x);
+                let bottom = self.texel(x, y + 1).lerp(self.texel(x + 1, y + 1), t.x);
+                top.lerp(bottom, t.y)
+            }
+        }
+    }
+}
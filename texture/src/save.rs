@@ -0,0 +1,293 @@
+use std::{io, path::Path};
+
+use bevy::{image::Image, math::Vec4};
+
+#[cfg(feature = "png")]
+use bevy::math::Vec2;
+#[cfg(feature = "png")]
+use bevy::{
+    asset::RenderAssetUsages,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+#[cfg(feature = "png")]
+use std::{
+    fs::File,
+    hash::{Hash, Hasher},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+#[cfg(feature = "png")]
+use crate::ImageBuilder;
+use crate::util::color_to_raw;
+
+/// Extension for saving [`Image`]s to disk, useful for inspecting or baking generated textures.
+pub trait SaveImageExt {
+    /// Save as an 8-bit RGBA png.
+    #[cfg(feature = "png")]
+    fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()>;
+
+    /// Save as a 32-bit float RGBA exr.
+    #[cfg(feature = "exr")]
+    fn save_exr(&self, path: impl AsRef<Path>) -> io::Result<()>;
+}
+
+fn decode_pixels(image: &Image) -> io::Result<Vec<Vec4>> {
+    let mut pixels = Vec::with_capacity((image.width() * image.height()) as usize);
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            let color = image.get_color_at(x, y).map_err(io::Error::other)?;
+            pixels.push(color_to_raw(color));
+        }
+    }
+    Ok(pixels)
+}
+
+impl SaveImageExt for Image {
+    #[cfg(feature = "png")]
+    fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = match (&self.data, self.texture_descriptor.format) {
+            (Some(data), TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb) => data.clone(),
+            _ => decode_pixels(self)?
+                .into_iter()
+                .flat_map(|x| {
+                    (x.clamp(Vec4::ZERO, Vec4::ONE) * 255.)
+                        .round()
+                        .as_u8vec4()
+                        .to_array()
+                })
+                .collect(),
+        };
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            self.width(),
+            self.height(),
+        );
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&data).map_err(io::Error::other)
+    }
+
+    #[cfg(feature = "exr")]
+    fn save_exr(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let width = self.width() as usize;
+        let pixels = decode_pixels(self)?;
+        exr::prelude::write_rgba_file(path, width, self.height() as usize, |x, y| {
+            let p = pixels[y * width + x];
+            (p.x, p.y, p.z, p.w)
+        })
+        .map_err(io::Error::other)
+    }
+}
+
+#[cfg(feature = "png")]
+fn decoding_error(error: png::DecodingError) -> io::Error {
+    match error {
+        png::DecodingError::IoError(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+/// Load an 8-bit RGBA png as [`TextureFormat::Rgba8Unorm`].
+///
+/// Malformed files return [`io::ErrorKind::InvalidData`].
+#[cfg(feature = "png")]
+pub fn load_png(path: impl AsRef<Path>) -> io::Result<Image> {
+    let decoder = png::Decoder::new(File::open(path)?);
+    let mut reader = decoder.read_info().map_err(decoding_error)?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).map_err(decoding_error)?;
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Expected an 8-bit RGBA png.",
+        ));
+    }
+    data.truncate(info.buffer_size());
+    Ok(Image::new(
+        Extent3d {
+            width: info.width,
+            height: info.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::all(),
+    ))
+}
+
+/// A disk cache of generated images, so expensive textures are only generated once.
+///
+/// Images are stored as pngs named by a hash of a caller supplied key, size and format.
+/// The key should identify the builder and its parameters, like a name and version,
+/// or the builder itself if it implements [`Hash`].
+#[cfg(feature = "png")]
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    pub directory: PathBuf,
+    /// If true, also fingerprint the builder by sampling it on a fixed grid of points,
+    /// so changes to a builder invalidate its cache file even if the key is unchanged.
+    ///
+    /// Builders that only differ between those points are not told apart.
+    pub probe: bool,
+}
+
+#[cfg(feature = "png")]
+impl ImageCache {
+    /// Number of samples per axis used to fingerprint a builder.
+    const PROBES: usize = 16;
+
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        ImageCache {
+            directory: directory.into(),
+            probe: false,
+        }
+    }
+
+    /// Also fingerprint builders by sampling them, see [`ImageCache::probe`].
+    pub fn with_probe(mut self) -> Self {
+        self.probe = true;
+        self
+    }
+
+    /// Obtain the cache file of a builder generated at a size.
+    pub fn path_of(
+        &self,
+        key: &impl Hash,
+        builder: &impl ImageBuilder,
+        width: usize,
+        height: usize,
+    ) -> PathBuf {
+        let mut hasher = StableHasher::default();
+        hasher.write(b"rgba8.png");
+        hasher.write(&(width as u64).to_le_bytes());
+        hasher.write(&(height as u64).to_le_bytes());
+        key.hash(&mut hasher);
+        if self.probe {
+            for y in 0..Self::PROBES {
+                for x in 0..Self::PROBES {
+                    let position = (Vec2::new(x as f32, y as f32) + Vec2::new(0.37, 0.61))
+                        / Self::PROBES as f32;
+                    for value in builder.sample_color(position).to_array() {
+                        hasher.write(&value.to_bits().to_le_bytes());
+                    }
+                }
+            }
+        }
+        self.directory.join(format!("{:016x}.png", hasher.finish()))
+    }
+
+    /// Load an image from disk if cached, otherwise generate and save it.
+    ///
+    /// Malformed cache files are regenerated and overwritten.
+    pub fn get_or_generate(
+        &self,
+        key: &impl Hash,
+        builder: &impl ImageBuilder,
+        width: usize,
+        height: usize,
+    ) -> io::Result<Image> {
+        let path = self.path_of(key, builder, width, height);
+        match load_png(&path) {
+            Ok(image) => return Ok(image),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => (),
+            Err(e) => return Err(e),
+        }
+        let image = builder.to_image(width, height);
+        std::fs::create_dir_all(&self.directory)?;
+        image.save_png(&path)?;
+        Ok(image)
+    }
+}
+
+/// FNV-1a, unlike `DefaultHasher` this is stable across rust versions.
+#[cfg(feature = "png")]
+#[derive(Debug)]
+struct StableHasher(u64);
+
+#[cfg(feature = "png")]
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf29ce484222325)
+    }
+}
+
+#[cfg(feature = "png")]
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "png"))]
+mod test {
+    use std::path::PathBuf;
+
+    use bevy::math::{Vec2, Vec4};
+
+    use crate::{ImageBuilder, ImageCache, SaveImageExt};
+
+    struct Ramp(f32);
+
+    impl ImageBuilder for Ramp {
+        fn sample(&self, position: Vec2) -> f32 {
+            position.x * self.0
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("texgen-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn cache_round_trip() {
+        let dir = temp_dir("cache");
+        let cache = ImageCache::new(&dir);
+        let image = cache.get_or_generate(&"ramp", &Ramp(1.), 16, 8).unwrap();
+        let path = cache.path_of(&"ramp", &Ramp(1.), 16, 8);
+        assert!(path.exists());
+
+        assert_ne!(path, cache.path_of(&"half ramp", &Ramp(0.5), 16, 8));
+        assert_ne!(path, cache.path_of(&"ramp", &Ramp(1.), 8, 16));
+
+        // Loaded from disk instead of regenerated.
+        let red = Ramp(0.).map_color(|_, _| Vec4::new(1., 0., 0., 1.));
+        red.to_image(16, 8).save_png(&path).unwrap();
+        let cached = cache.get_or_generate(&"ramp", &Ramp(1.), 16, 8).unwrap();
+        assert_eq!(cached.data, red.to_image(16, 8).data);
+
+        // Malformed files are regenerated.
+        std::fs::write(&path, b"not a png").unwrap();
+        let regenerated = cache.get_or_generate(&"ramp", &Ramp(1.), 16, 8).unwrap();
+        assert_eq!(regenerated.data, image.data);
+        assert_eq!(crate::load_png(&path).unwrap().data, image.data);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn probe() {
+        let cache = ImageCache::new(temp_dir("probe"));
+        assert_eq!(
+            cache.path_of(&"ramp", &Ramp(1.), 16, 8),
+            cache.path_of(&"ramp", &Ramp(0.5), 16, 8)
+        );
+        let cache = cache.with_probe();
+        assert_ne!(
+            cache.path_of(&"ramp", &Ramp(1.), 16, 8),
+            cache.path_of(&"ramp", &Ramp(0.5), 16, 8)
+        );
+    }
+}
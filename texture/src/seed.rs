@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::util::hash_u32;
+
+static GLOBAL_SEED: AtomicU32 = AtomicU32::new(0);
+
+/// The global seed used by the `new()` constructors of seeded builders.
+///
+/// Changing the seed re-rolls every texture created with `new()` afterwards,
+/// set it before loading [`LazyImage`](crate::LazyImage)s. Constructors with an
+/// explicit seed like `new_seeded` are not affected.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// TexGenSeed::set(42);
+/// let a = VoronoiImage::new(5);
+/// let b = VoronoiImage::new_seeded(5, TexGenSeed::derive(1));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TexGenSeed;
+
+impl TexGenSeed {
+    /// Set the global seed, defaults to `0`.
+    pub fn set(seed: u32) {
+        GLOBAL_SEED.store(seed, Ordering::Relaxed);
+    }
+
+    /// Obtain the global seed.
+    pub fn get() -> u32 {
+        GLOBAL_SEED.load(Ordering::Relaxed)
+    }
+
+    /// Derive a distinct seed from the global seed,
+    /// for builders in the same set that should not be correlated.
+    pub fn derive(index: u32) -> u32 {
+        hash_u32(Self::get() ^ hash_u32(index))
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::{
+    app::{App, Plugin},
+    asset::{Handle, load_internal_asset, weak_handle},
+    render::render_resource::Shader,
+};
+
+/// Handle of the `bevy_texture_gen` shader import.
+pub const TEXTURE_GEN_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("5d1c0f3e-7b0a-4c57-9a4e-2f8e6b3a91c4");
+
+/// Registers WGSL helpers for the textures this crate generates, import with
+///
+/// ```wgsl
+/// #import bevy_texture_gen::{dissolve_alpha, dissolve_edge, decode_flow, flow_uv, ramp_lookup, sdf_alpha, sdf_outline}
+/// ```
+///
+/// * `dissolve_alpha` and `dissolve_edge` dissolve by a mask, like [`BlueNoiseImage`](crate::BlueNoiseImage).
+/// * `decode_flow` and `flow_uv` distort by a flow map from [`VectorFieldBuilder::to_flow_map`](crate::VectorFieldBuilder::to_flow_map).
+/// * `ramp_lookup` samples a horizontal color ramp.
+/// * `sdf_alpha` and `sdf_outline` read a [`RasterImage::distance_field`](crate::RasterImage::distance_field).
+pub struct TextureGenShadersPlugin;
+
+impl Plugin for TextureGenShadersPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TEXTURE_GEN_SHADER_HANDLE,
+            "shaders/texture_gen.wgsl",
+            Shader::from_wgsl
+        );
+    }
+}
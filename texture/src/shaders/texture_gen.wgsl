@@ -0,0 +1,55 @@
+#define_import_path bevy_texture_gen
+
+// Alpha of a dissolve by `mask`, `progress` goes from `0` (solid) to `1` (gone).
+//
+// `softness` blends the cutoff over a range of mask values, `0` is a hard cutoff.
+fn dissolve_alpha(mask: f32, progress: f32, softness: f32) -> f32 {
+    let cutoff = progress * (1.0 + softness);
+    return smoothstep(cutoff - softness, cutoff, mask);
+}
+
+// Glow factor in `0..1` on the band of `width` mask values just above the dissolve cutoff.
+fn dissolve_edge(mask: f32, progress: f32, width: f32) -> f32 {
+    let cutoff = progress * (1.0 + width);
+    let d = mask - cutoff;
+    return select(0.0, 1.0 - d / max(width, 0.0001), d >= 0.0 && d < width);
+}
+
+// Decode a flow map texel generated by `to_flow_map`, `(0.5, 0.5)` is no flow.
+fn decode_flow(encoded: vec4<f32>) -> vec2<f32> {
+    return encoded.xy * 2.0 - 1.0;
+}
+
+// Two phase flow map distortion, sample the texture at `uv0` and `uv1`
+// and blend the results with `mix(a, b, weight)` to hide the reset of each phase.
+struct FlowUv {
+    uv0: vec2<f32>,
+    uv1: vec2<f32>,
+    weight: f32,
+}
+
+fn flow_uv(uv: vec2<f32>, flow: vec2<f32>, time: f32, strength: f32) -> FlowUv {
+    let phase0 = fract(time);
+    let phase1 = fract(time + 0.5);
+    var result: FlowUv;
+    result.uv0 = uv - flow * phase0 * strength;
+    result.uv1 = uv - flow * phase1 * strength;
+    result.weight = abs(1.0 - 2.0 * phase0);
+    return result;
+}
+
+// Sample a horizontal color ramp at `t` in `0..1`.
+fn ramp_lookup(ramp: texture_2d<f32>, ramp_sampler: sampler, t: f32) -> vec4<f32> {
+    return textureSample(ramp, ramp_sampler, vec2(clamp(t, 0.0, 1.0), 0.5));
+}
+
+// Antialiased coverage of a signed distance field generated by `distance_field`,
+// `smoothing` is the blend range around the edge in encoded units.
+fn sdf_alpha(distance: f32, smoothing: f32) -> f32 {
+    return smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+}
+
+// Coverage of an outline of `width` encoded units outside the edge of a signed distance field.
+fn sdf_outline(distance: f32, width: f32, smoothing: f32) -> f32 {
+    return sdf_alpha(distance + width, smoothing) - sdf_alpha(distance, smoothing);
+}
@@ -0,0 +1,89 @@
+use bevy::math::{IVec2, Vec2};
+
+use crate::{
+    ImageBuilder, TexGenSeed,
+    util::{hash_to_unit, hash2},
+};
+
+/// Scattered bright points with glows, for skybox stars and glitter sprites.
+///
+/// Stars are placed in a jittered grid so the density stays even at any resolution.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// let sky = StarFieldImage::new(400.).to_image(256, 256);
+/// let glitter = StarFieldImage::new(60.)
+///     .with_size_range(0.004, 0.012)
+///     .with_falloff(1.5)
+///     .to_image(64, 64);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StarFieldImage {
+    pub seed: u32,
+    /// Expected number of stars in `0..1`.
+    pub density: f32,
+    /// Minimum and maximum radius of the solid core.
+    pub size_range: (f32, f32),
+    /// Exponent of the glow around the core, higher values produce tighter glows.
+    pub falloff: f32,
+}
+
+impl StarFieldImage {
+    pub fn new(density: f32) -> Self {
+        Self::new_seeded(density, TexGenSeed::get())
+    }
+
+    pub fn new_seeded(density: f32, seed: u32) -> Self {
+        StarFieldImage {
+            seed,
+            density,
+            size_range: (0.002, 0.006),
+            falloff: 2.,
+        }
+    }
+
+    pub fn with_size_range(mut self, min: f32, max: f32) -> Self {
+        self.size_range = (min, max);
+        self
+    }
+
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Number of grid cells on each axis.
+    fn cells(&self) -> i32 {
+        self.density.max(1.).sqrt().ceil() as i32
+    }
+}
+
+impl ImageBuilder for StarFieldImage {
+    fn sample(&self, position: Vec2) -> f32 {
+        let cells = self.cells();
+        let chance = self.density / (cells * cells) as f32;
+        let cell_size = 1. / cells as f32;
+        let cell = (position * cells as f32).floor().as_ivec2();
+        let (min, max) = self.size_range;
+        let mut result: f32 = 0.;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let c = cell + IVec2::new(x, y);
+                let h = |i: u32| hash_to_unit(hash2(c.x, c.y, self.seed.wrapping_add(i)));
+                if h(0) >= chance {
+                    continue;
+                }
+                let center = (c.as_vec2() + Vec2::new(h(1), h(2))) * cell_size;
+                let radius = min + (max - min) * h(3);
+                let brightness = 0.4 + 0.6 * h(4);
+                let d = position.distance(center);
+                // Fades the glow out before it reaches cells that are not sampled.
+                let window = (1. - d / cell_size).max(0.).powi(2);
+                let glow = (radius / d.max(f32::EPSILON)).min(1.).powf(self.falloff);
+                result = result.max(glow * window * brightness);
+            }
+        }
+        result
+    }
+}
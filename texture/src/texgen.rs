@@ -0,0 +1,116 @@
+use bevy::{
+    app::{App, Plugin},
+    asset::{AssetApp, AssetLoader, LoadContext, io::Reader},
+    image::Image,
+};
+
+use crate::{TexGenDescriptor, TexGenError};
+
+impl From<ron::error::SpannedError> for TexGenError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        TexGenError::Ron(value)
+    }
+}
+
+/// [`AssetLoader`] for `.texgen.ron` files, loads a [`TexGenDescriptor`] as an [`Image`].
+///
+/// Generation runs on the asset task pool and supports hot reloading.
+#[derive(Debug, Default)]
+pub struct TexGenLoader;
+
+impl AssetLoader for TexGenLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = TexGenError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        _: &mut LoadContext<'_>,
+    ) -> Result<Image, TexGenError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let descriptor: TexGenDescriptor = ron::de::from_bytes(&bytes)?;
+        descriptor.generate()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["texgen.ron"]
+    }
+}
+
+/// Registers [`TexGenLoader`].
+pub struct TexGenAssetPlugin;
+
+impl Plugin for TexGenAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset_loader::<TexGenLoader>();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        app::App,
+        asset::{AssetApp, AssetPlugin, AssetServer, Assets, Handle, LoadState},
+        image::Image,
+        prelude::MinimalPlugins,
+        render::render_resource::TextureFormat,
+    };
+
+    use super::TexGenAssetPlugin;
+
+    /// Load `source` as a `.texgen.ron` file and wait until it is loaded or failed.
+    fn load(name: &str, source: &str) -> (App, Handle<Image>) {
+        let dir = std::env::temp_dir().join(format!("texgen-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("test.texgen.ron"), source).unwrap();
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: dir.to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+            TexGenAssetPlugin,
+        ));
+        app.init_asset::<Image>();
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("test.texgen.ron");
+        for _ in 0..1000 {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(&handle) {
+                LoadState::Loading | LoadState::NotLoaded => std::thread::yield_now(),
+                _ => break,
+            }
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        (app, handle)
+    }
+
+    #[test]
+    fn load_texgen() {
+        let (app, handle) = load(
+            "load",
+            "(width: 8, height: 4, format: Rgba8UnormSrgb, builder: Color((1.0, 0.0, 0.0, 1.0)))",
+        );
+        let images = app.world().resource::<Assets<Image>>();
+        let image = images.get(&handle).unwrap();
+        assert_eq!((image.width(), image.height()), (8, 4));
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(image.data.as_ref().unwrap()[..4], [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn load_malformed() {
+        let (app, handle) = load("malformed", "(width: 8, builder: Nothing)");
+        let state = app.world().resource::<AssetServer>().load_state(&handle);
+        assert!(matches!(state, LoadState::Failed(_)), "{state:?}");
+    }
+}
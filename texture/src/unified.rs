@@ -0,0 +1,279 @@
+use std::{fmt::Debug, sync::OnceLock};
+
+use bevy::math::Vec2;
+
+use crate::{ImageBuilder, TexGenSeed, util::anisotropy};
+
+/// Base noise of a [`NoiseBuilder`], availability depends on the enabled backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoiseKind {
+    #[cfg(feature = "noise-rs")]
+    Perlin,
+    #[cfg(feature = "noise-rs")]
+    Simplex,
+    #[cfg(feature = "noise-rs")]
+    SuperSimplex,
+    #[cfg(feature = "noise-rs")]
+    Value,
+    /// Distance to the nearest cell point.
+    #[cfg(feature = "noiz")]
+    Worley,
+}
+
+impl Default for NoiseKind {
+    #[cfg(feature = "noise-rs")]
+    fn default() -> Self {
+        NoiseKind::Perlin
+    }
+
+    #[cfg(not(feature = "noise-rs"))]
+    fn default() -> Self {
+        NoiseKind::Worley
+    }
+}
+
+/// How octaves of a [`NoiseBuilder`] are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fractal {
+    /// A single octave.
+    #[default]
+    None,
+    /// Fractal brownian motion, sum of octaves.
+    Fbm,
+    /// Sharp ridges at the zero crossings of each octave.
+    Ridged,
+    /// Rounded billows, the absolute value of each octave.
+    Billow,
+}
+
+type Layer = Box<dyn Fn(Vec2) -> f32 + Send + Sync>;
+
+/// A noise builder with a consistent API across backends.
+///
+/// Gradient noises are provided by `noise-rs` with the `noise-rs` feature,
+/// [`NoiseKind::Worley`] is provided by `noiz` with the `noiz` feature.
+///
+/// ```rust
+/// # use bevy_texture_gen::*;
+/// let clouds = NoiseBuilder::simplex()
+///     .seeded(4)
+///     .frequency(3.)
+///     .fbm()
+///     .octaves(5);
+/// let image = clouds.to_image(64, 64);
+/// ```
+pub struct NoiseBuilder {
+    kind: NoiseKind,
+    fractal: Fractal,
+    seed: u32,
+    frequency: f32,
+    octaves: usize,
+    lacunarity: f32,
+    persistence: f32,
+    stretch: Vec2,
+    angle: f32,
+    layers: OnceLock<Vec<Layer>>,
+}
+
+impl Debug for NoiseBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoiseBuilder")
+            .field("kind", &self.kind)
+            .field("fractal", &self.fractal)
+            .field("seed", &self.seed)
+            .field("frequency", &self.frequency)
+            .field("octaves", &self.octaves)
+            .field("lacunarity", &self.lacunarity)
+            .field("persistence", &self.persistence)
+            .field("stretch", &self.stretch)
+            .field("angle", &self.angle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for NoiseBuilder {
+    fn clone(&self) -> Self {
+        NoiseBuilder {
+            layers: OnceLock::new(),
+            ..*self
+        }
+    }
+}
+
+impl Default for NoiseBuilder {
+    fn default() -> Self {
+        Self::new(NoiseKind::default())
+    }
+}
+
+impl NoiseBuilder {
+    /// Create a single octave noise with frequency `5`, seeded by [`TexGenSeed`].
+    pub fn new(kind: NoiseKind) -> Self {
+        NoiseBuilder {
+            kind,
+            fractal: Fractal::None,
+            seed: TexGenSeed::get(),
+            frequency: 5.,
+            octaves: 6,
+            lacunarity: 2.,
+            persistence: 0.5,
+            stretch: Vec2::ONE,
+            angle: 0.,
+            layers: OnceLock::new(),
+        }
+    }
+
+    #[cfg(feature = "noise-rs")]
+    pub fn perlin() -> Self {
+        Self::new(NoiseKind::Perlin)
+    }
+
+    #[cfg(feature = "noise-rs")]
+    pub fn simplex() -> Self {
+        Self::new(NoiseKind::Simplex)
+    }
+
+    #[cfg(feature = "noise-rs")]
+    pub fn super_simplex() -> Self {
+        Self::new(NoiseKind::SuperSimplex)
+    }
+
+    #[cfg(feature = "noise-rs")]
+    pub fn value() -> Self {
+        Self::new(NoiseKind::Value)
+    }
+
+    #[cfg(feature = "noiz")]
+    pub fn worley() -> Self {
+        Self::new(NoiseKind::Worley)
+    }
+
+    pub fn seeded(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self.layers = OnceLock::new();
+        self
+    }
+
+    /// Number of features in `0..1` of the first octave.
+    ///
+    /// [`NoiseKind::Worley`] rounds each octave's frequency to an integer.
+    pub fn frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self.layers = OnceLock::new();
+        self
+    }
+
+    /// Number of octaves if fractal, defaults to `6`.
+    pub fn octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves.max(1);
+        self.layers = OnceLock::new();
+        self
+    }
+
+    /// Frequency multiplier between octaves, defaults to `2`.
+    pub fn lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self.layers = OnceLock::new();
+        self
+    }
+
+    /// Amplitude multiplier between octaves, defaults to `0.5`.
+    pub fn persistence(mut self, persistence: f32) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Multiplies the frequency by `stretch` along axes rotated by `angle` in radians,
+    /// for streaky wind or wood grain patterns.
+    pub fn anisotropy(mut self, stretch: Vec2, angle: f32) -> Self {
+        self.stretch = stretch;
+        self.angle = angle;
+        self
+    }
+
+    pub fn fractal(mut self, fractal: Fractal) -> Self {
+        self.fractal = fractal;
+        self.layers = OnceLock::new();
+        self
+    }
+
+    pub fn fbm(self) -> Self {
+        self.fractal(Fractal::Fbm)
+    }
+
+    pub fn ridged(self) -> Self {
+        self.fractal(Fractal::Ridged)
+    }
+
+    pub fn billow(self) -> Self {
+        self.fractal(Fractal::Billow)
+    }
+
+    fn layers(&self) -> &[Layer] {
+        self.layers.get_or_init(|| {
+            let octaves = match self.fractal {
+                Fractal::None => 1,
+                _ => self.octaves,
+            };
+            (0..octaves)
+                .map(|i| {
+                    let frequency = self.frequency * self.lacunarity.powi(i as i32);
+                    layer(self.kind, self.seed.wrapping_add(i as u32), frequency)
+                })
+                .collect()
+        })
+    }
+}
+
+/// Create a single octave in `-1..1`.
+fn layer(kind: NoiseKind, seed: u32, frequency: f32) -> Layer {
+    #[cfg(feature = "noise-rs")]
+    fn noise_rs(
+        noise: impl noise::NoiseFn<f64, 2> + Send + Sync + 'static,
+        frequency: f32,
+    ) -> Layer {
+        Box::new(move |position| noise.get((position * frequency).as_dvec2().to_array()) as f32)
+    }
+    match kind {
+        #[cfg(feature = "noise-rs")]
+        NoiseKind::Perlin => noise_rs(noise::Perlin::new(seed), frequency),
+        #[cfg(feature = "noise-rs")]
+        NoiseKind::Simplex => noise_rs(noise::Simplex::new(seed), frequency),
+        #[cfg(feature = "noise-rs")]
+        NoiseKind::SuperSimplex => noise_rs(noise::SuperSimplex::new(seed), frequency),
+        #[cfg(feature = "noise-rs")]
+        NoiseKind::Value => noise_rs(noise::Value::new(seed), frequency),
+        #[cfg(feature = "noiz")]
+        NoiseKind::Worley => {
+            let voronoi = crate::VoronoiImage::new_seeded(frequency.round().max(1.) as i32, seed);
+            Box::new(move |position| voronoi.sample(position) * 2. - 1.)
+        }
+    }
+}
+
+impl ImageBuilder for NoiseBuilder {
+    fn sample(&self, position: Vec2) -> f32 {
+        let position = anisotropy(self.stretch, self.angle).transform_point2(position);
+        let layers = self.layers();
+        if self.fractal == Fractal::None {
+            return layers[0](position) * 0.5 + 0.5;
+        }
+        let mut amplitude = 1.;
+        let mut sum = 0.;
+        let mut total = 0.;
+        for layer in layers {
+            let n = layer(position);
+            let n = match self.fractal {
+                Fractal::Ridged => 1. - 2. * n.abs(),
+                Fractal::Billow => 2. * n.abs() - 1.,
+                _ => n,
+            };
+            sum += n * amplitude;
+            total += amplitude;
+            amplitude *= self.persistence;
+        }
+        (sum / total) * 0.5 + 0.5
+    }
+}
@@ -1,17 +1,67 @@
-use bevy::math::Vec2;
+use bevy::{
+    color::{Color, ColorToComponents},
+    math::{Affine2, Mat2, Vec2, Vec4},
+};
 
-pub trait AsVec2 {
-    fn as_vec2(&self) -> Vec2;
+use crate::ImageBuilder;
+
+/// Integer hash with good avalanche, used for per-cell randomness.
+pub(crate) fn hash_u32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
+
+/// Hash a 2d integer coordinate with a seed.
+pub(crate) fn hash2(x: i32, y: i32, seed: u32) -> u32 {
+    hash_u32(hash_u32(hash_u32(seed) ^ x as u32) ^ y as u32)
+}
+
+/// Convert a hash to a float in `0..1`.
+pub(crate) fn hash_to_unit(hash: u32) -> f32 {
+    (hash >> 8) as f32 / (1u32 << 24) as f32
 }
 
-impl AsVec2 for f32 {
-    fn as_vec2(&self) -> Vec2 {
-        Vec2::splat(*self)
+/// Obtain the stored values of a color without color space conversion.
+pub(crate) fn color_to_raw(color: Color) -> Vec4 {
+    match color {
+        Color::Srgba(c) => c.to_vec4(),
+        Color::LinearRgba(c) => c.to_vec4(),
+        c => c.to_linear().to_vec4(),
     }
 }
 
-impl AsVec2 for Vec2 {
-    fn as_vec2(&self) -> Vec2 {
-        *self
+/// Find the values at the `low` and `high` percentiles of a builder on a `128x128` grid.
+pub(crate) fn sample_range(builder: &impl ImageBuilder, low: f32, high: f32) -> (f32, f32) {
+    const N: usize = 128;
+    let mut values = Vec::with_capacity(N * N);
+    for y in 0..N {
+        for x in 0..N {
+            let position = Vec2::new(x as f32, y as f32) / (N - 1) as f32;
+            values.push(builder.sample(position));
+        }
     }
+    values.sort_by(f32::total_cmp);
+    let last = (values.len() - 1) as f32;
+    let at = |p: f32| values[(p.clamp(0., 1.) * last).round() as usize];
+    (at(low), at(high))
+}
+
+/// Offset of the `i`th supersample of a pixel in `-0.5..0.5`.
+///
+/// Uses the R2 low discrepancy sequence, shifted per pixel to avoid visible patterns.
+pub(crate) fn supersample_offset(x: usize, y: usize, i: usize) -> Vec2 {
+    const A1: f32 = 0.754_877_7;
+    const A2: f32 = 0.569_840_3;
+    let hash = hash2(x as i32, y as i32, 0);
+    let shift = Vec2::new(hash_to_unit(hash), hash_to_unit(hash_u32(hash)));
+    (Vec2::new(A1, A2) * i as f32 + shift).fract() - Vec2::splat(0.5)
+}
+
+/// Scale frequency by `stretch` along axes rotated by `angle`.
+pub(crate) fn anisotropy(stretch: Vec2, angle: f32) -> Affine2 {
+    Affine2::from_mat2(Mat2::from_diagonal(stretch) * Mat2::from_angle(-angle))
 }
@@ -1,23 +1,93 @@
-use bevy::math::Vec2;
+use std::{borrow::Cow, fmt::Debug};
+
+use bevy::math::{Vec2, Vec4};
 use noiz::{
     Noise, SampleableFor,
+    cell_noise::{PerNearestPoint, WorleyDifference, WorleySecondLeastDistance},
     cells::{OrthoGrid, Voronoi},
-    prelude::{EuclideanLength, PerCellPointDistances, WorleyLeastDistance},
-    rng::NoiseRng,
+    lengths::{ChebyshevLength, EuclideanLength, ManhattanLength, MinkowskiLength},
+    prelude::{PerCellPointDistances, WorleyLeastDistance},
+    rng::{NoiseRng, Random, UNorm},
 };
 
-use crate::ImageBuilder;
+use crate::{
+    ImageBuilder, TexGenSeed,
+    util::{anisotropy, hash_to_unit, hash_u32},
+};
 
+#[deprecated(note = "VoronoiImage is configured by its fields, see `VoronoiImage::from_noise`.")]
 pub type VoronoiNoise = Noise<
     PerCellPointDistances<Voronoi<false, OrthoGrid<i32>>, EuclideanLength, WorleyLeastDistance>,
 >;
 
+/// Output of a [`VoronoiImage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoronoiOutput {
+    /// Distance to the nearest point, or `F1`.
+    #[default]
+    Distance,
+    /// Distance to the second nearest point, or `F2`.
+    SecondDistance,
+    /// `F2 - F1`, dark on cell edges.
+    Edges,
+    /// A random grayscale value per cell.
+    CellValue,
+    /// A random color per cell.
+    CellColor,
+}
+
+/// Distance metric of a [`VoronoiImage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoronoiDistance {
+    /// Round cells.
+    #[default]
+    Euclidean,
+    /// Diamond shaped cells with diagonal edges.
+    Manhattan,
+    /// Square cells with axis aligned edges.
+    Chebyshev,
+    /// Generalized distance, `1` is Manhattan, `2` is Euclidean,
+    /// values below `1` produce star shaped cells.
+    Minkowski(f32),
+}
+
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(deprecated)]
 pub struct VoronoiImage {
-    pub noise: Noise<
-        PerCellPointDistances<Voronoi<false, OrthoGrid<i32>>, EuclideanLength, WorleyLeastDistance>,
-    >,
+    #[deprecated(note = "Unused, set `frequency` and `seed` instead.")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub noise: VoronoiNoise,
+    pub frequency: i32,
+    pub seed: u32,
     /// If some, 3d, else 2d.
     pub z: Option<f32>,
+    pub output: VoronoiOutput,
+    pub distance: VoronoiDistance,
+    /// Frequency multiplier along the axes rotated by `angle`.
+    pub stretch: Vec2,
+    /// Angle of the anisotropy axes in radians.
+    pub angle: f32,
+    /// Noise built by constructors and setters, if `frequency`, `seed` or `distance`
+    /// no longer match, noise is built on every sample instead.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cache: VoronoiCache,
+}
+
+impl Debug for VoronoiImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoronoiImage")
+            .field("frequency", &self.frequency)
+            .field("seed", &self.seed)
+            .field("z", &self.z)
+            .field("output", &self.output)
+            .field("distance", &self.distance)
+            .field("stretch", &self.stretch)
+            .field("angle", &self.angle)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for VoronoiImage {
@@ -26,62 +96,277 @@ impl Default for VoronoiImage {
     }
 }
 
+type Cells = Voronoi<false, OrthoGrid<i32>>;
+
+/// Every output of a [`VoronoiImage`] for one distance metric.
+#[derive(Clone, Copy)]
+struct VoronoiNoises<L> {
+    distance: Noise<PerCellPointDistances<Cells, L, WorleyLeastDistance>>,
+    second_distance: Noise<PerCellPointDistances<Cells, L, WorleySecondLeastDistance>>,
+    edges: Noise<PerCellPointDistances<Cells, L, WorleyDifference>>,
+    cell_value: Noise<PerNearestPoint<Cells, L, Random<UNorm, f32>>>,
+}
+
+impl<L: Copy> VoronoiNoises<L> {
+    fn new(frequency: i32, seed: u32, length_mode: L) -> Self {
+        let mut cells = Cells::default();
+        cells.partitoner.0 = frequency;
+        VoronoiNoises {
+            distance: seeded(
+                PerCellPointDistances {
+                    cells,
+                    length_mode,
+                    worley_mode: WorleyLeastDistance,
+                },
+                frequency,
+                seed,
+            ),
+            second_distance: seeded(
+                PerCellPointDistances {
+                    cells,
+                    length_mode,
+                    worley_mode: WorleySecondLeastDistance,
+                },
+                frequency,
+                seed,
+            ),
+            edges: seeded(
+                PerCellPointDistances {
+                    cells,
+                    length_mode,
+                    worley_mode: WorleyDifference,
+                },
+                frequency,
+                seed,
+            ),
+            cell_value: seeded(
+                PerNearestPoint {
+                    cells,
+                    length_mode,
+                    noise: Random::default(),
+                },
+                frequency,
+                seed,
+            ),
+        }
+    }
+}
+
+fn seeded<N>(noise: N, frequency: i32, seed: u32) -> Noise<N> {
+    Noise {
+        noise,
+        seed: NoiseRng(seed),
+        frequency: frequency as f32,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum VoronoiLengths {
+    Euclidean(VoronoiNoises<EuclideanLength>),
+    Manhattan(VoronoiNoises<ManhattanLength>),
+    Chebyshev(VoronoiNoises<ChebyshevLength>),
+    Minkowski(VoronoiNoises<MinkowskiLength>),
+}
+
+/// Noise of a [`VoronoiImage`] and the parameters it was built with.
+#[derive(Clone, Copy)]
+pub struct VoronoiCache {
+    frequency: i32,
+    seed: u32,
+    distance: VoronoiDistance,
+    noises: VoronoiLengths,
+}
+
+impl Debug for VoronoiCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoronoiCache")
+            .field("frequency", &self.frequency)
+            .field("seed", &self.seed)
+            .field("distance", &self.distance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for VoronoiCache {
+    fn default() -> Self {
+        VoronoiCache::new(0, 0, VoronoiDistance::Euclidean)
+    }
+}
+
+impl VoronoiCache {
+    fn new(frequency: i32, seed: u32, distance: VoronoiDistance) -> Self {
+        let noises = match distance {
+            VoronoiDistance::Euclidean => {
+                VoronoiLengths::Euclidean(VoronoiNoises::new(frequency, seed, EuclideanLength))
+            }
+            VoronoiDistance::Manhattan => {
+                VoronoiLengths::Manhattan(VoronoiNoises::new(frequency, seed, ManhattanLength))
+            }
+            VoronoiDistance::Chebyshev => {
+                VoronoiLengths::Chebyshev(VoronoiNoises::new(frequency, seed, ChebyshevLength))
+            }
+            VoronoiDistance::Minkowski(p) => {
+                VoronoiLengths::Minkowski(VoronoiNoises::new(frequency, seed, MinkowskiLength(p)))
+            }
+        };
+        VoronoiCache {
+            frequency,
+            seed,
+            distance,
+            noises,
+        }
+    }
+}
+
+macro_rules! sample_voronoi {
+    ($this: expr, $position: expr, $output: ident) => {{
+        let cache = $this.cache();
+        match &cache.noises {
+            VoronoiLengths::Euclidean(noises) => sample_voronoi!($this, $position, noises.$output;),
+            VoronoiLengths::Manhattan(noises) => sample_voronoi!($this, $position, noises.$output;),
+            VoronoiLengths::Chebyshev(noises) => sample_voronoi!($this, $position, noises.$output;),
+            VoronoiLengths::Minkowski(noises) => sample_voronoi!($this, $position, noises.$output;),
+        }
+    }};
+    ($this: expr, $position: expr, $noise: expr;) => {{
+        let result: f32 = match $this.z {
+            Some(z) => $noise.sample($position.extend(z)),
+            None => $noise.sample($position),
+        };
+        result
+    }};
+}
+
 impl VoronoiImage {
     pub fn new(frequency: i32) -> Self {
-        let mut noise = VoronoiNoise::default();
-        noise.frequency = frequency as f32;
-        noise.noise.cells.partitoner.0 = frequency;
-        Self { noise, z: None }
+        Self::new_seeded(frequency, TexGenSeed::get())
     }
 
     pub fn new3d(frequency: i32) -> Self {
-        let mut noise = VoronoiNoise::default();
-        noise.frequency = frequency as f32;
-        noise.noise.cells.partitoner.0 = frequency;
-        Self { noise, z: Some(0.) }
+        Self::new3d_seeded(frequency, TexGenSeed::get())
     }
+
+    #[allow(deprecated)]
     pub fn new_seeded(frequency: i32, seed: u32) -> Self {
-        let mut noise = VoronoiNoise::default();
-        noise.frequency = frequency as f32;
-        noise.noise.cells.partitoner.0 = frequency;
-        noise.seed = NoiseRng(seed);
-        Self { noise, z: None }
+        Self {
+            noise: VoronoiNoise::default(),
+            frequency,
+            seed,
+            z: None,
+            output: VoronoiOutput::Distance,
+            distance: VoronoiDistance::Euclidean,
+            stretch: Vec2::ONE,
+            angle: 0.,
+            cache: VoronoiCache::new(frequency, seed, VoronoiDistance::Euclidean),
+        }
     }
 
     pub fn new3d_seeded(frequency: i32, seed: u32) -> Self {
-        let mut noise = VoronoiNoise::default();
-        noise.frequency = frequency as f32;
-        noise.noise.cells.partitoner.0 = frequency;
-        noise.seed = NoiseRng(seed);
-        Self { noise, z: Some(0.) }
+        Self {
+            z: Some(0.),
+            ..Self::new_seeded(frequency, seed)
+        }
+    }
+
+    /// Create from the noise previously stored in `VoronoiImage::noise`,
+    /// keeps its frequency and seed.
+    #[deprecated(note = "Use `VoronoiImage::new_seeded` instead.")]
+    #[allow(deprecated)]
+    pub fn from_noise(noise: VoronoiNoise) -> Self {
+        Self::new_seeded(noise.noise.cells.partitoner.0, noise.seed.0)
+    }
+
+    /// Sets the value returned by the Worley cells.
+    pub fn set_output(mut self, output: VoronoiOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Sets the distance function used by the Worley cells.
+    pub fn set_distance_function(mut self, distance: VoronoiDistance) -> Self {
+        self.distance = distance;
+        self.cache = VoronoiCache::new(self.frequency, self.seed, distance);
+        self
+    }
+
+    /// Stretches cells, `Vec2::new(0.2, 1.)` produces cells elongated along `angle` in radians.
+    pub fn set_anisotropy(mut self, stretch: Vec2, angle: f32) -> Self {
+        self.stretch = stretch;
+        self.angle = angle;
+        self
     }
 
-    // /// Sets the distance function used by the Worley cells.
-    // pub fn set_distance_function(mut self, function: impl Fn(Vec2) -> f32 + 'static) -> Self {
-    //     self.noise.noise.length_mode =
-    //     self
-    // }
+    fn input(&self, position: Vec2) -> Vec2 {
+        if self.stretch == Vec2::ONE && self.angle == 0. {
+            return position;
+        }
+        anisotropy(self.stretch, self.angle).transform_point2(position)
+    }
 
-    // /// Enables or disables applying the distance from the nearest seed point
-    // /// to the output value.
-    // pub fn set_return_type(mut self, return_type: ReturnType) -> Self {
-    //     self.noise = self.noise.set_return_type(return_type);
-    //     self
-    // }
+    fn cache(&self) -> Cow<'_, VoronoiCache> {
+        let cache = &self.cache;
+        if (cache.frequency, cache.seed, cache.distance)
+            == (self.frequency, self.seed, self.distance)
+        {
+            Cow::Borrowed(cache)
+        } else {
+            Cow::Owned(VoronoiCache::new(self.frequency, self.seed, self.distance))
+        }
+    }
 
-    // /// Sets the frequency of the seed points.
-    // pub fn set_frequency(mut self, frequency: usize) -> Self {
-    //     self.noise = self.noise.set_frequency(frequency as f64);
-    //     self
-    // }
+    fn cell_value(&self, position: Vec2) -> f32 {
+        sample_voronoi!(self, position, cell_value)
+    }
 }
 
 impl ImageBuilder for VoronoiImage {
     fn sample(&self, position: Vec2) -> f32 {
-        if let Some(z) = self.z {
-            self.noise.sample(position.extend(z))
-        } else {
-            self.noise.sample(position)
+        let position = self.input(position);
+        match self.output {
+            VoronoiOutput::Distance => sample_voronoi!(self, position, distance),
+            VoronoiOutput::SecondDistance => sample_voronoi!(self, position, second_distance),
+            VoronoiOutput::Edges => sample_voronoi!(self, position, edges),
+            VoronoiOutput::CellValue => self.cell_value(position),
+            VoronoiOutput::CellColor => self.sample_color(position).x,
+        }
+    }
+
+    fn sample_color(&self, position: Vec2) -> Vec4 {
+        if self.output != VoronoiOutput::CellColor {
+            let x = self.sample(position);
+            return Vec4::new(x, x, x, 1.);
+        }
+        let hash = hash_u32(self.cell_value(self.input(position)).to_bits());
+        Vec4::new(
+            hash_to_unit(hash),
+            hash_to_unit(hash_u32(hash ^ 0x68bc21eb)),
+            hash_to_unit(hash_u32(hash ^ 0x02e5be93)),
+            1.,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::Vec2;
+
+    use crate::{ImageBuilder, VoronoiDistance, VoronoiImage, VoronoiOutput};
+
+    #[test]
+    fn cached_matches_modified() {
+        let cached = VoronoiImage::new_seeded(4, 9)
+            .set_output(VoronoiOutput::Edges)
+            .set_distance_function(VoronoiDistance::Minkowski(0.7));
+        let mut modified = VoronoiImage::new_seeded(2, 1).set_output(VoronoiOutput::Edges);
+        modified.frequency = 4;
+        modified.seed = 9;
+        modified.distance = VoronoiDistance::Minkowski(0.7);
+        for position in [
+            Vec2::new(0.1, 0.2),
+            Vec2::new(0.77, 0.4),
+            Vec2::new(0.5, 0.9),
+        ] {
+            assert_eq!(cached.sample(position), modified.sample(position));
         }
     }
 }